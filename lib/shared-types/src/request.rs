@@ -3,8 +3,14 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use shotmarker_csv_parser::{
+    standings::StandingsRules,
+    string::ScoringConvention,
+};
 use uuid::Uuid;
 
+use crate::response::Role;
+
 #[remain::sorted]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum LeagueOperation {
@@ -26,6 +32,14 @@ pub enum LeagueOperation {
         id:          Uuid,
         league_name: String,
     },
+    SetScoringConvention {
+        id:                 Uuid,
+        scoring_convention: ScoringConvention,
+    },
+    SetStandingsRules {
+        id:              Uuid,
+        standings_rules: StandingsRules,
+    },
     SetStartDate {
         id:         Uuid,
         start_date: Option<NaiveDate>,
@@ -50,6 +64,10 @@ pub enum MatchOperation {
         id:   Uuid,
         name: String,
     },
+    SetShotsPerString {
+        id:               Uuid,
+        shots_per_string: Option<u32>,
+    },
 }
 
 impl std::default::Default for MatchOperation {
@@ -81,12 +99,183 @@ pub enum ClassOperation {
     },
 }
 
+/// Changes to a league's shooters. A shooter's default class must be one of the league's classes.
+#[remain::sorted]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ShooterOperation {
+    /// Adds another name the shooter's strings are given, so that strings with it are assigned to
+    /// them on upload.
+    AddAlias {
+        id:    Uuid,
+        alias: String,
+    },
+    Create {
+        name:             String,
+        default_class_id: Option<Uuid>,
+    },
+    Delete {
+        id: Uuid,
+    },
+    RemoveAlias {
+        id:    Uuid,
+        alias: String,
+    },
+    SetDefaultClass {
+        id:               Uuid,
+        default_class_id: Option<Uuid>,
+    },
+    SetName {
+        id:   Uuid,
+        name: String,
+    },
+}
+
+/// Changes to an uploaded shot string: attributing it to a shooter and class, or tidying up its
+/// shots when the shooter didn't on the ShotMarker. The string's score is recomputed from its shots
+/// after each edit to them, and the uploaded values are kept so that the edits can be reverted.
+#[remain::sorted]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum StringOperation {
+    /// Assigns the string to the shooter and class proposed for it, and remembers the shooter's
+    /// name as it appears in the string as one of their aliases.
+    AcceptShooterProposal {
+        id: Uuid,
+    },
+    /// Attributes the string to a shooter, in `class_id` or, when that isn't given, the shooter's
+    /// default class.
+    AssignShooter {
+        id:         Uuid,
+        shooter_id: Uuid,
+        class_id:   Option<Uuid>,
+    },
+    /// Converts the last `count` sighters to record shots, as F-Class rules allow.
+    ConvertSighters {
+        id:    Uuid,
+        count: usize,
+    },
+    /// Hides a shot, so that it no longer counts towards the string's score.
+    DropShot {
+        id:      Uuid,
+        shot_id: Uuid,
+    },
+    /// Moves the shots of `other_id` into the string, such as when a shooter's string was broken
+    /// in two by restarting the ShotMarker, and removes `other_id`. Each shot keeps the export it
+    /// was uploaded in.
+    MergeStrings {
+        id:       Uuid,
+        other_id: Uuid,
+    },
+    /// Matches the string's name against the league's shooters again, such as after adding
+    /// shooters or aliases. Strings already assigned to a shooter are left as they are.
+    ProposeShooter {
+        id: Uuid,
+    },
+    /// Restores the string's shots and score to how they were uploaded. Merged and split strings
+    /// stay that way.
+    RevertEdits {
+        id: Uuid,
+    },
+    /// Restores a shot's tags to how they were uploaded, such as to undo converting it from a
+    /// sighter or dropping it. Once none of the string's shots are edited, its score is restored
+    /// too.
+    RevertShot {
+        id:      Uuid,
+        shot_id: Uuid,
+    },
+    /// Sets or clears the class the string is scored in, keeping its shooter.
+    SetClass {
+        id:       Uuid,
+        class_id: Option<Uuid>,
+    },
+    /// Moves the shots from `at_shot_id` onwards into a new string, such as when two shooters
+    /// shared a target and their shots ended up in one string.
+    SplitString {
+        id:         Uuid,
+        at_shot_id: Uuid,
+    },
+    /// Clears the string's shooter and class.
+    UnassignShooter {
+        id: Uuid,
+    },
+    UnhideShot {
+        id:      Uuid,
+        shot_id: Uuid,
+    },
+}
+
+impl StringOperation {
+    /// The string being edited.
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::AcceptShooterProposal {
+                id,
+            }
+            | Self::AssignShooter {
+                id,
+                ..
+            }
+            | Self::ConvertSighters {
+                id,
+                ..
+            }
+            | Self::DropShot {
+                id,
+                ..
+            }
+            | Self::MergeStrings {
+                id,
+                ..
+            }
+            | Self::ProposeShooter {
+                id,
+            }
+            | Self::RevertEdits {
+                id,
+            }
+            | Self::RevertShot {
+                id,
+                ..
+            }
+            | Self::SetClass {
+                id,
+                ..
+            }
+            | Self::SplitString {
+                id,
+                ..
+            }
+            | Self::UnassignShooter {
+                id,
+            }
+            | Self::UnhideShot {
+                id,
+                ..
+            } => *id,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SmCsvExportUpload {
     pub filename: String,
     pub content:  String,
 }
 
+/// Changes to which roles a user has, by username. Granting a role a user already has, or revoking
+/// one they don't, changes nothing.
+#[remain::sorted]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RoleOperation {
+    Grant {
+        username: String,
+        role:     Role,
+    },
+    Revoke {
+        username: String,
+        role:     Role,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Login {
     pub username: String,
@@ -95,6 +284,31 @@ pub struct Login {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RegisterUser {
-    pub username: String,
-    pub password: String,
+    pub username:    String,
+    pub password:    String,
+    /// Needed when registration is invite-only.
+    #[serde(default)]
+    pub invite_code: Option<String>,
+}
+
+/// Admins' changes to user accounts, by username.
+#[remain::sorted]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum UserOperation {
+    /// Creates an account, however registration is set up.
+    Create {
+        username: String,
+        password: String,
+    },
+    /// Stops the user from logging in, and logs them out.
+    Disable {
+        username: String,
+    },
+    Enable {
+        username: String,
+    },
+    ResetPassword {
+        username: String,
+        password: String,
+    },
 }
@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
 use chrono::{
     DateTime,
     NaiveDate,
+    NaiveDateTime,
     NaiveTime,
     Utc,
 };
@@ -8,29 +11,72 @@ use serde::{
     Deserialize,
     Serialize,
 };
-use shotmarker_csv_parser::string::StringScore;
-pub use shotmarker_csv_parser::string::shot::{
-    ShotPosition,
-    ShotScore,
-    ShotVelocity,
+pub use shotmarker_csv_parser::{
+    analytics::{
+        GroupMeasurements,
+        GroupOptions,
+        GroupShot,
+        GroupStatistics,
+        Spread,
+    },
+    error::ParseWarningKind,
+    matching::KnownShooter,
+    results::CountBack,
+    standings::{
+        StandingsRules,
+        TieBreaker,
+    },
+    string::{
+        ScoringConvention,
+        ShotMarkerStringMetrics,
+        distance::{
+            Distance,
+            DistanceUnit,
+            StringDistance,
+        },
+        shot::{
+            ShotPosition,
+            ShotScore,
+            ShotTag,
+            ShotTags,
+            ShotVelocity,
+        },
+        target::StringTarget,
+    },
+    target_face::TargetFace,
+    units::MILLIMETERS_PER_INCH,
+    validation::{
+        ScoredShot,
+        ValidationFinding,
+    },
+};
+use shotmarker_csv_parser::{
+    error::ParseWarning,
+    string::StringScore,
 };
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct League {
-    pub id:          Uuid,
-    pub name:        String,
-    pub description: Option<String>,
-    pub created_at:  DateTime<Utc>,
-    pub start_date:  Option<NaiveDate>,
-    pub end_date:    Option<NaiveDate>,
+    pub id:                 Uuid,
+    pub name:               String,
+    pub description:        Option<String>,
+    pub created_at:         DateTime<Utc>,
+    pub start_date:         Option<NaiveDate>,
+    pub end_date:           Option<NaiveDate>,
+    pub scoring_convention: ScoringConvention,
+    #[serde(default)]
+    pub standings_rules:    StandingsRules,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Match {
-    pub id:         Uuid,
-    pub name:       String,
-    pub event_date: NaiveDate,
+    pub id:               Uuid,
+    pub name:             String,
+    pub event_date:       NaiveDate,
+    /// The number of scored shots in each string of the course of fire, which uploaded strings
+    /// are checked against.
+    pub shots_per_string: Option<u32>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
@@ -43,32 +89,125 @@ pub struct ShotMarkerExport {
     pub match_id:       Uuid,
 }
 
+/// The result of uploading a ShotMarker export. Shot rows and strings that couldn't be parsed are
+/// skipped rather than failing the upload, and described in `warnings`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SmCsvExportUploaded {
+    pub export_id: Uuid,
+    pub warnings:  Vec<ExportWarning>,
+}
+
+/// Part of an uploaded export that was skipped, and where in the export it is.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExportWarning {
+    pub kind:          ParseWarningKind,
+    /// Why it couldn't be parsed.
+    pub message:       String,
+    /// 1-based line number within the export.
+    pub line:          usize,
+    /// 1-based column (in characters) within `line`.
+    pub column:        usize,
+    /// The shot column being parsed, such as `x (mm)` or `v (fps)`.
+    pub field:         Option<String>,
+    /// The header line of the shot string being parsed.
+    pub string_header: Option<String>,
+}
+
+impl From<&ParseWarning> for ExportWarning {
+    fn from(warning: &ParseWarning) -> Self {
+        ExportWarning {
+            kind:          warning.kind,
+            message:       warning.error.kind.to_string(),
+            line:          warning.error.line,
+            column:        warning.error.column,
+            field:         warning.error.field.clone(),
+            string_header: warning.error.string_header.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at line {}, column {}", self.kind, self.line, self.column)?;
+        if let Some(field) = &self.field {
+            write!(f, " ({field})")?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ShotMarkerShotString {
-    pub id:          Uuid,
-    pub string_date: NaiveDate,
-    pub string_name: String,
-    pub target:      String,
-    pub distance:    String,
-    pub score:       StringScore,
-    pub export_id:   Uuid,
-    pub shooter_id:  Option<Uuid>,
-    pub class_id:    Option<Uuid>,
+    pub id:                  Uuid,
+    pub string_date:         NaiveDate,
+    pub string_name:         String,
+    pub target:              StringTarget,
+    pub distance:            StringDistance,
+    pub score:               StringScore,
+    pub metrics:             Option<ShotMarkerStringMetrics>,
+    pub export_id:           Uuid,
+    pub shooter_id:          Option<Uuid>,
+    pub class_id:            Option<Uuid>,
+    /// What checking the string on upload found to need review. `None` for strings uploaded
+    /// before strings were checked.
+    pub validation_findings: Option<Vec<ValidationFinding>>,
+    /// Whether the string's shots or score have been edited since it was uploaded. See
+    /// [`crate::request::StringOperation`].
+    pub edited:              bool,
+    /// The shooter the string's name most likely belongs to, when it wasn't a close enough match
+    /// to be assigned without review.
+    pub shooter_proposal:    Option<ShooterProposal>,
+}
+
+/// A shooter and class proposed for a string from its name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShooterProposal {
+    pub shooter_id: Uuid,
+    pub class_id:   Option<Uuid>,
+    /// How similar the string's name is to the shooter's name or one of their aliases, up to 1.0.
+    pub confidence: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ShotMarkerShot {
-    pub id:             Uuid,
-    pub shot_time:      NaiveTime,
-    pub shot_id:        String,
-    pub tags:           String,
-    pub score:          ShotScore,
-    pub position:       ShotPosition,
-    pub velocity:       ShotVelocity,
-    pub yaw:            f64,
-    pub pitch:          f64,
-    pub quality:        Option<String>,
-    pub shot_string_id: Uuid,
+    pub id:                 Uuid,
+    pub shot_time:          NaiveTime,
+    /// The ShotMarker's local time the shot was fired at.
+    pub shot_date_time:     NaiveDateTime,
+    /// The instant the shot was fired, when the server knows the time zone ShotMarker clocks are
+    /// set to.
+    #[serde(default)]
+    pub shot_utc_date_time: Option<DateTime<Utc>>,
+    pub shot_id:            String,
+    pub tags:               ShotTags,
+    pub score:              ShotScore,
+    pub position:           ShotPosition,
+    pub velocity:           ShotVelocity,
+    pub yaw:                f64,
+    pub pitch:              f64,
+    pub quality:            Option<String>,
+    /// Values from shot columns the parser doesn't know about, keyed by column name.
+    #[serde(default)]
+    pub extras:             BTreeMap<String, String>,
+    pub shot_string_id:     Uuid,
+    /// The export the shot was uploaded in. This is normally the string's export, but not once
+    /// strings from different exports have been merged.
+    pub export_id:          Uuid,
+}
+
+impl GroupShot for ShotMarkerShot {
+    fn position(&self) -> &ShotPosition { &self.position }
+
+    fn velocity(&self) -> &ShotVelocity { &self.velocity }
+
+    fn tags(&self) -> &ShotTags { &self.tags }
+}
+
+impl ScoredShot for ShotMarkerShot {
+    fn score(&self) -> ShotScore { self.score }
+
+    fn tags(&self) -> &ShotTags { &self.tags }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -84,10 +223,199 @@ pub struct Shooter {
     pub id:               Uuid,
     pub name:             String,
     pub default_class_id: Option<Uuid>,
+    pub league_id:        Uuid,
+    /// Other names the shooter's strings are given, such as misspellings of their name.
+    pub aliases:          Vec<String>,
+}
+
+impl KnownShooter for Shooter {
+    fn name(&self) -> &str { &self.name }
+
+    fn aliases(&self) -> &[String] { &self.aliases }
+}
+
+/// A match's results, with the shooters in each class ranked by their total over their strings.
+/// Ties are broken by X count, and then by count-back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MatchResults {
+    pub match_id:             Uuid,
+    /// The classes in name order, followed by the shooters whose strings aren't in a class.
+    pub classes:              Vec<ClassResults>,
+    /// The number of the match's strings not yet assigned to a shooter, which aren't counted.
+    pub unattributed_strings: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClassResults {
+    /// `None` for strings that haven't been put in a class.
+    pub class:   Option<Class>,
+    /// The class's shooters, best first.
+    pub entries: Vec<ResultEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResultEntry {
+    /// The shooter's place in the class, starting from 1. Shooters still tied after count-back
+    /// share a place.
+    pub place:        usize,
+    pub shooter_id:   Uuid,
+    pub shooter_name: String,
+    pub score:        StringScore,
+    pub count_back:   CountBack,
+    /// The shooter's strings in the class, in the order they were fired.
+    pub string_ids:   Vec<Uuid>,
+}
+
+/// A league's season standings: each shooter's total over the matches that count under the
+/// league's rules, ranked within each class.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeagueStandings {
+    pub league_id: Uuid,
+    pub rules:     StandingsRules,
+    /// The classes in name order, followed by the shooters whose strings aren't in a class.
+    pub classes:   Vec<ClassStandings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClassStandings {
+    /// `None` for strings that haven't been put in a class.
+    pub class:   Option<Class>,
+    /// The class's shooters, best first, with those yet to qualify last.
+    pub entries: Vec<StandingsEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StandingsEntry {
+    /// The shooter's place in the class, starting from 1, or `None` if they haven't shot enough
+    /// matches to qualify. Shooters still tied after the tie-breakers share a place.
+    pub place:        Option<usize>,
+    pub shooter_id:   Uuid,
+    pub shooter_name: String,
+    /// The total of the shooter's counted matches.
+    pub score:        StringScore,
+    pub qualified:    bool,
+    /// The shooter's result in each match they shot in the class, in date order.
+    pub matches:      Vec<MatchContribution>,
+}
+
+/// A shooter's result in one match, and whether it counts towards their season total.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MatchContribution {
+    pub match_id:   Uuid,
+    pub match_name: String,
+    pub event_date: NaiveDate,
+    /// The shooter's place in the class in the match.
+    pub place:      usize,
+    pub score:      StringScore,
+    /// Whether the match was dropped under the league's rules, and so isn't counted.
+    pub dropped:    bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct User {
     pub id:       Uuid,
     pub username: String,
+    /// Disabled users can't log in.
+    pub active:   bool,
+    #[serde(default)]
+    pub roles:    Vec<Role>,
+}
+
+/// A code that lets someone register when registration is invite-only. Each can be used once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Invite {
+    pub code:       String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a user may change. Anyone may see leagues and their results; changing them takes a role.
+#[remain::sorted]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Runs a league: its details, classes, matches and shooters, as well as its scoring.
+    LeagueOfficer {
+        league_id: Uuid,
+    },
+    /// Uploads a league's exports, and edits and assigns their strings.
+    Scorer {
+        league_id: Uuid,
+    },
+    /// Shoots in a league, without being able to change it.
+    Shooter {
+        league_id: Uuid,
+    },
+    /// Can change anything, including which leagues there are and who has which roles.
+    SiteAdmin,
+}
+
+/// The body of the API's error responses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiError {
+    pub code:    ApiErrorCode,
+    pub message: String,
+    /// Where in the request the error is, when it's about part of it: the line and column an
+    /// upload failed to parse at, for example.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.details.is_empty() {
+            let details: Vec<_> =
+                self.details.iter().map(|(field, detail)| format!("{field}: {detail}")).collect();
+            write!(f, " ({})", details.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// What kind of error an [`ApiError`] is. Codes are stable, so clients can match on them rather
+/// than on messages.
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiErrorCode {
+    AccountDisabled,
+    /// The request needs a user that isn't logged in.
+    AlreadyLoggedIn,
+    ClassNotFound,
+    /// The change conflicts with existing data, such as a duplicate.
+    Conflict,
+    /// A name can't be empty.
+    EmptyName,
+    ExportNotFound,
+    /// The user doesn't have a role that permits the change.
+    Forbidden,
+    /// Something went wrong on the server. The details are in its log.
+    Internal,
+    InvalidCredentials,
+    /// A league can't end before it starts.
+    InvalidDateRange,
+    /// The invite code is unknown, or has already been used.
+    InvalidInvite,
+    /// The site's only active admin can't have the role revoked, or be disabled.
+    LastSiteAdmin,
+    LeagueNotFound,
+    MatchNotFound,
+    MergeWithItself,
+    NoShooterProposal,
+    NotEnoughSighters,
+    /// Something the request refers to doesn't exist.
+    NotFound,
+    NotLoggedIn,
+    /// An uploaded export couldn't be parsed.
+    ParseFailed,
+    /// Registration isn't open to the public, so accounts are made by admins.
+    RegistrationClosed,
+    ShooterNotFound,
+    ShotNotFound,
+    SplitAtFirstShot,
+    StringNotFound,
+    TooManyAttempts,
+    UserNotFound,
+    UsernameTaken,
 }
@@ -1,4 +1,8 @@
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::BufReader,
+    path::PathBuf,
+};
 
 use anyhow::Result;
 use clap::Parser;
@@ -6,7 +10,15 @@ use log::{
     debug,
     info,
 };
-use shotmarker_csv_parser::parser::export_parser;
+use shotmarker_csv_parser::{
+    parser::{
+        ParseOptions,
+        reader::ShotMarkerExportReader,
+    },
+    string::shot::ShotScore,
+    target_face::TargetFace,
+    units::MILLIMETERS_PER_INCH,
+};
 use svg::{
     Document,
     node::element::{
@@ -67,13 +79,14 @@ fn main() -> Result<()> {
     };
     info!("Output directory: {}", outdir.display());
 
-    let input_file = std::fs::read_to_string(input_file)?;
+    let input_file = BufReader::new(File::open(input_file)?);
 
-    let (rest, export) = export_parser(&input_file)?;
-    debug!("Unparsed file content: {rest:?}");
-    debug!("Export generated {} with {} shot strings", export.generated_date, export.string_count);
+    let export = ShotMarkerExportReader::new(input_file, ParseOptions::default())?;
+    let header = export.header();
+    debug!("Export generated {} with {} shot strings", header.generated_date, header.string_count);
 
-    for shot_string in &export.strings {
+    for shot_string in export {
+        let shot_string = shot_string?;
         info!(
             "Shot string ({} {} {}): {}",
             shot_string.target, shot_string.distance, shot_string.score, shot_string.name
@@ -89,13 +102,21 @@ fn main() -> Result<()> {
                 (viewbox_min_scaled, viewbox_min_scaled, viewbox_size_scaled, viewbox_size_scaled),
             )
             .set("zoomAndPan", "magnify");
-        let five_ring_radius = 24.0 * UNIT_SCALE_FACTOR;
-        let six_ring_radius = 18.0 * UNIT_SCALE_FACTOR;
-        let seven_ring_radius = 12.0 * UNIT_SCALE_FACTOR;
-        let eight_ring_radius = 9.0 * UNIT_SCALE_FACTOR;
-        let nine_ring_radius = 6.0 * UNIT_SCALE_FACTOR;
-        let ten_ring_radius = 3.0 * UNIT_SCALE_FACTOR;
-        let x_ring_radius = 1.5 * UNIT_SCALE_FACTOR;
+        let face = shot_string
+            .distance
+            .distance()
+            .map_or_else(TargetFace::nra_mr63fc, TargetFace::nra_f_class);
+        let ring_radius = |score| {
+            face.ring(score).map_or(0.0, |ring| ring.radius() / MILLIMETERS_PER_INCH)
+                * UNIT_SCALE_FACTOR
+        };
+        let five_ring_radius = ring_radius(ShotScore::Numeric(5));
+        let six_ring_radius = ring_radius(ShotScore::Numeric(6));
+        let seven_ring_radius = ring_radius(ShotScore::Numeric(7));
+        let eight_ring_radius = ring_radius(ShotScore::Numeric(8));
+        let nine_ring_radius = ring_radius(ShotScore::Numeric(9));
+        let ten_ring_radius = ring_radius(ShotScore::Numeric(10));
+        let x_ring_radius = ring_radius(ShotScore::X);
         let background = Rectangle::new()
             .set("x", viewbox_min_scaled)
             .set("y", viewbox_min_scaled)
@@ -306,7 +327,7 @@ fn main() -> Result<()> {
                     .set("r", BULLET_SIZE * UNIT_SCALE_FACTOR)
                     .set("stroke", "black")
                     .set("stroke-width", SHOT_STROKE_WIDTH);
-                if shot.tags == "sighter" {
+                if shot.is_sighter() {
                     circle = circle.set("fill", "DimGray");
                 } else {
                     circle = circle.set("fill", "DarkGray");
@@ -327,7 +348,7 @@ fn main() -> Result<()> {
                 .add(shot_circle)
                 .add(shot_label);
 
-            if shot.tags == "sighter" {
+            if shot.is_sighter() {
                 sighters.push(shot_group);
             } else {
                 scored.push(shot_group);
@@ -0,0 +1,203 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::string::shot::{
+    ShotMarkerShot,
+    ShotPosition,
+    ShotTags,
+    ShotVelocity,
+    ShotXYinch,
+    ShotXYmil,
+    ShotXYmm,
+    ShotXYmoa,
+};
+
+/// A shot that group statistics can be calculated over. Implemented for shots parsed from an
+/// export, as well as for shots as returned by the server, so that both report the same numbers.
+pub trait GroupShot {
+    fn position(&self) -> &ShotPosition;
+
+    fn velocity(&self) -> &ShotVelocity;
+
+    fn tags(&self) -> &ShotTags;
+}
+
+impl GroupShot for ShotMarkerShot {
+    fn position(&self) -> &ShotPosition { &self.position }
+
+    fn velocity(&self) -> &ShotVelocity { &self.velocity }
+
+    fn tags(&self) -> &ShotTags { &self.tags }
+}
+
+/// Which of a string's shots to calculate statistics over. The default includes every shot.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupOptions {
+    #[serde(default)]
+    pub exclude_sighters: bool,
+    #[serde(default)]
+    pub exclude_hidden:   bool,
+}
+
+impl GroupOptions {
+    /// Only the shots that count towards the string's score, as with ShotMarker's own `avg:` and
+    /// `SD:` rows.
+    pub fn scored_shots() -> Self {
+        Self {
+            exclude_sighters: true,
+            exclude_hidden:   true,
+        }
+    }
+
+    pub fn includes(&self, tags: &ShotTags) -> bool {
+        !(self.exclude_sighters && tags.is_sighter() || self.exclude_hidden && tags.is_hidden())
+    }
+}
+
+/// The mean, spread, and standard deviation of a set of values. Like ShotMarker, the standard
+/// deviation is of the values themselves (the population), rather than an estimate from a sample.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Spread {
+    pub mean: f64,
+    pub sd:   f64,
+    pub min:  f64,
+    pub max:  f64,
+}
+
+impl Spread {
+    fn new(values: &[f64]) -> Self {
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count;
+
+        Self {
+            mean,
+            sd: variance.sqrt(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    pub fn extreme_spread(&self) -> f64 { self.max - self.min }
+}
+
+/// A group's size and position in a single unit. Distances are between shot centres.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GroupMeasurements {
+    /// Horizontal spread. `x.mean` is the group's horizontal centre, and `x.extreme_spread()` its
+    /// width.
+    pub x:              Spread,
+    /// Vertical spread. `y.mean` is the group's vertical centre, and `y.extreme_spread()` its
+    /// height.
+    pub y:              Spread,
+    /// The largest distance between any two shots.
+    pub extreme_spread: f64,
+    /// The average distance of the shots from the group's centre.
+    pub mean_radius:    f64,
+    /// The standard deviation of the shots' distance from the group's centre, combining the two
+    /// axes.
+    pub radial_sd:      f64,
+    /// The radius around the group's centre holding half of the shots.
+    pub cep50:          f64,
+}
+
+impl GroupMeasurements {
+    fn new(points: &[(f64, f64)]) -> Self {
+        let x = Spread::new(&points.iter().map(|&(x, _)| x).collect::<Vec<_>>());
+        let y = Spread::new(&points.iter().map(|&(_, y)| y).collect::<Vec<_>>());
+
+        let mut radii: Vec<_> =
+            points.iter().map(|&(px, py)| (px - x.mean).hypot(py - y.mean)).collect();
+        radii.sort_by(f64::total_cmp);
+        let middle = radii.len() / 2;
+        let cep50 = if radii.len() % 2 == 0 {
+            (radii[middle - 1] + radii[middle]) / 2.0
+        } else {
+            radii[middle]
+        };
+
+        let extreme_spread = points
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, a)| points[idx + 1..].iter().map(move |b| (a, b)))
+            .map(|(&(ax, ay), &(bx, by))| (ax - bx).hypot(ay - by))
+            .fold(0.0, f64::max);
+
+        Self {
+            x,
+            y,
+            extreme_spread,
+            mean_radius: radii.iter().sum::<f64>() / radii.len() as f64,
+            radial_sd: x.sd.hypot(y.sd),
+            cep50,
+        }
+    }
+}
+
+/// Statistics for a group of shots, with the group's measurements in each of the units of
+/// [`ShotPosition`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupStatistics {
+    pub shot_count:   usize,
+    pub mm:           GroupMeasurements,
+    pub inch:         GroupMeasurements,
+    pub moa:          GroupMeasurements,
+    pub mil:          GroupMeasurements,
+    pub velocity_fps: Spread,
+    pub velocity_ms:  Spread,
+}
+
+impl GroupStatistics {
+    /// Statistics for the `shots` included by `options`, or `None` if that leaves no shots.
+    pub fn for_shots<S: GroupShot>(shots: &[S], options: GroupOptions) -> Option<Self> {
+        let shots: Vec<_> = shots.iter().filter(|shot| options.includes(shot.tags())).collect();
+        if shots.is_empty() {
+            return None;
+        }
+
+        let measure = |point: fn(&ShotPosition) -> (f64, f64)| {
+            GroupMeasurements::new(
+                &shots.iter().map(|shot| point(shot.position())).collect::<Vec<_>>(),
+            )
+        };
+        let fps: Vec<_> = shots.iter().map(|shot| f64::from(shot.velocity().fps)).collect();
+        let ms: Vec<_> = shots.iter().map(|shot| shot.velocity().ms).collect();
+
+        Some(Self {
+            shot_count:   shots.len(),
+            mm:           measure(|position| (position.mm.x, position.mm.y)),
+            inch:         measure(|position| (position.inch.x, position.inch.y)),
+            moa:          measure(|position| (position.moa.x, position.moa.y)),
+            mil:          measure(|position| (position.mil.x, position.mil.y)),
+            velocity_fps: Spread::new(&fps),
+            velocity_ms:  Spread::new(&ms),
+        })
+    }
+
+    /// The group's centre, the average position of its shots.
+    pub fn center(&self) -> ShotPosition {
+        ShotPosition {
+            mm:   ShotXYmm {
+                x: self.mm.x.mean,
+                y: self.mm.y.mean,
+            },
+            inch: ShotXYinch {
+                x: self.inch.x.mean,
+                y: self.inch.y.mean,
+            },
+            moa:  ShotXYmoa {
+                x: self.moa.x.mean,
+                y: self.moa.y.mean,
+            },
+            mil:  ShotXYmil {
+                x: self.mil.x.mean,
+                y: self.mil.y.mean,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
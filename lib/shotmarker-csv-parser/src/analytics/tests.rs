@@ -0,0 +1,96 @@
+use super::*;
+use crate::parser::export_parser;
+
+const SQUARE_GROUP: &str = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 strings from Jan 31 of 1 total in archive
+
+Jan 31 2025,Square Shooter,3,600y,39-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,6:57:23 pm,S1,sighter,8,200,200,7.87,7.87,1.25,1.25,0.36,0.36,600,1968,-0.6,-3.2,,
+,6:58:00 pm,1,,10,10,10,0.39,0.39,0.06,0.06,0.02,0.02,610,2001,-0.6,-3.2,,
+,6:58:30 pm,2,,10,-10,10,-0.39,0.39,-0.06,0.06,-0.02,0.02,612,2008,-0.6,-3.2,,
+,6:59:00 pm,3,,X,-10,-10,-0.39,-0.39,-0.06,-0.06,-0.02,-0.02,614,2014,-0.6,-3.2,,
+,6:59:30 pm,4,hidden,9,10,-10,0.39,-0.39,0.06,-0.06,0.02,-0.02,616,2021,-0.6,-3.2,,
+";
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "Expected {expected}, found {actual}");
+}
+
+#[test]
+fn group_statistics_square_group() {
+    let (_rest, export) = export_parser(SQUARE_GROUP).unwrap();
+    let shots = &export.strings[0].shots;
+
+    let options = GroupOptions {
+        exclude_sighters: true,
+        exclude_hidden:   false,
+    };
+    let statistics = GroupStatistics::for_shots(shots, options).unwrap();
+    assert_eq!(statistics.shot_count, 4);
+
+    let mm = statistics.mm;
+    assert_close(mm.x.mean, 0.0);
+    assert_close(mm.y.mean, 0.0);
+    assert_close(mm.x.extreme_spread(), 20.0);
+    assert_close(mm.x.sd, 10.0);
+    assert_close(mm.y.sd, 10.0);
+    assert_close(mm.extreme_spread, 800.0_f64.sqrt());
+    assert_close(mm.mean_radius, 200.0_f64.sqrt());
+    assert_close(mm.radial_sd, 200.0_f64.sqrt());
+    assert_close(mm.cep50, 200.0_f64.sqrt());
+
+    assert_close(statistics.inch.extreme_spread, 0.78 * 2.0_f64.sqrt());
+    assert_close(statistics.moa.x.sd, 0.06);
+    assert_close(statistics.mil.y.extreme_spread(), 0.04);
+    assert_eq!(
+        statistics.center().mm,
+        ShotXYmm {
+            x: mm.x.mean,
+            y: mm.y.mean,
+        }
+    );
+
+    assert_close(statistics.velocity_fps.mean, 2011.0);
+    assert_close(statistics.velocity_fps.extreme_spread(), 20.0);
+    assert_close(statistics.velocity_ms.extreme_spread(), 6.0);
+}
+
+#[test]
+fn group_statistics_shot_selection() {
+    let (_rest, export) = export_parser(SQUARE_GROUP).unwrap();
+    let shots = &export.strings[0].shots;
+
+    let all_shots = GroupStatistics::for_shots(shots, GroupOptions::default()).unwrap();
+    assert_eq!(all_shots.shot_count, 5);
+    assert_close(all_shots.mm.x.max, 200.0);
+
+    let scored = GroupStatistics::for_shots(shots, GroupOptions::scored_shots()).unwrap();
+    assert_eq!(scored.shot_count, 3);
+    assert_close(scored.mm.cep50, (2000.0_f64 / 9.0).sqrt());
+
+    let sighters: Vec<_> = shots.iter().filter(|shot| shot.is_sighter()).cloned().collect();
+    assert_eq!(GroupStatistics::for_shots(&sighters, GroupOptions::scored_shots()), None);
+}
+
+#[test]
+fn group_statistics_match_shotmarker_metrics() {
+    let (_rest, export) =
+        export_parser(include_str!("../../example-files/SM_shotslog_Jan_07.csv")).unwrap();
+
+    for shot_string in &export.strings {
+        let Some(metrics) = &shot_string.metrics else {
+            continue;
+        };
+        let statistics =
+            GroupStatistics::for_shots(&shot_string.shots, GroupOptions::scored_shots()).unwrap();
+
+        // ShotMarker calculates its metrics from unrounded positions, but exports positions, and
+        // their average, rounded to the millimeter.
+        assert!((statistics.mm.x.mean - metrics.average.position.mm.x).abs() <= 1.0);
+        assert!((statistics.mm.y.mean - metrics.average.position.mm.y).abs() <= 1.0);
+        assert!((statistics.mm.x.sd - metrics.std_deviation.position.mm.x).abs() <= 0.5);
+        assert!((statistics.mm.y.sd - metrics.std_deviation.position.mm.y).abs() <= 0.5);
+    }
+}
@@ -1,3 +1,26 @@
+use std::fmt;
+
+use miette::{
+    Diagnostic,
+    LabeledSpan,
+    MietteError,
+    MietteSpanContents,
+    Severity,
+    SourceCode,
+    SourceSpan,
+    SpanContents,
+};
+use nom::error::{
+    ContextError,
+    ErrorKind,
+    FromExternalError,
+    ParseError,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
 #[remain::sorted]
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ShotMarkerCsvParserError {
@@ -16,9 +39,309 @@ pub enum ShotMarkerCsvParserError {
         seconds: u32,
         is_pm:   bool,
     },
+    #[error("Missing required shot columns: {}", columns.join(", "))]
+    MissingColumns {
+        columns: Vec<String>,
+    },
     #[error("Unexpected number of shot strings: expected {expected}, found {found}")]
     UnexpectedStringCount {
         expected: usize,
         found:    usize,
     },
 }
+
+/// An export that could not be parsed, pointing at the line, column, and (for shot rows) the
+/// column of the CSV that the parser gave up on.
+///
+/// Renders as a labelled snippet of the offending line through [`miette`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub struct ShotMarkerParseError {
+    /// 1-based line number within the export.
+    pub line:          usize,
+    /// 1-based column (in characters) within `line`.
+    pub column:        usize,
+    /// The header line of the shot string being parsed, such as
+    /// `Jan 07 2025,Alex Thompson open,16,600y,200-14X`.
+    pub string_header: Option<String>,
+    /// The shot column being parsed, such as `x (mm)` or `v (fps)`.
+    pub field:         Option<String>,
+    #[source]
+    pub kind:          ShotMarkerParseErrorKind,
+    snippet:           Box<ParseErrorSnippet>,
+    span:              SourceSpan,
+}
+
+#[remain::sorted]
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ShotMarkerParseErrorKind {
+    #[error(transparent)]
+    Csv(#[from] ShotMarkerCsvParserError),
+    #[error("Unexpected end of input")]
+    Incomplete,
+    #[error("Unexpected input ({context})")]
+    UnexpectedInput {
+        context: String,
+    },
+}
+
+impl ShotMarkerParseError {
+    /// Locates `error` within `source`, the complete input that was handed to the parser.
+    pub(crate) fn new(source: &str, error: nom::Err<ShotMarkerNomError<'_>>) -> Self {
+        let error = match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => error,
+            nom::Err::Incomplete(_) => {
+                ShotMarkerNomError::from_error_kind(&source[source.len()..], ErrorKind::Eof)
+            }
+        };
+
+        let offset = source.len().saturating_sub(error.input.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = source[offset..].find('\n').map_or(source.len(), |newline| offset + newline);
+        let line_text = source[line_start..line_end].trim_end_matches('\r');
+        let line = source[..line_start].matches('\n').count() + 1;
+        let column = source[line_start..offset].chars().count() + 1;
+
+        // Highlight the rest of the offending cell, or the rest of the line outside of a shot row.
+        let span_start = (offset - line_start).min(line_text.len());
+        let span_end = match error.field {
+            Some(_) => {
+                line_text[span_start..].find(',').map_or(line_text.len(), |c| span_start + c)
+            }
+            None => line_text.len(),
+        };
+
+        let kind = match error.kind {
+            ShotMarkerNomErrorKind::External(error) => ShotMarkerParseErrorKind::Csv(error),
+            ShotMarkerNomErrorKind::Nom(ErrorKind::Eof) if error.input.is_empty() => {
+                ShotMarkerParseErrorKind::Incomplete
+            }
+            ShotMarkerNomErrorKind::Nom(_) if error.context.is_none() && error.field.is_some() => {
+                let cell = &line_text[span_start..span_end];
+                ShotMarkerParseErrorKind::UnexpectedInput {
+                    context: if cell.is_empty() {
+                        "found an empty value".to_string()
+                    } else {
+                        format!("found `{cell}`")
+                    },
+                }
+            }
+            ShotMarkerNomErrorKind::Nom(kind) => {
+                ShotMarkerParseErrorKind::UnexpectedInput {
+                    context: error.context.unwrap_or(kind.description()).to_string(),
+                }
+            }
+            ShotMarkerNomErrorKind::Char(expected) => {
+                ShotMarkerParseErrorKind::UnexpectedInput {
+                    context: error.context.map_or_else(
+                        || format!("expected '{expected}'"),
+                        |context| context.to_string(),
+                    ),
+                }
+            }
+        };
+
+        Self {
+            line,
+            column,
+            string_header: error.string_header.map(str::to_string),
+            field: error.field.map(str::to_string),
+            kind,
+            snippet: Box::new(ParseErrorSnippet {
+                text:       line_text.to_string(),
+                first_line: line - 1,
+            }),
+            span: (span_start, span_end - span_start).into(),
+        }
+    }
+
+    /// Moves the error down `lines` lines, for errors in a piece of an export that didn't start at
+    /// the top.
+    pub(crate) fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self.snippet.first_line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ShotMarkerParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "Invalid `{field}` value")?,
+            None => write!(f, "Unable to parse export")?,
+        }
+        write!(f, " on line {}, column {}", self.line, self.column)?;
+        if let Some(string_header) = &self.string_header {
+            write!(f, " of shot string \"{string_header}\"")?;
+        }
+
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl Diagnostic for ShotMarkerParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new("shotmarker_csv_parser::parse"))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> { Some(self.snippet.as_ref()) }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = match &self.field {
+            Some(field) => format!("{}: {}", field, self.kind),
+            None => self.kind.to_string(),
+        };
+
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(Some(label), self.span))))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.string_header.as_ref().map(|string_header| {
+            Box::new(format!("This row belongs to the shot string \"{string_header}\""))
+                as Box<dyn fmt::Display>
+        })
+    }
+}
+
+/// An export that could not be read by [`crate::parser::reader::ShotMarkerExportReader`].
+#[remain::sorted]
+#[derive(Debug, thiserror::Error, Diagnostic)]
+pub enum ShotMarkerReadError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ShotMarkerParseError),
+}
+
+/// A row or string that [`crate::parser::ParseMode::Lenient`] skipped rather than failing the
+/// whole export, along with why it couldn't be parsed.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{kind}: {error}")]
+pub struct ParseWarning {
+    pub kind:  ParseWarningKind,
+    pub error: ShotMarkerParseError,
+}
+
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+pub enum ParseWarningKind {
+    #[error("Skipped shot row")]
+    SkippedRow,
+    #[error("Skipped shot string")]
+    SkippedString,
+    #[error("Unexpected number of shot strings")]
+    UnexpectedStringCount,
+}
+
+impl Diagnostic for ParseWarning {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> { self.error.code() }
+
+    fn severity(&self) -> Option<Severity> { Some(Severity::Warning) }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> { self.error.source_code() }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> { self.error.labels() }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> { self.error.help() }
+}
+
+/// The single line of the export an error points at. Keeping only that line means the error
+/// doesn't hold on to the whole export, while still reporting the line's real number.
+#[derive(Debug, Clone)]
+struct ParseErrorSnippet {
+    text:       String,
+    /// 0-based line number of `text` within the export.
+    first_line: usize,
+}
+
+impl SourceCode for ParseErrorSnippet {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        let contents = self.text.read_span(span, context_lines_before, context_lines_after)?;
+
+        Ok(Box::new(MietteSpanContents::new_named(
+            "ShotMarker export".to_string(),
+            contents.data(),
+            *contents.span(),
+            contents.line() + self.first_line,
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+/// The error type threaded through the nom parsers. Unlike [`nom::error::Error`] it remembers the
+/// shot column and shot string being parsed, so [`ShotMarkerParseError`] can point at them.
+#[derive(Debug, Clone)]
+pub(crate) struct ShotMarkerNomError<'a> {
+    input:         &'a str,
+    kind:          ShotMarkerNomErrorKind,
+    context:       Option<&'static str>,
+    field:         Option<&'static str>,
+    string_header: Option<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+enum ShotMarkerNomErrorKind {
+    Char(char),
+    External(ShotMarkerCsvParserError),
+    Nom(ErrorKind),
+}
+
+impl<'a> ShotMarkerNomError<'a> {
+    fn new(input: &'a str, kind: ShotMarkerNomErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            context: None,
+            field: None,
+            string_header: None,
+        }
+    }
+
+    /// Attributes the error to the CSV column `field` starting at `cell`, unless a more deeply
+    /// nested parser already did.
+    pub(crate) fn in_field(mut self, field: &'static str, cell: &'a str) -> Self {
+        if self.field.is_none() {
+            self.field = Some(field);
+            self.input = cell;
+        }
+        self
+    }
+
+    /// Attributes the error to the shot string with the header line `string_header`.
+    pub(crate) fn in_string(mut self, string_header: &'a str) -> Self {
+        self.string_header.get_or_insert(string_header);
+        self
+    }
+}
+
+impl<'a> ParseError<&'a str> for ShotMarkerNomError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self::new(input, ShotMarkerNomErrorKind::Nom(kind))
+    }
+
+    // Keep the innermost error, since that is the one that points at the offending input.
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self { other }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Self::new(input, ShotMarkerNomErrorKind::Char(c))
+    }
+}
+
+impl<'a> ContextError<&'a str> for ShotMarkerNomError<'a> {
+    fn add_context(_input: &'a str, context: &'static str, mut other: Self) -> Self {
+        other.context.get_or_insert(context);
+        other
+    }
+}
+
+impl<'a> FromExternalError<&'a str, ShotMarkerCsvParserError> for ShotMarkerNomError<'a> {
+    fn from_external_error(input: &'a str, _kind: ErrorKind, e: ShotMarkerCsvParserError) -> Self {
+        Self::new(input, ShotMarkerNomErrorKind::External(e))
+    }
+}
@@ -5,15 +5,47 @@ use serde::{
     Serialize,
 };
 
+pub mod analytics;
 pub mod error;
+pub mod matching;
 pub mod parser;
+pub mod results;
+pub mod standings;
 pub mod string;
+pub mod target_face;
 pub mod units;
+pub mod validation;
+pub mod writer;
+
+/// The lines at the top of an export, saying when it was generated and which strings it holds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShotMarkerExportHeader {
+    pub generated_date:       NaiveDate,
+    pub string_count:         usize,
+    pub string_date:          NaiveDate,
+    /// The number of strings in the ShotMarker's whole archive, of which this export holds
+    /// `string_count`.
+    pub archive_string_count: usize,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShotMarkerExport {
-    pub generated_date: NaiveDate,
-    pub string_count:   usize,
-    pub string_date:    NaiveDate,
-    pub strings:        Vec<ShotMarkerShotString>,
+    pub generated_date:       NaiveDate,
+    pub string_count:         usize,
+    pub string_date:          NaiveDate,
+    /// See [`ShotMarkerExportHeader::archive_string_count`].
+    #[serde(default)]
+    pub archive_string_count: usize,
+    pub strings:              Vec<ShotMarkerShotString>,
+}
+
+impl ShotMarkerExport {
+    pub fn header(&self) -> ShotMarkerExportHeader {
+        ShotMarkerExportHeader {
+            generated_date:       self.generated_date,
+            string_count:         self.string_count,
+            string_date:          self.string_date,
+            archive_string_count: self.archive_string_count,
+        }
+    }
 }
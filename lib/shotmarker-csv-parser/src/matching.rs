@@ -0,0 +1,183 @@
+/// How similar a string's shooter name must be to a known shooter's name or alias to be proposed
+/// as a match.
+pub const MIN_CONFIDENCE: f64 = 0.75;
+
+/// A shooter that string names can be matched against. Implemented for shooters as returned by the
+/// server, whose aliases are the names they've been known by in earlier exports.
+pub trait KnownShooter {
+    fn name(&self) -> &str;
+
+    fn aliases(&self) -> &[String];
+}
+
+/// A ShotMarker string name, such as `Ethan Blackwood F Open`, split into the shooter's name and
+/// the class it says they shot in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringName {
+    pub shooter: String,
+    /// The index of the class named, into the class names it was split with. `None` when no class
+    /// was named, or when what was named could be more than one of them.
+    pub class:   Option<usize>,
+}
+
+/// Splits `name` into the shooter's name and a class hint. The class can be given by its full name
+/// or by the end of it, ignoring case and punctuation, so `open`, `F-Open` and `f open` are all
+/// `F Open`. Anything after the class, such as `6br` in `Gale Rangel F open 6br`, is dropped.
+pub fn split_string_name(name: &str, class_names: &[&str]) -> StringName {
+    let words: Vec<(&str, String)> = name
+        .split_whitespace()
+        .map(|word| (word, normalize_word(word)))
+        .filter(|(_, normalized)| !normalized.is_empty())
+        .collect();
+
+    // Each class can be named by any run of its final words, collapsed together so that `FTR`
+    // matches `F TR`. The more of the class's name that was used, the better the hint.
+    let class_phrases: Vec<(String, usize, usize)> = class_names
+        .iter()
+        .enumerate()
+        .flat_map(|(class, class_name)| {
+            let class_words: Vec<_> = class_name
+                .split_whitespace()
+                .map(normalize_word)
+                .filter(|word| !word.is_empty())
+                .collect();
+            (0..class_words.len())
+                .map(move |skipped| (class_words[skipped..].concat(), class, skipped))
+        })
+        .collect();
+
+    // Leave at least one word for the shooter's name, and take the earliest class hint, along
+    // with as many of the following words as are part of it.
+    for start in 1..words.len() {
+        for end in (start + 1..=words.len()).rev() {
+            let phrase: String =
+                words[start..end].iter().map(|(_, normalized)| normalized.as_str()).collect();
+            let Some(fewest_skipped) = class_phrases
+                .iter()
+                .filter(|(class_phrase, ..)| *class_phrase == phrase)
+                .map(|&(_, _, skipped)| skipped)
+                .min()
+            else {
+                continue;
+            };
+            let mut classes = class_phrases
+                .iter()
+                .filter(|(class_phrase, _, skipped)| {
+                    *class_phrase == phrase && *skipped == fewest_skipped
+                })
+                .map(|&(_, class, _)| class);
+            let class = classes.next().filter(|_| classes.next().is_none());
+
+            return StringName {
+                shooter: join_words(&words[..start]),
+                class,
+            };
+        }
+    }
+
+    StringName {
+        shooter: join_words(&words),
+        class:   None,
+    }
+}
+
+/// The known shooter that a string's shooter name is most likely to be.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShooterMatch<'s, S> {
+    pub shooter:    &'s S,
+    /// How similar the names are, from [`MIN_CONFIDENCE`] up to 1.0 for the same name.
+    pub confidence: f64,
+    /// Whether the name is the shooter's name or one of their aliases, and no other shooter's.
+    /// Exact matches are safe to assign without review.
+    pub exact:      bool,
+}
+
+/// Finds the shooter in `shooters` whose name or aliases are most similar to `shooter_name`, or
+/// `None` if none are similar enough. Names are compared ignoring case, punctuation, and the order
+/// of their words.
+pub fn match_shooter<'s, S: KnownShooter>(
+    shooter_name: &str,
+    shooters: &'s [S],
+) -> Option<ShooterMatch<'s, S>> {
+    let name = normalize_name(shooter_name);
+    if name.is_empty() {
+        return None;
+    }
+
+    let scored: Vec<_> = shooters
+        .iter()
+        .map(|shooter| {
+            let confidence = std::iter::once(shooter.name())
+                .chain(shooter.aliases().iter().map(String::as_str))
+                .map(|known_name| similarity(&name, &normalize_name(known_name)))
+                .fold(0.0, f64::max);
+            (shooter, confidence)
+        })
+        .collect();
+
+    let exact_count = scored.iter().filter(|(_, confidence)| *confidence == 1.0).count();
+    let (shooter, confidence) = scored.into_iter().max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    if confidence < MIN_CONFIDENCE {
+        return None;
+    }
+
+    Some(ShooterMatch {
+        shooter,
+        confidence,
+        exact: exact_count == 1 && confidence == 1.0,
+    })
+}
+
+/// Lowercases `word` and drops anything that isn't a letter or digit.
+fn normalize_word(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(normalize_word)
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn join_words(words: &[(&str, String)]) -> String {
+    words.iter().map(|(word, _)| *word).collect::<Vec<_>>().join(" ")
+}
+
+/// The similarity of two normalized names, from 0.0 to 1.0 for the same name, taking whichever
+/// is closer of the names as given and with their words sorted.
+fn similarity(a: &str, b: &str) -> f64 {
+    let sorted = |name: &str| {
+        let mut words: Vec<_> = name.split(' ').collect();
+        words.sort_unstable();
+        words.join(" ")
+    };
+
+    edit_similarity(a, b).max(edit_similarity(&sorted(a), &sorted(b)))
+}
+
+/// One minus the Levenshtein distance between `a` and `b`, as a fraction of the longer's length.
+fn edit_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<_> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests;
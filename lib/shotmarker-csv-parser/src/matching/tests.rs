@@ -0,0 +1,99 @@
+use super::*;
+
+const CLASS_NAMES: [&str; 4] = ["F Open", "F-TR", "Sling", "Open"];
+
+struct Shooter {
+    name:    &'static str,
+    aliases: Vec<String>,
+}
+
+impl KnownShooter for Shooter {
+    fn name(&self) -> &str { self.name }
+
+    fn aliases(&self) -> &[String] { &self.aliases }
+}
+
+fn shooters() -> Vec<Shooter> {
+    vec![
+        Shooter {
+            name:    "Ethan Blackwood",
+            aliases: vec!["E Blackwood".to_string()],
+        },
+        Shooter {
+            name:    "Ethan Hawthorne",
+            aliases: Vec::new(),
+        },
+        Shooter {
+            name:    "Lou Oneal",
+            aliases: vec!["Lou O'Neal".to_string()],
+        },
+    ]
+}
+
+#[test]
+fn split_string_names() {
+    let cases = [
+        ("Ethan Blackwood F Open", "Ethan Blackwood", Some(0)),
+        ("Ethan Blackwell open", "Ethan Blackwell", Some(3)),
+        ("Dave Haney f open", "Dave Haney", Some(0)),
+        ("Claudia Wolf ftr", "Claudia Wolf", Some(1)),
+        ("lou oneal ftr 2", "lou oneal", Some(1)),
+        ("Gale Rangel F open 6br", "Gale Rangel", Some(0)),
+        ("Pasquale Barker sling ", "Pasquale Barker", Some(2)),
+        ("Kirsten Valencia - FPR", "Kirsten Valencia FPR", None),
+        ("Marcus Holloway", "Marcus Holloway", None),
+        ("Open", "Open", None),
+    ];
+    for (name, shooter, class) in cases {
+        assert_eq!(
+            split_string_name(name, &CLASS_NAMES),
+            StringName {
+                shooter: shooter.to_string(),
+                class,
+            },
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn split_string_name_with_ambiguous_class() {
+    let name = split_string_name("Walton Rush open", &["F Open", "Sling Open"]);
+
+    assert_eq!(name.shooter, "Walton Rush");
+    assert_eq!(name.class, None);
+}
+
+#[test]
+fn match_shooter_names() {
+    let shooters = shooters();
+
+    let exact = match_shooter("ETHAN BLACKWOOD", &shooters).unwrap();
+    assert_eq!(exact.shooter.name, "Ethan Blackwood");
+    assert!(exact.exact);
+
+    let alias = match_shooter("lou o'neal", &shooters).unwrap();
+    assert_eq!(alias.shooter.name, "Lou Oneal");
+    assert!(alias.exact);
+
+    let misspelled = match_shooter("Ethan Blackwell", &shooters).unwrap();
+    assert_eq!(misspelled.shooter.name, "Ethan Blackwood");
+    assert!(!misspelled.exact);
+    assert!((misspelled.confidence - 0.8).abs() < 1e-9, "{}", misspelled.confidence);
+
+    let reordered = match_shooter("Hawthorne Ethan", &shooters).unwrap();
+    assert_eq!(reordered.shooter.name, "Ethan Hawthorne");
+
+    assert!(match_shooter("unnamed", &shooters).is_none());
+    assert!(match_shooter("", &shooters).is_none());
+}
+
+#[test]
+fn match_shooter_shared_alias_is_not_exact() {
+    let mut shooters = shooters();
+    shooters[1].aliases.push("Ethan Blackwood".to_string());
+
+    let shared = match_shooter("Ethan Blackwood", &shooters).unwrap();
+    assert_eq!(shared.confidence, 1.0);
+    assert!(!shared.exact);
+}
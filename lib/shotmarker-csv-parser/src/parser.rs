@@ -1,66 +1,260 @@
+use std::cell::RefCell;
+
 use chrono::{
     Datelike,
+    Days,
     NaiveDate,
     NaiveTime,
 };
 use nom::{
-    IResult,
     Parser,
     branch::alt,
     bytes::complete::{
+        is_not,
         tag,
         tag_no_case,
         take_until,
+        take_while,
     },
     character::complete::space1,
     combinator::{
+        cut,
+        map,
         opt,
         value,
     },
     error::{
         ErrorKind,
         FromExternalError,
+        ParseError,
         context,
     },
     multi::{
-        many_m_n,
-        many0,
+        separated_list0,
+        separated_list1,
     },
     number::complete::double,
-    sequence::terminated,
+    sequence::{
+        delimited,
+        preceded,
+    },
 };
 
 use crate::{
     ShotMarkerExport,
-    error::ShotMarkerCsvParserError,
-    parser::util::ws,
+    ShotMarkerExportHeader,
+    error::{
+        ParseWarning,
+        ParseWarningKind,
+        ShotMarkerCsvParserError,
+        ShotMarkerNomError,
+        ShotMarkerParseError,
+    },
+    parser::{
+        row::shot_row_parser,
+        util::{
+            column,
+            field,
+            ws,
+        },
+    },
     string::{
+        ScoringConvention,
+        ShotMarkerMetricValues,
+        ShotMarkerMetricVelocity,
         ShotMarkerShotString,
         ShotMarkerStringMetrics,
         StringScore,
+        column::{
+            ShotColumn,
+            ShotColumns,
+        },
+        distance::StringDistance,
         shot::{
             ShotMarkerShot,
             ShotPosition,
             ShotScore,
+            ShotTag,
+            ShotTags,
             ShotVelocity,
             ShotXYinch,
             ShotXYmil,
             ShotXYmm,
             ShotXYmoa,
         },
+        target::StringTarget,
+    },
+    units::{
+        FEET_PER_METER,
+        MILLIMETERS_PER_INCH,
     },
 };
 
+pub mod reader;
+mod row;
 #[cfg(test)]
 mod tests;
 pub mod util;
 
-pub fn export_parser(
+type IResult<'a, O> = nom::IResult<&'a str, O, ShotMarkerNomError<'a>>;
+
+/// How [`export_parser_with_options`] handles shot rows and strings it can't parse.
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Skip the row or string, record a [`ParseWarning`] for it, and carry on.
+    Lenient,
+    /// Fail the whole export.
+    #[default]
+    Strict,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub mode: ParseMode,
+}
+
+/// An export parsed by [`export_parser_with_options`].
+#[derive(Debug, Clone)]
+pub struct ParsedExport<'a> {
+    /// Any unparsed trailing input.
+    pub rest:     &'a str,
+    pub export:   ShotMarkerExport,
+    /// What was skipped to parse the export, in the order it appears. Always empty in
+    /// [`ParseMode::Strict`].
+    pub warnings: Vec<ParseWarning>,
+}
+
+/// State shared by the parsers for a single export.
+#[derive(Default)]
+struct ParseState<'a> {
+    options:  ParseOptions,
+    warnings: RefCell<Vec<(ParseWarningKind, ShotMarkerNomError<'a>)>>,
+}
+
+impl<'a> ParseState<'a> {
+    fn new(options: ParseOptions) -> Self {
+        Self {
+            options,
+            warnings: RefCell::default(),
+        }
+    }
+
+    /// In [`ParseMode::Lenient`], records a failure as a warning so the caller can skip past what
+    /// failed. Anything else is handed back to be returned.
+    fn recover(
+        &self,
+        kind: ParseWarningKind,
+        error: nom::Err<ShotMarkerNomError<'a>>,
+    ) -> Result<(), nom::Err<ShotMarkerNomError<'a>>> {
+        match error {
+            nom::Err::Failure(error) if self.options.mode == ParseMode::Lenient => {
+                self.warnings.borrow_mut().push((kind, error));
+                Ok(())
+            }
+            error => Err(error),
+        }
+    }
+
+    fn warning_count(&self, kind: ParseWarningKind) -> usize {
+        self.warnings.borrow().iter().filter(|(warning_kind, _error)| *warning_kind == kind).count()
+    }
+}
+
+/// Parses a complete ShotMarker CSV export, returning any unparsed trailing input along with the
+/// export. Fails on the first row or string that can't be parsed.
+pub fn export_parser(input: &str) -> Result<(&str, ShotMarkerExport), ShotMarkerParseError> {
+    let ParsedExport {
+        rest,
+        export,
+        ..
+    } = export_parser_with_options(input, ParseOptions::default())?;
+
+    Ok((rest, export))
+}
+
+/// Parses a complete ShotMarker CSV export, handling bad rows and strings according to
+/// `options`.
+pub fn export_parser_with_options(
     input: &str,
-) -> Result<(&str, ShotMarkerExport), nom::Err<nom::error::Error<String>>> {
-    export_parser_inner(input).map_err(|e| e.to_owned())
+    options: ParseOptions,
+) -> Result<ParsedExport<'_>, ShotMarkerParseError> {
+    let state = ParseState::new(options);
+    let (rest, export) = export_parser_inner(&state, input)
+        .map_err(|error| ShotMarkerParseError::new(input, error))?;
+    let warnings = state
+        .warnings
+        .into_inner()
+        .into_iter()
+        .map(|(kind, error)| {
+            ParseWarning {
+                kind,
+                error: ShotMarkerParseError::new(input, nom::Err::Failure(error)),
+            }
+        })
+        .collect();
+
+    Ok(ParsedExport {
+        rest,
+        export,
+        warnings,
+    })
 }
-fn export_parser_inner(input: &str) -> IResult<&str, ShotMarkerExport> {
+
+fn export_parser_inner<'a>(
+    state: &ParseState<'a>,
+    input: &'a str,
+) -> IResult<'a, ShotMarkerExport> {
+    let (
+        rest,
+        ShotMarkerExportHeader {
+            generated_date,
+            string_count,
+            string_date,
+            archive_string_count,
+        },
+    ) = export_header_parser(input)?;
+    let (rest, strings) = shot_strings_parser(state, rest)?;
+
+    // Strings skipped in lenient mode were still counted by the export header.
+    let skipped_strings = state.warning_count(ParseWarningKind::SkippedString);
+    if string_count != strings.len() + skipped_strings {
+        state.recover(
+            ParseWarningKind::UnexpectedStringCount,
+            unexpected_string_count(rest, string_count, strings.len()),
+        )?;
+    }
+
+    let shot_marker_export = ShotMarkerExport {
+        generated_date,
+        string_count,
+        string_date,
+        archive_string_count,
+        strings,
+    };
+
+    Ok((rest, shot_marker_export))
+}
+
+fn unexpected_string_count(
+    input: &str,
+    expected: usize,
+    found: usize,
+) -> nom::Err<ShotMarkerNomError<'_>> {
+    nom::Err::Failure(ShotMarkerNomError::from_external_error(
+        input,
+        ErrorKind::Verify,
+        ShotMarkerCsvParserError::UnexpectedStringCount {
+            expected,
+            found,
+        },
+    ))
+}
+
+/// Parses the lines at the top of an export, such as:
+///   ShotMarker Archived Data (generated Jan 07 2025)
+///   Exported 53 strings from Jan 07 of 53 total in archive
+fn export_header_parser(input: &str) -> IResult<'_, ShotMarkerExportHeader> {
     let (rest, _) =
         context("Parsing start of export", ws(tag_no_case("ShotMarker Archived Data (generated")))
             .parse(input)?;
@@ -76,44 +270,104 @@ fn export_parser_inner(input: &str) -> IResult<&str, ShotMarkerExport> {
     let (rest, _) = ws(tag_no_case("from")).parse(rest)?;
     let (rest, string_date) = month_and_day_parser(generated_date.year(), rest)?;
     let (rest, _) = ws(tag_no_case("of")).parse(rest)?;
-    let (rest, _) =
-        ws(context("Parsing total shot strings in archive", nom::character::complete::u32))
+    let (rest, archive_string_count) =
+        ws(context("Parsing total shot strings in archive", nom::character::complete::usize))
             .parse(rest)?;
     let (rest, _) = ws(tag_no_case("total in archive")).parse(rest)?;
-    let (rest, strings) = shot_strings_parser(rest)?;
 
-    if string_count != strings.len() {
-        return Err(nom::Err::Failure(nom::error::Error::from_external_error(
-            input,
-            ErrorKind::Verify,
-            ShotMarkerCsvParserError::UnexpectedStringCount {
-                expected: string_count,
-                found:    strings.len(),
-            },
-        )));
-    }
-
-    let shot_marker_export = ShotMarkerExport {
+    let header = ShotMarkerExportHeader {
         generated_date,
         string_count,
         string_date,
-        strings,
+        archive_string_count,
     };
 
-    Ok((rest, shot_marker_export))
+    Ok((rest, header))
 }
 
-fn shot_strings_parser(input: &str) -> IResult<&str, Vec<ShotMarkerShotString>> {
-    let (rest, strings) = many0(shot_string_parser).parse(input)?;
+fn shot_strings_parser<'a>(
+    state: &ParseState<'a>,
+    input: &'a str,
+) -> IResult<'a, Vec<ShotMarkerShotString>> {
+    let mut strings = Vec::new();
+    let mut rest = input;
+    loop {
+        match shot_string_parser(state, rest) {
+            Ok((string_rest, shot_string)) => {
+                strings.push(shot_string);
+                rest = string_rest;
+            }
+            Err(nom::Err::Error(_)) => break,
+            Err(error) => {
+                state.recover(ParseWarningKind::SkippedString, error)?;
+                rest = skip_shot_string(rest);
+            }
+        }
+    }
 
     Ok((rest, strings))
 }
 
-fn shot_string_parser(input: &str) -> IResult<&str, ShotMarkerShotString> {
-    let (rest, (date, name, target, distance, score)) =
-        ws(shot_string_header_parser).parse(input)?;
-    let (rest, shots) = many0(ws(shot_string_entry_parser)).parse(rest)?;
-    let (rest, metrics) = opt(ws(shot_string_metrics_parser)).parse(rest)?;
+/// Parses a shot string, attributing any error to the string's header line.
+fn shot_string_parser<'a>(
+    state: &ParseState<'a>,
+    input: &'a str,
+) -> IResult<'a, ShotMarkerShotString> {
+    let string_header = input.trim_start().lines().next().unwrap_or_default().trim_end();
+    let in_string =
+        |error: nom::Err<ShotMarkerNomError<'a>>| error.map(|error| error.in_string(string_header));
+
+    let (mut rest, (date, name, target, distance, score, columns)) =
+        ws(shot_string_header_parser).parse(input).map_err(in_string)?;
+
+    // Step through the rows by hand rather than with `many0`, so that in lenient mode a bad row can
+    // be skipped without losing the rest of the string.
+    let mut shots = Vec::new();
+    let mut metrics = None;
+    loop {
+        if metrics.is_none() {
+            match ws(|input| shot_string_entry_parser(date, &columns, input)).parse(rest) {
+                Ok((row_rest, shot)) => {
+                    shots.push(shot);
+                    rest = row_rest;
+                    continue;
+                }
+                Err(nom::Err::Error(_)) => {}
+                Err(error) => {
+                    state.recover(ParseWarningKind::SkippedRow, in_string(error))?;
+                    rest = skip_line(rest);
+                    continue;
+                }
+            }
+
+            match opt(ws(|input| shot_string_metrics_parser(&columns, input))).parse(rest) {
+                Ok((metrics_rest, Some(string_metrics))) => {
+                    metrics = Some(string_metrics);
+                    rest = metrics_rest;
+                    continue;
+                }
+                Ok((_rest, None)) => {}
+                Err(error) => {
+                    state.recover(ParseWarningKind::SkippedRow, in_string(error))?;
+                    rest = skip_line(rest);
+                    continue;
+                }
+            }
+        }
+
+        match shot_string_end_parser(date, &columns, rest) {
+            Ok((end_rest, ())) => {
+                rest = end_rest;
+                break;
+            }
+            Err(error) => {
+                state.recover(ParseWarningKind::SkippedRow, in_string(error))?;
+                rest = skip_line(rest);
+            }
+        }
+    }
+
+    roll_over_shot_dates(&mut shots);
 
     let shot_string = ShotMarkerShotString {
         date,
@@ -123,115 +377,267 @@ fn shot_string_parser(input: &str) -> IResult<&str, ShotMarkerShotString> {
         score,
         shots,
         metrics,
+        columns,
     };
 
     Ok((rest, shot_string))
 }
 
 /// Parses the shot string header into:
-///   Date, Name, Target, Distance, Score
+///   Date, Name, Target, Distance, Score, Shot columns
 fn shot_string_header_parser(
     input: &str,
-) -> IResult<&str, (NaiveDate, String, String, String, StringScore)> {
+) -> IResult<'_, (NaiveDate, String, StringTarget, StringDistance, StringScore, ShotColumns)> {
     let (rest, date) = ws(date_parser).parse(input)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, name) = ws(take_until(",")).parse(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, target) = ws(take_until(",")).parse(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, distance) = ws(take_until(",")).parse(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, score) = ws(parse_string_score).parse(rest)?;
-
-    // Individual shot field header.
-    let (rest, _) = ws(tag(",time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality")).parse(rest)?;
-
-    Ok((rest, (date, name.to_owned(), target.to_owned(), distance.to_owned(), score.to_owned())))
+    // Keep the name as written, padding and all, so it can be written back out unchanged. The target
+    // and distance are trimmed before they're typed, so padding doesn't make them unknown.
+    let (rest, name) = column("name", take_until(",")).parse(rest)?;
+    let (rest, target) = column("target", take_until(",")).parse(rest)?;
+    let (rest, distance) = column("distance", take_until(",")).parse(rest)?;
+    let (rest, score) =
+        field("score", cut(preceded(tag(","), ws(parse_string_score)))).parse(rest)?;
+    let (rest, columns) =
+        context("Parsing shot column header", cut(ws(shot_columns_parser))).parse(rest)?;
+
+    Ok((
+        rest,
+        (
+            date,
+            name.to_owned(),
+            target.trim().into(),
+            distance.trim().into(),
+            score.to_owned(),
+            columns,
+        ),
+    ))
+}
+
+/// Parses the individual shot column header, such as
+/// `,time,id,tags,score,x (mm),y (mm),...,quality`, failing if a required column is missing.
+fn shot_columns_parser(input: &str) -> IResult<'_, ShotColumns> {
+    let (rest, _) = tag(",")(input)?;
+    let (rest, names) =
+        separated_list1(tag(","), take_while(|c| !matches!(c, ',' | '\r' | '\n'))).parse(rest)?;
+
+    let columns = ShotColumns::new(names.into_iter().map(str::to_string).collect());
+    let missing = columns.missing_required();
+    if !missing.is_empty() {
+        return Err(nom::Err::Failure(ShotMarkerNomError::from_external_error(
+            input,
+            ErrorKind::Verify,
+            ShotMarkerCsvParserError::MissingColumns {
+                columns: missing.iter().map(|column| column.name().to_string()).collect(),
+            },
+        )));
+    }
+
+    Ok((rest, columns))
 }
 
-fn shot_string_metrics_parser(input: &str) -> IResult<&str, ShotMarkerStringMetrics> {
-    let (rest, _) = ws(tag_no_case(",,,,avg:,")).parse(input)?;
-    let (rest, _) = ws(many_m_n(13, 13, terminated(ws(double), tag(",")))).parse(rest)?;
-    let (rest, _) = ws(tag_no_case(",,,,SD:,")).parse(rest)?;
-    let (rest, _) = ws(many_m_n(13, 13, terminated(ws(double), tag(",")))).parse(rest)?;
+fn shot_string_metrics_parser<'a>(
+    columns: &ShotColumns,
+    input: &'a str,
+) -> IResult<'a, ShotMarkerStringMetrics> {
+    let (rest, average) = ws(|input| metric_row_parser(columns, "avg:", input)).parse(input)?;
+    let (rest, std_deviation) = context(
+        "Parsing string standard deviations",
+        cut(ws(|input| metric_row_parser(columns, "SD:", input))),
+    )
+    .parse(rest)?;
+
+    let metrics = ShotMarkerStringMetrics {
+        average,
+        std_deviation,
+    };
 
-    Ok((rest, ShotMarkerStringMetrics {}))
+    Ok((rest, metrics))
 }
 
-fn shot_string_entry_parser(input: &str) -> IResult<&str, ShotMarkerShot> {
-    let (rest, _) = tag(",")(input)?;
-    let (rest, time) = time_parser(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, id) = take_until(",")(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, tags) = take_until(",")(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, score) = parse_shot_score(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, x_mm) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, y_mm) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, x_inch) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, y_inch) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, x_moa) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, y_moa) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, x_mil) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, y_mil) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, velocity_ms) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, velocity_fps) = nom::character::complete::u32(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, yaw_degrees) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, pitch_degrees) = double(rest)?;
-    let (rest, _) = tag(",")(rest)?;
-    let (rest, quality) = opt(double).parse(rest)?;
-    let (rest, _) = tag(",")(rest)?;
+/// Parses an `avg:` or `SD:` row, whose values line up with the shot columns from `x (mm)`
+/// through `quality`.
+fn metric_row_parser<'a>(
+    columns: &ShotColumns,
+    label: &str,
+    input: &'a str,
+) -> IResult<'a, ShotMarkerMetricValues> {
+    let (rest, row) = shot_row_parser(columns, input)?;
+    if !row.label().is_some_and(|row_label| row_label.eq_ignore_ascii_case(label)) {
+        return Err(nom::Err::Error(ShotMarkerNomError::from_error_kind(input, ErrorKind::Tag)));
+    }
+
+    let x_mm = row.required(ShotColumn::MmX, ws(double))?;
+    let y_mm = row.required(ShotColumn::MmY, ws(double))?;
+    let velocity_ms = row.optional(ShotColumn::VelocityMs, ws(double))?;
+    let velocity_fps = row.optional(ShotColumn::VelocityFps, ws(double))?;
+
+    let metric_values = ShotMarkerMetricValues {
+        position: ShotPosition {
+            mm:   ShotXYmm {
+                x: x_mm,
+                y: y_mm,
+            },
+            inch: ShotXYinch {
+                x: row
+                    .optional(ShotColumn::InchX, ws(double))?
+                    .unwrap_or(x_mm / MILLIMETERS_PER_INCH),
+                y: row
+                    .optional(ShotColumn::InchY, ws(double))?
+                    .unwrap_or(y_mm / MILLIMETERS_PER_INCH),
+            },
+            moa:  ShotXYmoa {
+                x: row.required(ShotColumn::MoaX, ws(double))?,
+                y: row.required(ShotColumn::MoaY, ws(double))?,
+            },
+            mil:  ShotXYmil {
+                x: row.required(ShotColumn::MilX, ws(double))?,
+                y: row.required(ShotColumn::MilY, ws(double))?,
+            },
+        },
+        velocity: ShotMarkerMetricVelocity {
+            ms:  velocity_ms.or(velocity_fps.map(|fps| fps / FEET_PER_METER)).unwrap_or_default(),
+            fps: velocity_fps.or(velocity_ms.map(|ms| ms * FEET_PER_METER)).unwrap_or_default(),
+        },
+        yaw:      row.required(ShotColumn::Yaw, ws(double))?,
+        pitch:    row.required(ShotColumn::Pitch, ws(double))?,
+        quality:  row.optional(ShotColumn::Quality, ws(opt(double)))?.flatten(),
+    };
+
+    Ok((rest, metric_values))
+}
+
+/// Parses a single shot row, dating it on `date`. See [`roll_over_shot_dates`] for strings fired
+/// across midnight.
+fn shot_string_entry_parser<'a>(
+    date: NaiveDate,
+    columns: &ShotColumns,
+    input: &'a str,
+) -> IResult<'a, ShotMarkerShot> {
+    let (rest, row) = shot_row_parser(columns, input)?;
+    // Only rows with a time are shots, so anything else (such as the `avg:` row) ends the string.
+    // From here on, report errors rather than backtracking out of the row.
+    let Some(time) = row.value(ShotColumn::Time, ws(time_parser))? else {
+        return Err(nom::Err::Error(ShotMarkerNomError::from_error_kind(input, ErrorKind::Verify)));
+    };
+
+    let x_mm = row.required(ShotColumn::MmX, ws(double))?;
+    let y_mm = row.required(ShotColumn::MmY, ws(double))?;
+    let velocity_ms = row.optional(ShotColumn::VelocityMs, ws(double))?;
+    let velocity_fps = row.optional(ShotColumn::VelocityFps, ws(nom::character::complete::u32))?;
 
     let shot = ShotMarkerShot {
         time,
-        id: id.to_string(),
-        tags: tags.to_string(),
-        score,
+        date_time: date.and_time(time),
+        id: row.required(ShotColumn::Id, nom::combinator::rest)?.trim().to_string(),
+        tags: row.optional(ShotColumn::Tags, shot_tags_parser)?.unwrap_or_default(),
+        score: row.required(ShotColumn::Score, ws(parse_shot_score))?,
         position: ShotPosition {
             mm:   ShotXYmm {
                 x: x_mm,
                 y: y_mm,
             },
             inch: ShotXYinch {
-                x: x_inch,
-                y: y_inch,
+                x: row
+                    .optional(ShotColumn::InchX, ws(double))?
+                    .unwrap_or(x_mm / MILLIMETERS_PER_INCH),
+                y: row
+                    .optional(ShotColumn::InchY, ws(double))?
+                    .unwrap_or(y_mm / MILLIMETERS_PER_INCH),
             },
             moa:  ShotXYmoa {
-                x: x_moa,
-                y: y_moa,
+                x: row.required(ShotColumn::MoaX, ws(double))?,
+                y: row.required(ShotColumn::MoaY, ws(double))?,
             },
             mil:  ShotXYmil {
-                x: x_mil,
-                y: y_mil,
+                x: row.required(ShotColumn::MilX, ws(double))?,
+                y: row.required(ShotColumn::MilY, ws(double))?,
             },
         },
         velocity: ShotVelocity {
-            ms:  velocity_ms,
-            fps: velocity_fps,
+            ms:  velocity_ms
+                .or(velocity_fps.map(|fps| f64::from(fps) / FEET_PER_METER))
+                .unwrap_or_default(),
+            fps: velocity_fps
+                .or(velocity_ms.map(|ms| (ms * FEET_PER_METER).round() as u32))
+                .unwrap_or_default(),
         },
-        yaw: yaw_degrees,
-        pitch: pitch_degrees,
-        quality,
+        yaw: row.required(ShotColumn::Yaw, ws(double))?,
+        pitch: row.required(ShotColumn::Pitch, ws(double))?,
+        quality: row.optional(ShotColumn::Quality, ws(opt(double)))?.flatten(),
+        extras: row.extras(),
     };
 
     Ok((rest, shot))
 }
 
-fn time_parser(input: &str) -> IResult<&str, NaiveTime> {
-    let (rest, mut hours) = nom::character::complete::u32(input)?;
+/// A shot string runs until the next string header or the end of the export. Anything else is a row
+/// that isn't a shot we could make sense of, so parse it as one again to report why.
+fn shot_string_end_parser<'a>(
+    date: NaiveDate,
+    columns: &ShotColumns,
+    input: &'a str,
+) -> IResult<'a, ()> {
+    let next_line = input.trim_start();
+    if next_line.is_empty() || date_parser(next_line).is_ok() {
+        return Ok((input, ()));
+    }
+
+    match context("Parsing shot row", |input| shot_string_entry_parser(date, columns, input))
+        .parse(next_line)
+    {
+        Err(nom::Err::Error(error)) => Err(nom::Err::Failure(error)),
+        Err(error) => Err(error),
+        Ok(_) => {
+            Err(nom::Err::Failure(ShotMarkerNomError::from_error_kind(
+                next_line,
+                ErrorKind::Verify,
+            )))
+        }
+    }
+}
+
+/// Skips the rest of the current line, along with any blank lines before it.
+fn skip_line(input: &str) -> &str {
+    let input = input.trim_start();
+    input.find('\n').map_or(&input[input.len()..], |newline| &input[newline + 1..])
+}
+
+/// Skips a shot string that couldn't be parsed, up to the next string header.
+fn skip_shot_string(input: &str) -> &str {
+    let mut rest = skip_line(input);
+    while !rest.trim_start().is_empty() && date_parser(rest.trim_start()).is_err() {
+        rest = skip_line(rest);
+    }
+
+    rest
+}
+
+/// Shots are recorded in the order they were fired, so any shot whose time of day is earlier than
+/// the shot before it must have been fired after midnight.
+fn roll_over_shot_dates(shots: &mut [ShotMarkerShot]) {
+    let mut days_elapsed = 0;
+    let mut previous_time = None;
+    for shot in shots {
+        if previous_time.is_some_and(|previous_time| shot.time < previous_time) {
+            days_elapsed += 1;
+        }
+        previous_time = Some(shot.time);
+        shot.date_time = shot.date_time + Days::new(days_elapsed);
+    }
+}
+
+/// Parses the `/`-separated tags column, which may be empty.
+fn shot_tags_parser(input: &str) -> IResult<'_, ShotTags> {
+    let (rest, tags) =
+        separated_list0(tag("/"), map(is_not("/,"), |tag: &str| ShotTag::from(tag.trim())))
+            .parse(input)?;
+
+    Ok((rest, ShotTags::new(tags)))
+}
+
+/// Parses a 12-hour clock time such as `6:57:23 pm`. `12:xx am` is just after midnight, and
+/// `12:xx pm` is just after noon.
+fn time_parser(input: &str) -> IResult<'_, NaiveTime> {
+    let (rest, hours) = nom::character::complete::u32(input)?;
     let (rest, _) = tag(":")(rest)?;
     let (rest, minutes) = nom::character::complete::u32(rest)?;
     let (rest, _) = tag(":")(rest)?;
@@ -240,12 +646,17 @@ fn time_parser(input: &str) -> IResult<&str, NaiveTime> {
     let (rest, is_pm) =
         alt((value(false, tag_no_case("am")), value(true, tag_no_case("pm")))).parse(rest)?;
 
-    if is_pm {
-        hours += 12;
-    }
+    let hours_24 = match (hours, is_pm) {
+        (1..=11, false) => Some(hours),
+        (12, false) => Some(0),
+        (1..=11, true) => Some(hours + 12),
+        (12, true) => Some(12),
+        _ => None,
+    };
 
-    let parsed_time = NaiveTime::from_hms_opt(hours, minutes, seconds).ok_or(nom::Err::Failure(
-        nom::error::Error::from_external_error(
+    let parsed_time = hours_24
+        .and_then(|hours_24| NaiveTime::from_hms_opt(hours_24, minutes, seconds))
+        .ok_or(nom::Err::Failure(ShotMarkerNomError::from_external_error(
             input,
             ErrorKind::Verify,
             ShotMarkerCsvParserError::InvalidTime {
@@ -254,19 +665,18 @@ fn time_parser(input: &str) -> IResult<&str, NaiveTime> {
                 seconds,
                 is_pm,
             },
-        ),
-    ))?;
+        )))?;
 
     Ok((rest, parsed_time))
 }
 
-fn month_and_day_parser(year: i32, input: &str) -> IResult<&str, NaiveDate> {
+fn month_and_day_parser(year: i32, input: &str) -> IResult<'_, NaiveDate> {
     let (rest, month) = month_parser(input)?;
     let (rest, _) = nom::character::complete::space1(rest)?;
     let (rest, day) = month_day_parser(rest)?;
 
     let parsed_date = NaiveDate::from_ymd_opt(year, month, day).ok_or(nom::Err::Failure(
-        nom::error::Error::from_external_error(
+        ShotMarkerNomError::from_external_error(
             input,
             ErrorKind::Verify,
             ShotMarkerCsvParserError::InvalidDate {
@@ -280,7 +690,7 @@ fn month_and_day_parser(year: i32, input: &str) -> IResult<&str, NaiveDate> {
     Ok((rest, parsed_date))
 }
 
-fn date_parser(input: &str) -> IResult<&str, NaiveDate> {
+fn date_parser(input: &str) -> IResult<'_, NaiveDate> {
     let (rest, month) = month_parser(input)?;
     let (rest, _) = nom::character::complete::space1(rest)?;
     let (rest, day) = month_day_parser(rest)?;
@@ -288,7 +698,7 @@ fn date_parser(input: &str) -> IResult<&str, NaiveDate> {
     let (rest, year) = year_parser(rest)?;
 
     let parsed_date = NaiveDate::from_ymd_opt(year, month, day).ok_or(nom::Err::Failure(
-        nom::error::Error::from_external_error(
+        ShotMarkerNomError::from_external_error(
             input,
             ErrorKind::Verify,
             ShotMarkerCsvParserError::InvalidDate {
@@ -302,7 +712,7 @@ fn date_parser(input: &str) -> IResult<&str, NaiveDate> {
     Ok((rest, parsed_date))
 }
 
-fn month_parser(input: &str) -> IResult<&str, u32> {
+fn month_parser(input: &str) -> IResult<'_, u32> {
     alt((
         value(1, tag_no_case("Jan")),
         value(2, tag_no_case("Feb")),
@@ -320,39 +730,55 @@ fn month_parser(input: &str) -> IResult<&str, u32> {
     .parse(input)
 }
 
-fn month_day_parser(input: &str) -> IResult<&str, u32> { nom::character::complete::u32(input) }
+fn month_day_parser(input: &str) -> IResult<'_, u32> { nom::character::complete::u32(input) }
 
-fn year_parser(input: &str) -> IResult<&str, i32> { nom::character::complete::i32(input) }
+fn year_parser(input: &str) -> IResult<'_, i32> { nom::character::complete::i32(input) }
 
-fn parse_string_score(input: &str) -> IResult<&str, StringScore> {
+/// Parses a string total, either NRA style (`200-16X`) or ICFRA style (`75.10V`).
+fn parse_string_score(input: &str) -> IResult<'_, StringScore> {
     let (rest, points) = nom::character::complete::u32(input)?;
-    let (rest, _) = tag_no_case("-")(rest)?;
-    let (rest, x_count) = nom::character::complete::u32(rest)?;
-    let (rest, _) = tag_no_case("X")(rest)?;
+    let (rest, (x_count, convention)) = alt((
+        map(delimited(tag("-"), nom::character::complete::u32, tag_no_case("X")), |x_count| {
+            (x_count, ScoringConvention::Nra)
+        }),
+        map(delimited(tag("."), nom::character::complete::u32, tag_no_case("V")), |v_count| {
+            (v_count, ScoringConvention::Icfra)
+        }),
+    ))
+    .parse(rest)?;
 
     let string_score = StringScore {
         points,
         x_count,
+        convention,
     };
 
     Ok((rest, string_score))
 }
 
-fn parse_shot_score(input: &str) -> IResult<&str, ShotScore> {
-    let (rest, score) = opt(alt((parse_shot_score_x, parse_shot_score_numeric))).parse(input)?;
+fn parse_shot_score(input: &str) -> IResult<'_, ShotScore> {
+    let (rest, score) =
+        opt(alt((parse_shot_score_x, parse_shot_score_v, parse_shot_score_numeric)))
+            .parse(input)?;
 
     let score = score.unwrap_or(ShotScore::None);
 
     Ok((rest, score))
 }
 
-fn parse_shot_score_x(input: &str) -> IResult<&str, ShotScore> {
+fn parse_shot_score_x(input: &str) -> IResult<'_, ShotScore> {
     let (rest, _) = tag_no_case("X")(input)?;
 
     Ok((rest, ShotScore::X))
 }
 
-fn parse_shot_score_numeric(input: &str) -> IResult<&str, ShotScore> {
+fn parse_shot_score_v(input: &str) -> IResult<'_, ShotScore> {
+    let (rest, _) = tag_no_case("V")(input)?;
+
+    Ok((rest, ShotScore::V))
+}
+
+fn parse_shot_score_numeric(input: &str) -> IResult<'_, ShotScore> {
     let (rest, score) = nom::character::complete::u8(input)?;
 
     Ok((rest, ShotScore::Numeric(score)))
@@ -0,0 +1,229 @@
+use std::io::BufRead;
+
+use nom::error::{
+    ContextError,
+    ErrorKind,
+    ParseError,
+};
+
+use crate::{
+    ShotMarkerExportHeader,
+    error::{
+        ParseWarning,
+        ParseWarningKind,
+        ShotMarkerNomError,
+        ShotMarkerParseError,
+        ShotMarkerReadError,
+    },
+    parser::{
+        ParseOptions,
+        ParseState,
+        date_parser,
+        export_header_parser,
+        shot_string_parser,
+        unexpected_string_count,
+    },
+    string::ShotMarkerShotString,
+};
+
+/// Reads a ShotMarker CSV export one shot string at a time, so that only the string being parsed
+/// is held in memory rather than the whole export. This makes it suitable for full archives as
+/// well as a single day's export.
+///
+/// The export header is read up front by [`Self::new`], and the shot strings are then read by
+/// iterating over the reader. Rows and strings are handled according to the [`ParseOptions`], as
+/// with [`crate::parser::export_parser_with_options`]. Once the iterator has returned an error, it
+/// is finished.
+pub struct ShotMarkerExportReader<R> {
+    chunks:          ExportChunks<R>,
+    options:         ParseOptions,
+    header:          ShotMarkerExportHeader,
+    warnings:        Vec<ParseWarning>,
+    strings_read:    usize,
+    strings_skipped: usize,
+    finished:        bool,
+}
+
+impl<R: BufRead> ShotMarkerExportReader<R> {
+    pub fn new(reader: R, options: ParseOptions) -> Result<Self, ShotMarkerReadError> {
+        let mut chunks = ExportChunks {
+            reader,
+            next_string_header: None,
+            lines_read: 0,
+        };
+        let ExportChunk {
+            first_line,
+            text,
+        } = chunks.next_chunk()?.unwrap_or_default();
+
+        let (rest, header) = export_header_parser(&text)
+            .map_err(|error| ShotMarkerParseError::new(&text, error).offset_lines(first_line))?;
+
+        let mut reader = Self {
+            chunks,
+            options,
+            header,
+            warnings: Vec::new(),
+            strings_read: 0,
+            strings_skipped: 0,
+            finished: false,
+        };
+
+        // Anything between the export header and the first string can't be part of a string.
+        let rest = rest.trim_start();
+        if !rest.is_empty() {
+            let error = nom::Err::Failure(ShotMarkerNomError::add_context(
+                rest,
+                "Parsing shot string header",
+                ShotMarkerNomError::from_error_kind(rest, ErrorKind::Verify),
+            ));
+            reader.recover(ParseWarningKind::SkippedRow, &text, first_line, error)?;
+        }
+
+        Ok(reader)
+    }
+
+    pub fn header(&self) -> &ShotMarkerExportHeader { &self.header }
+
+    /// What has been skipped so far. Always empty in [`crate::parser::ParseMode::Strict`].
+    pub fn warnings(&self) -> &[ParseWarning] { &self.warnings }
+
+    /// Records `error` as a warning in lenient mode, or returns it otherwise.
+    fn recover(
+        &mut self,
+        kind: ParseWarningKind,
+        text: &str,
+        first_line: usize,
+        error: nom::Err<ShotMarkerNomError<'_>>,
+    ) -> Result<(), ShotMarkerParseError> {
+        let state = ParseState::new(self.options);
+        let result = state.recover(kind, error);
+        self.collect_warnings(state, text, first_line);
+
+        result.map_err(|error| ShotMarkerParseError::new(text, error).offset_lines(first_line))
+    }
+
+    fn collect_warnings(&mut self, state: ParseState<'_>, text: &str, first_line: usize) {
+        self.warnings.extend(state.warnings.into_inner().into_iter().map(|(kind, error)| {
+            ParseWarning {
+                kind,
+                error: ShotMarkerParseError::new(text, nom::Err::Failure(error))
+                    .offset_lines(first_line),
+            }
+        }));
+    }
+
+    fn read_string(&mut self) -> Result<Option<ShotMarkerShotString>, ShotMarkerReadError> {
+        loop {
+            let Some(ExportChunk {
+                first_line,
+                text,
+            }) = self.chunks.next_chunk()?
+            else {
+                return Ok(None);
+            };
+
+            let state = ParseState::new(self.options);
+            let result = shot_string_parser(&state, &text).map(|(_rest, shot_string)| shot_string);
+            let result = match result {
+                Ok(shot_string) => Ok(shot_string),
+                // Anything other than a string header starting the chunk can't be skipped past, so
+                // is treated the same as a string that failed part way through.
+                Err(nom::Err::Error(error)) => Err(nom::Err::Failure(error)),
+                Err(error) => Err(error),
+            };
+            let result = result.map_err(|error| {
+                state
+                    .recover(ParseWarningKind::SkippedString, error)
+                    .map_err(|error| ShotMarkerParseError::new(&text, error))
+            });
+            self.collect_warnings(state, &text, first_line);
+
+            match result {
+                Ok(shot_string) => {
+                    self.strings_read += 1;
+                    return Ok(Some(shot_string));
+                }
+                Err(Ok(())) => self.strings_skipped += 1,
+                Err(Err(error)) => return Err(error.offset_lines(first_line).into()),
+            }
+        }
+    }
+
+    /// Checks the number of strings read against the export header, once they've all been read.
+    fn check_string_count(&mut self) -> Result<(), ShotMarkerParseError> {
+        // Strings skipped in lenient mode were still counted by the export header.
+        if self.header.string_count == self.strings_read + self.strings_skipped {
+            return Ok(());
+        }
+
+        let lines_read = self.chunks.lines_read;
+        let error = unexpected_string_count("", self.header.string_count, self.strings_read);
+        self.recover(ParseWarningKind::UnexpectedStringCount, "", lines_read, error)
+    }
+}
+
+impl<R: BufRead> Iterator for ShotMarkerExportReader<R> {
+    type Item = Result<ShotMarkerShotString, ShotMarkerReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let result = match self.read_string() {
+            Ok(Some(shot_string)) => return Some(Ok(shot_string)),
+            Ok(None) => self.check_string_count().map_err(Into::into),
+            Err(error) => Err(error),
+        };
+        self.finished = true;
+
+        result.err().map(Err)
+    }
+}
+
+/// Splits an export into the export header and then one piece per shot string, each running up to
+/// the next line that starts a string.
+struct ExportChunks<R> {
+    reader:             R,
+    /// The string header line that ended the previous chunk.
+    next_string_header: Option<String>,
+    lines_read:         usize,
+}
+
+#[derive(Default)]
+struct ExportChunk {
+    /// 0-based line number of the start of `text` within the export.
+    first_line: usize,
+    text:       String,
+}
+
+impl<R: BufRead> ExportChunks<R> {
+    fn next_chunk(&mut self) -> std::io::Result<Option<ExportChunk>> {
+        let first_line = self.lines_read - usize::from(self.next_string_header.is_some());
+        let mut text = self.next_string_header.take().unwrap_or_default();
+
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            self.lines_read += 1;
+
+            if !text.trim().is_empty() && date_parser(line.trim_start()).is_ok() {
+                self.next_string_header = Some(line);
+                break;
+            }
+            text.push_str(&line);
+        }
+
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(ExportChunk {
+            first_line,
+            text,
+        }))
+    }
+}
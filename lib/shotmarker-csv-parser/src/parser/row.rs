@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use nom::{
+    Parser,
+    bytes::complete::tag,
+    combinator::all_consuming,
+    error::{
+        ErrorKind,
+        FromExternalError,
+    },
+};
+
+use crate::{
+    error::{
+        ShotMarkerCsvParserError,
+        ShotMarkerNomError,
+    },
+    parser::IResult,
+    string::column::{
+        ShotColumn,
+        ShotColumns,
+    },
+};
+
+/// A single shot or metrics row, split into cells and decoded by the string's column header.
+pub(crate) struct ShotRow<'a, 'c> {
+    columns: &'c ShotColumns,
+    /// Each cell runs from the start of the entry to the next `,` or the end of the line. Keeping
+    /// the rest of the input, rather than just the cell, lets errors point back into the export.
+    cells:   Vec<&'a str>,
+    /// The input following the row, standing in for any cells missing from a short row.
+    rest:    &'a str,
+}
+
+/// Splits a `,`-prefixed row into its cells. The blank first column isn't part of
+/// [`ShotColumns`], so it is dropped.
+pub(crate) fn shot_row_parser<'a, 'c>(
+    columns: &'c ShotColumns,
+    input: &'a str,
+) -> IResult<'a, ShotRow<'a, 'c>> {
+    let (row, _) = tag(",")(input)?;
+    let row_len = row.find(['\r', '\n']).unwrap_or(row.len());
+
+    let mut cells = vec![row];
+    cells.extend(row[..row_len].match_indices(',').map(|(idx, _)| &row[idx + 1..]));
+
+    let rest = &row[row_len..];
+    let row = ShotRow {
+        columns,
+        cells,
+        rest,
+    };
+
+    Ok((rest, row))
+}
+
+impl<'a> ShotRow<'a, '_> {
+    /// Decodes the cell for `column` with `parser`, which must consume all of it. Returns `None`
+    /// if the string has no such column.
+    pub(crate) fn value<O, F>(
+        &self,
+        column: ShotColumn,
+        parser: F,
+    ) -> Result<Option<O>, nom::Err<ShotMarkerNomError<'a>>>
+    where
+        F: Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>,
+    {
+        let Some(idx) = self.columns.position(column) else {
+            return Ok(None);
+        };
+        let cell = self.cells.get(idx).copied().unwrap_or(self.rest);
+
+        all_consuming(parser)
+            .parse(cell_text(cell))
+            .map(|(_rest, value)| Some(value))
+            .map_err(|error| error.map(|error| error.in_field(column.name(), cell)))
+    }
+
+    /// Like [`Self::value`], but for a row already known to be a shot or metrics row, so a bad
+    /// value is reported rather than backtracked from.
+    pub(crate) fn optional<O, F>(
+        &self,
+        column: ShotColumn,
+        parser: F,
+    ) -> Result<Option<O>, nom::Err<ShotMarkerNomError<'a>>>
+    where
+        F: Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>,
+    {
+        self.value(column, parser).map_err(|error| {
+            match error {
+                nom::Err::Error(error) => nom::Err::Failure(error),
+                error => error,
+            }
+        })
+    }
+
+    /// Like [`Self::optional`], for a column [`ShotColumns::missing_required`] has already
+    /// checked the header for.
+    pub(crate) fn required<O, F>(
+        &self,
+        column: ShotColumn,
+        parser: F,
+    ) -> Result<O, nom::Err<ShotMarkerNomError<'a>>>
+    where
+        F: Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>,
+    {
+        self.optional(column, parser)?.ok_or_else(|| {
+            nom::Err::Failure(ShotMarkerNomError::from_external_error(
+                self.cells[0],
+                ErrorKind::Verify,
+                ShotMarkerCsvParserError::MissingColumns {
+                    columns: vec![column.name().to_string()],
+                },
+            ))
+        })
+    }
+
+    /// The first non-empty cell, which labels `avg:` and `SD:` rows.
+    pub(crate) fn label(&self) -> Option<&'a str> {
+        self.cells.iter().map(|cell| cell_text(cell).trim()).find(|cell| !cell.is_empty())
+    }
+
+    /// The raw values of any columns that aren't a known [`ShotColumn`].
+    pub(crate) fn extras(&self) -> BTreeMap<String, String> {
+        self.columns
+            .extras()
+            .map(|(idx, name)| {
+                let value = self.cells.get(idx).map_or("", |cell| cell_text(cell));
+                (name.to_string(), value.to_string())
+            })
+            .collect()
+    }
+}
+
+fn cell_text(cell: &str) -> &str {
+    let cell_len = cell.find([',', '\r', '\n']).unwrap_or(cell.len());
+    &cell[..cell_len]
+}
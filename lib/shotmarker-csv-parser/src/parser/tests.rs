@@ -1,16 +1,212 @@
 use super::*;
-use crate::string::ShotMarkerStringMetrics;
+use crate::{
+    error::{
+        ParseWarningKind,
+        ShotMarkerParseErrorKind,
+        ShotMarkerReadError,
+    },
+    parser::reader::ShotMarkerExportReader,
+    string::{
+        ShotMarkerStringMetrics,
+        distance::{
+            Distance,
+            DistanceUnit,
+        },
+    },
+};
 
 use std::time::Instant;
 
 #[test]
 fn shot_string_metrics_parser_valid_input() {
     let input = ",,,,avg:,1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,9.0,10.0,11.0,12.0,13.0,\n,,,,SD:,0.1,0.2,0.3,0.4,0.5,0.6,0.7,0.8,0.9,1.0,1.1,1.2,1.3,\n";
-    let result = shot_string_metrics_parser(input);
+    let result = shot_string_metrics_parser(&ShotColumns::default(), input);
     assert!(result.is_ok());
     let (rest, metrics) = result.unwrap();
     assert_eq!(rest, "");
-    assert!(matches!(metrics, ShotMarkerStringMetrics {}));
+    let ShotMarkerStringMetrics {
+        average,
+        std_deviation,
+    } = metrics;
+    assert_eq!(average.position.mm.x, 1.0);
+    assert_eq!(average.position.mm.y, 2.0);
+    assert_eq!(average.position.inch.x, 3.0);
+    assert_eq!(average.position.mil.y, 8.0);
+    assert_eq!(average.velocity.ms, 9.0);
+    assert_eq!(average.velocity.fps, 10.0);
+    assert_eq!(average.yaw, 11.0);
+    assert_eq!(average.pitch, 12.0);
+    assert_eq!(average.quality, Some(13.0));
+    assert_eq!(std_deviation.position.moa.x, 0.5);
+    assert_eq!(std_deviation.velocity.fps, 1.0);
+    assert_eq!(std_deviation.quality, Some(1.3));
+}
+
+#[test]
+fn shot_string_metrics_parser_too_few_values() {
+    let input = ",,,,avg:,1.0,2.0,3.0,4.0,5.0,6.0,7.0,8.0,\n,,,,SD:,0.1,0.2,0.3,0.4,0.5,0.6,0.7,0.8,0.9,1.0,1.1,1.2,1.3,\n";
+    let result = shot_string_metrics_parser(&ShotColumns::default(), input);
+    assert!(result.is_err());
+}
+
+#[test]
+fn shot_tags_parser_combinations() {
+    let (rest, tags) = shot_tags_parser(",X").unwrap();
+    assert_eq!(rest, ",X");
+    assert!(tags.is_empty());
+
+    let (rest, tags) = shot_tags_parser("sighter,X").unwrap();
+    assert_eq!(rest, ",X");
+    assert!(tags.is_sighter());
+    assert!(!tags.is_hidden());
+
+    let (_rest, tags) = shot_tags_parser("hidden/warning-angle,").unwrap();
+    assert!(tags.is_hidden());
+    assert!(tags.has_warning());
+    assert!(tags.contains(&ShotTag::WarningAngle));
+    assert_eq!(tags.to_string(), "hidden/warning-angle");
+
+    let (_rest, tags) = shot_tags_parser("sighter/new-firmware-tag,").unwrap();
+    assert!(tags.is_sighter());
+    assert!(tags.contains(&ShotTag::Unknown("new-firmware-tag".to_string())));
+    assert_eq!(tags.to_string(), "sighter/new-firmware-tag");
+}
+
+#[test]
+fn time_parser_twelve_hour_clock() {
+    let cases = [
+        ("12:05:10 am", NaiveTime::from_hms_opt(0, 5, 10)),
+        ("1:00:00 am", NaiveTime::from_hms_opt(1, 0, 0)),
+        ("11:59:59 am", NaiveTime::from_hms_opt(11, 59, 59)),
+        ("12:05:10 pm", NaiveTime::from_hms_opt(12, 5, 10)),
+        ("1:00:00 pm", NaiveTime::from_hms_opt(13, 0, 0)),
+        ("11:59:59 PM", NaiveTime::from_hms_opt(23, 59, 59)),
+    ];
+    for (input, expected) in cases {
+        let (rest, time) = time_parser(input).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(Some(time), expected, "Parsing {input:?}");
+    }
+}
+
+#[test]
+fn time_parser_invalid_hours() {
+    assert!(time_parser("0:05:10 am").is_err());
+    assert!(time_parser("13:05:10 pm").is_err());
+    assert!(time_parser("11:60:00 pm").is_err());
+}
+
+#[test]
+fn shot_string_parser_midnight_rollover() {
+    let input = "Jan 31 2025,Late Shooter,3,600y,20-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,10,72,10,2.85,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,12:00:05 am,2,,X,22,70,0.85,2.76,0.14,0.44,0.04,0.13,620.8,2037,-0.5,-3.2,,
+";
+    let (_rest, shot_string) = shot_string_parser(&ParseState::default(), input).unwrap();
+    let [first, second] = &shot_string.shots[..] else {
+        panic!("Expected two shots, found {}", shot_string.shots.len());
+    };
+
+    assert_eq!(first.date_time.to_string(), "2025-01-31 23:59:40");
+    assert_eq!(second.date_time.to_string(), "2025-02-01 00:00:05");
+    assert!(first.date_time < second.date_time);
+
+    let utc = second.utc_date_time(&chrono_tz::America::Los_Angeles).unwrap();
+    assert_eq!(utc.to_rfc3339(), "2025-02-01T08:00:05+00:00");
+}
+
+#[test]
+fn shot_string_parser_reordered_and_extra_columns() {
+    let input = "Jan 31 2025,Late Shooter,3,600y,20-1X
+,id,time,score,x (mm),y (mm),x (moa),y (moa),x (mil),y (mil),v (fps),yaw (deg),pitch (deg),wind (mph),quality
+,1,11:59:40 pm,10,72,10,0.45,0.06,0.13,0.02,2005,-0.6,-3.2,7,,
+,2,11:59:55 pm,X,22,70,0.14,0.44,0.04,0.13,2037,-0.5,-3.2,,0.4,
+,,,avg:,47,40,0.3,0.25,0.09,0.08,2021,-0.55,-3.2,,0.4,
+,,,SD:,35.36,42.43,0.22,0.27,0.06,0.08,22.6,0.07,0,,0,
+";
+    let (rest, shot_string) = shot_string_parser(&ParseState::default(), input).unwrap();
+    assert_eq!(rest, "");
+    assert!(!shot_string.columns.contains(ShotColumn::Tags));
+    assert_eq!(shot_string.columns.extras().collect::<Vec<_>>(), vec![(12, "wind (mph)")]);
+
+    let [first, second] = &shot_string.shots[..] else {
+        panic!("Expected two shots, found {}", shot_string.shots.len());
+    };
+    assert_eq!(first.id, "1");
+    assert_eq!(first.time, NaiveTime::from_hms_opt(23, 59, 40).unwrap());
+    assert_eq!(first.score, ShotScore::Numeric(10));
+    assert!(first.tags.is_empty());
+    assert_eq!(first.position.mm.x, 72.0);
+    assert_eq!(first.position.inch.x, 72.0 / 25.4);
+    assert_eq!(first.velocity.fps, 2005);
+    assert_eq!(first.quality, None);
+    assert_eq!(first.extras.get("wind (mph)").map(String::as_str), Some("7"));
+    assert_eq!(second.score, ShotScore::X);
+    assert_eq!(second.quality, Some(0.4));
+    assert_eq!(second.extras.get("wind (mph)").map(String::as_str), Some(""));
+
+    let metrics = shot_string.metrics.expect("String has avg/SD rows");
+    assert_eq!(metrics.average.position.mm.x, 47.0);
+    assert_eq!(metrics.average.velocity.fps, 2021.0);
+    assert_eq!(metrics.std_deviation.position.mil.y, 0.08);
+}
+
+#[test]
+fn shot_string_parser_icfra_v_bulls() {
+    let input = "Jan 31 2025,Late Shooter,3,1000y,15.2V
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,5,172,10,6.77,0.39,0.65,0.04,0.19,0.01,611.1,2005,-0.6,-3.2,,
+,11:59:55 pm,2,,V,22,70,0.85,2.76,0.08,0.26,0.02,0.08,620.8,2037,-0.5,-3.2,,
+,12:00:10 am,3,,v,-40,12,-1.57,0.47,-0.15,0.05,-0.04,0.01,615.4,2019,-0.5,-3.2,,
+";
+    let (_rest, shot_string) = shot_string_parser(&ParseState::default(), input).unwrap();
+    let scores: Vec<_> = shot_string.shots.iter().map(|shot| shot.score).collect();
+    assert_eq!(scores, [ShotScore::Numeric(5), ShotScore::V, ShotScore::V]);
+    assert!(ShotScore::Numeric(5) < ShotScore::V);
+    assert!(ShotScore::None < ShotScore::Numeric(2));
+
+    assert_eq!(shot_string.score.points, 15);
+    assert_eq!(shot_string.score.x_count, 2);
+    assert_eq!(shot_string.score.convention, ScoringConvention::Icfra);
+    assert_eq!(shot_string.score.to_string(), "15.2V");
+
+    let (_rest, more_v_bulls) = parse_string_score("15.3V").unwrap();
+    let (_rest, fewer_points) = parse_string_score("14.3V").unwrap();
+    assert!(shot_string.score < more_v_bulls);
+    assert!(fewer_points < shot_string.score);
+
+    let (_rest, nra) = parse_string_score("200-16X").unwrap();
+    assert_eq!(nra.convention, ScoringConvention::Nra);
+    assert_eq!(nra.to_string(), "200-16X");
+
+    // The same numbers under different conventions aren't the same score.
+    let (_rest, icfra) = parse_string_score("75.10V").unwrap();
+    let (_rest, nra) = parse_string_score("75-10X").unwrap();
+    assert_ne!(icfra, nra);
+    assert!(icfra < nra || nra < icfra);
+}
+
+#[test]
+fn shot_string_parser_missing_required_columns() {
+    let input = "Jan 31 2025,Late Shooter,3,600y,20-1X
+,time,id,tags,score,x (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),yaw (deg),pitch (deg),quality
+,11:59:40 pm,1,,10,72,2.85,0.39,0.45,0.06,0.13,0.02,-0.6,-3.2,,
+";
+    let error = export_parser(&format!(
+        "ShotMarker Archived Data (generated Jan 31 2025)\nExported 1 string from Jan 31 of 1 total in archive\n{input}"
+    ))
+    .unwrap_err();
+
+    assert_eq!(error.line, 4);
+    let ShotMarkerParseErrorKind::Csv(ShotMarkerCsvParserError::MissingColumns {
+        columns,
+    }) = error.kind
+    else {
+        panic!("Expected missing columns, found {:?}", error.kind);
+    };
+    assert_eq!(columns, vec!["y (mm)", "v (fps)"]);
 }
 
 #[test]
@@ -80,6 +276,234 @@ fn export_parser_different_dates() {
     assert!(export.strings.is_empty());
 }
 
+#[test]
+fn export_parser_error_points_at_bad_shot_field() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 string from Jan 31 of 1 total in archive
+
+Jan 31 2025,Late Shooter,3,600y,20-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,10,72,10,2.85,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,11:59:55 pm,2,,X,abc,70,0.85,2.76,0.14,0.44,0.04,0.13,620.8,2037,-0.5,-3.2,,
+";
+    let error = export_parser(input).unwrap_err();
+
+    assert_eq!(error.line, 8);
+    assert_eq!(error.column, 19);
+    assert_eq!(error.field.as_deref(), Some("x (mm)"));
+    assert_eq!(error.string_header.as_deref(), Some("Jan 31 2025,Late Shooter,3,600y,20-1X"));
+    assert!(matches!(error.kind, ShotMarkerParseErrorKind::UnexpectedInput { .. }));
+
+    let label = miette::Diagnostic::labels(&error).unwrap().next().unwrap();
+    assert_eq!(label.offset(), 18);
+    assert_eq!(label.len(), "abc".len());
+}
+
+#[test]
+fn export_parser_error_wraps_invalid_shot_time() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 string from Jan 31 of 1 total in archive
+Jan 31 2025,Late Shooter,3,600y,20-1X
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,13:59:40 pm,1,,10,72,10,2.85,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+";
+    let error = export_parser(input).unwrap_err();
+
+    assert_eq!(error.line, 5);
+    assert_eq!(error.column, 2);
+    assert_eq!(error.field.as_deref(), Some("time"));
+    assert!(matches!(
+        error.kind,
+        ShotMarkerParseErrorKind::Csv(ShotMarkerCsvParserError::InvalidTime {
+            hours: 13,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn export_parser_error_on_unrecognised_row() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 string from Jan 31 of 1 total in archive
+Jan 31 2025,Late Shooter,3,600y,20-1X
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,10,72,10,2.85,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,not a time,2,,X,22,70,0.85,2.76,0.14,0.44,0.04,0.13,620.8,2037,-0.5,-3.2,,
+";
+    let error = export_parser(input).unwrap_err();
+
+    assert_eq!(error.line, 6);
+    assert_eq!(error.field.as_deref(), Some("time"));
+    assert!(error.string_header.is_some());
+}
+
+#[test]
+fn export_parser_lenient_skips_bad_rows_and_strings() {
+    let original = include_str!("../../example-files/SM_shotslog_Jan_07.csv");
+    let damaged = original
+        .replacen(",6:57:50 pm,S2,sighter,10,22,", ",6:57:50 pm,S2,sighter,10,twenty-two,", 1)
+        .replacen(
+            "Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14X",
+            "Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14",
+            1,
+        );
+
+    let error = export_parser(&damaged).unwrap_err();
+    assert_eq!(error.line, 8);
+
+    let (_rest, original_export) = export_parser(original).unwrap();
+    let ParsedExport {
+        rest,
+        export,
+        warnings,
+    } = export_parser_with_options(
+        &damaged,
+        ParseOptions {
+            mode: ParseMode::Lenient,
+        },
+    )
+    .unwrap();
+
+    assert!(rest.is_empty());
+    assert_eq!(export.string_count, 53);
+    assert_eq!(export.strings.len(), 52);
+    assert_eq!(export.strings[0].shots.len(), original_export.strings[0].shots.len() - 1);
+    assert!(export.strings[0].shots.iter().all(|shot| shot.id != "S2"));
+    assert!(export.strings[0].metrics.is_some());
+    assert_eq!(export.strings[1].name, original_export.strings[2].name);
+
+    let [row_warning, string_warning] = &warnings[..] else {
+        panic!("Expected two warnings, found {warnings:?}");
+    };
+    assert_eq!(row_warning.kind, ParseWarningKind::SkippedRow);
+    assert_eq!(row_warning.error.line, 8);
+    assert_eq!(row_warning.error.field.as_deref(), Some("x (mm)"));
+    assert_eq!(string_warning.kind, ParseWarningKind::SkippedString);
+    assert_eq!(string_warning.error.line, 35);
+}
+
+#[test]
+fn export_parser_lenient_reports_string_count() {
+    let input = "ShotMarker Archived Data (generated Jan 1 2023)\nExported 2 strings from Jan 1 of 2 total in archive\n";
+
+    assert!(export_parser(input).is_err());
+
+    let parsed = export_parser_with_options(
+        input,
+        ParseOptions {
+            mode: ParseMode::Lenient,
+        },
+    )
+    .unwrap();
+    assert!(parsed.export.strings.is_empty());
+    assert_eq!(parsed.warnings.len(), 1);
+    assert_eq!(parsed.warnings[0].kind, ParseWarningKind::UnexpectedStringCount);
+}
+
+#[test]
+fn export_reader_matches_export_parser() {
+    let example_file_contents = [
+        include_str!("../../example-files/SM_shotslog_Jan_07.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_14.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_21.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_28.csv"),
+    ];
+    for (idx, example_file_content) in example_file_contents.iter().enumerate() {
+        let (_rest, export) = export_parser(example_file_content).unwrap();
+        let reader =
+            ShotMarkerExportReader::new(example_file_content.as_bytes(), ParseOptions::default())
+                .unwrap();
+
+        assert_eq!(reader.header().generated_date, export.generated_date);
+        assert_eq!(reader.header().string_count, export.string_count);
+        assert_eq!(reader.header().string_date, export.string_date);
+
+        let strings = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            format!("{strings:?}"),
+            format!("{:?}", export.strings),
+            "Reader and parser disagree on example file {idx}"
+        );
+    }
+}
+
+#[test]
+fn export_reader_errors_point_into_the_export() {
+    let original = include_str!("../../example-files/SM_shotslog_Jan_07.csv");
+    let damaged = original.replacen(
+        "Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14X",
+        "Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14",
+        1,
+    );
+
+    let mut reader =
+        ShotMarkerExportReader::new(damaged.as_bytes(), ParseOptions::default()).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+
+    let Some(Err(ShotMarkerReadError::Parse(error))) = reader.next() else {
+        panic!("Expected the second string to fail to parse");
+    };
+    assert_eq!(error.line, 35);
+    assert_eq!(
+        error.string_header.as_deref(),
+        Some("Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14")
+    );
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn export_reader_lenient_skips_bad_rows_and_strings() {
+    let original = include_str!("../../example-files/SM_shotslog_Jan_07.csv");
+    let damaged = original
+        .replacen(",6:57:50 pm,S2,sighter,10,22,", ",6:57:50 pm,S2,sighter,10,twenty-two,", 1)
+        .replacen(
+            "Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14X",
+            "Jan 07 2025,Ethan Blackwood F Open,15,600y,200-14",
+            1,
+        );
+    let lenient = ParseOptions {
+        mode: ParseMode::Lenient,
+    };
+
+    let ParsedExport {
+        export,
+        warnings,
+        ..
+    } = export_parser_with_options(&damaged, lenient).unwrap();
+    let mut reader = ShotMarkerExportReader::new(damaged.as_bytes(), lenient).unwrap();
+    let strings = (&mut reader).collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(format!("{strings:?}"), format!("{:?}", export.strings));
+    assert_eq!(reader.warnings().len(), warnings.len());
+    for (reader_warning, warning) in reader.warnings().iter().zip(&warnings) {
+        assert_eq!(reader_warning.kind, warning.kind);
+        assert_eq!(reader_warning.error.line, warning.error.line);
+        assert_eq!(reader_warning.error.column, warning.error.column);
+        assert_eq!(reader_warning.error.field, warning.error.field);
+    }
+}
+
+#[test]
+fn export_reader_reports_string_count() {
+    let input = "ShotMarker Archived Data (generated Jan 1 2023)\nExported 2 strings from Jan 1 of 2 total in archive\n";
+
+    let mut reader =
+        ShotMarkerExportReader::new(input.as_bytes(), ParseOptions::default()).unwrap();
+    assert!(matches!(reader.next(), Some(Err(ShotMarkerReadError::Parse(_)))));
+
+    let mut reader = ShotMarkerExportReader::new(
+        input.as_bytes(),
+        ParseOptions {
+            mode: ParseMode::Lenient,
+        },
+    )
+    .unwrap();
+    assert!(reader.next().is_none());
+    assert_eq!(reader.warnings().len(), 1);
+    assert_eq!(reader.warnings()[0].kind, ParseWarningKind::UnexpectedStringCount);
+}
+
 #[test]
 fn example_files() {
     let example_file_contents = [
@@ -110,3 +534,108 @@ fn example_files() {
         );
     }
 }
+
+#[test]
+fn example_file_shot_tags() {
+    let (_rest, export) =
+        export_parser(include_str!("../../example-files/SM_shotslog_Jan_21.csv")).unwrap();
+    let shots = export.strings.iter().flat_map(|string| &string.shots);
+
+    let mut sighters = 0;
+    let mut hidden = 0;
+    for shot in shots {
+        assert!(
+            !shot.tags.iter().any(|tag| matches!(tag, ShotTag::Unknown(_))),
+            "Unexpected unknown tag on shot {}: {}",
+            shot.id,
+            shot.tags
+        );
+        if shot.is_sighter() {
+            sighters += 1;
+        }
+        if shot.is_hidden() {
+            hidden += 1;
+        }
+    }
+    assert!(sighters > 0);
+    assert!(hidden > 0);
+}
+
+#[test]
+fn example_file_string_metrics() {
+    let (_rest, export) =
+        export_parser(include_str!("../../example-files/SM_shotslog_Jan_07.csv")).unwrap();
+    let metrics = export.strings[0].metrics.expect("First string has avg/SD rows");
+
+    assert_eq!(metrics.average.position.mm.x, 10.0);
+    assert_eq!(metrics.average.position.mm.y, -20.0);
+    assert_eq!(metrics.average.velocity.ms, 618.5);
+    assert_eq!(metrics.average.velocity.fps, 2029.2);
+    assert_eq!(metrics.average.quality, Some(0.2));
+    assert_eq!(metrics.std_deviation.position.inch.y, 1.04);
+    assert_eq!(metrics.std_deviation.velocity.fps, 8.6);
+    assert_eq!(metrics.std_deviation.pitch, 0.11);
+}
+
+#[test]
+fn example_file_string_distance_and_target() {
+    let (_rest, export) =
+        export_parser(include_str!("../../example-files/SM_shotslog_Jan_07.csv")).unwrap();
+    let shot_string = &export.strings[0];
+
+    assert_eq!(shot_string.target, StringTarget::Lane(16));
+    assert_eq!(
+        shot_string.distance,
+        StringDistance::Distance(Distance {
+            value: 600,
+            unit:  DistanceUnit::Yards,
+        })
+    );
+    assert_eq!(shot_string.distance.yards(), Some(600.0));
+    assert!((shot_string.distance.meters().unwrap() - 548.64).abs() < 1e-9);
+}
+
+#[test]
+fn shot_string_parser_trims_padded_target_and_distance() {
+    let input = "Jan 31 2025, Late Shooter , 3 , 600y ,20-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,10,72,10,2.85,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,12:00:05 am,2,,X,22,70,0.85,2.76,0.14,0.44,0.04,0.13,620.8,2037,-0.5,-3.2,,
+";
+    let (_rest, shot_string) = shot_string_parser(&ParseState::default(), input).unwrap();
+
+    assert_eq!(shot_string.name, " Late Shooter ");
+    assert_eq!(shot_string.target, StringTarget::Lane(3));
+    assert_eq!(shot_string.distance.yards(), Some(600.0));
+}
+
+#[test]
+fn string_distance_and_target_fall_back_to_raw_text() {
+    assert_eq!(
+        StringDistance::from("300m").distance(),
+        Some(Distance {
+            value: 300,
+            unit:  DistanceUnit::Meters,
+        })
+    );
+    assert_eq!(StringDistance::from("300m").to_string(), "300m");
+
+    for raw in ["", "600", "600 yds", "0600y", "long"] {
+        let distance = StringDistance::from(raw);
+        assert_eq!(distance, StringDistance::Unknown(raw.to_string()));
+        assert_eq!(distance.yards(), None);
+        assert_eq!(distance.to_string(), raw);
+    }
+
+    assert_eq!(StringTarget::from("8").lane(), Some(8));
+    for raw in ["", "08", " 8", "8A"] {
+        let target = StringTarget::from(raw);
+        assert_eq!(target, StringTarget::Unknown(raw.to_string()));
+        assert_eq!(target.to_string(), raw);
+    }
+
+    let mut targets = ["10", "B", "9"].map(StringTarget::from);
+    targets.sort();
+    assert_eq!(targets.map(|target| target.to_string()), ["9", "10", "B"]);
+}
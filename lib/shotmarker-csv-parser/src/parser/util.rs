@@ -1,10 +1,17 @@
 use nom::{
     Parser,
+    bytes::complete::tag,
     character::complete::multispace0,
+    combinator::{
+        cut,
+        peek,
+    },
     error::ParseError,
     sequence::delimited,
 };
 
+use crate::error::ShotMarkerNomError;
+
 /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
 /// trailing whitespace, returning the output of `inner`.
 pub fn ws<'a, O, E: ParseError<&'a str>, F>(inner: F) -> impl Parser<&'a str, Output = O, Error = E>
@@ -13,3 +20,31 @@ where
 {
     delimited(multispace0, inner, multispace0)
 }
+
+/// A combinator that attributes any error from `inner` to the CSV column `name`, pointing at the
+/// start of the cell rather than wherever within it `inner` gave up.
+pub(crate) fn field<'a, O, F>(
+    name: &'static str,
+    mut inner: F,
+) -> impl Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>
+where
+    F: Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>,
+{
+    move |input: &'a str| {
+        let cell = input.strip_prefix(',').unwrap_or(input);
+        inner.parse(input).map_err(|error| error.map(|error| error.in_field(name, cell)))
+    }
+}
+
+/// A combinator for a `,`-prefixed CSV column `name` in a row that has already been recognised, so
+/// a failure to parse it (or anything left over in the cell) is reported rather than backtracked
+/// from.
+pub(crate) fn column<'a, O, F>(
+    name: &'static str,
+    inner: F,
+) -> impl Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>
+where
+    F: Parser<&'a str, Output = O, Error = ShotMarkerNomError<'a>>,
+{
+    field(name, cut(delimited(tag(","), inner, peek(tag(",")))))
+}
@@ -0,0 +1,98 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    string::{
+        ScoringConvention,
+        StringScore,
+    },
+    validation::ScoredShot,
+};
+
+/// Points over the final shots of a competitor's match, for breaking ties that remain after the
+/// total and X count, NRA style: the last 10 shots, then the last 5, then the last shot.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CountBack {
+    pub last_ten:  u32,
+    pub last_five: u32,
+    pub last_shot: u32,
+}
+
+/// A competitor's total over the strings they fired in a match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    pub score:        StringScore,
+    pub string_count: usize,
+    /// The points of each scored shot, in the order they were fired.
+    shot_points:      Vec<u32>,
+}
+
+impl Aggregate {
+    pub fn new(convention: ScoringConvention) -> Self {
+        Self {
+            score:        StringScore {
+                points: 0,
+                x_count: 0,
+                convention,
+            },
+            string_count: 0,
+            shot_points:  Vec::new(),
+        }
+    }
+
+    /// Adds a string's score to the total, and its scored shots to those counted back over.
+    /// Strings must be added in the order they were fired.
+    pub fn add_string<S: ScoredShot>(&mut self, score: &StringScore, shots: &[S]) {
+        self.score.points += score.points;
+        self.score.x_count += score.x_count;
+        self.string_count += 1;
+        self.shot_points.extend(
+            shots
+                .iter()
+                .filter(|shot| shot.is_scored())
+                .map(|shot| shot.score().points(self.score.convention)),
+        );
+    }
+
+    pub fn count_back(&self) -> CountBack {
+        let last = |count: usize| {
+            self.shot_points[self.shot_points.len().saturating_sub(count)..].iter().sum()
+        };
+
+        CountBack {
+            last_ten:  last(10),
+            last_five: last(5),
+            last_shot: last(1),
+        }
+    }
+
+    /// What competitors are ranked by, with the best being the greatest.
+    fn ranking(&self) -> (&StringScore, CountBack) { (&self.score, self.count_back()) }
+}
+
+/// Ranks `entries` best first by their aggregates: by points, then X count, then count-back.
+/// Returns each entry with its place, where entries still tied after count-back share a place and
+/// the places after them are skipped.
+pub fn rank<T>(mut entries: Vec<T>, aggregate: impl Fn(&T) -> &Aggregate) -> Vec<(usize, T)> {
+    entries.sort_by(|a, b| aggregate(b).ranking().cmp(&aggregate(a).ranking()));
+
+    let mut ranked: Vec<(usize, T)> = Vec::with_capacity(entries.len());
+    for (idx, entry) in entries.into_iter().enumerate() {
+        let place = match ranked.last() {
+            Some((place, previous))
+                if aggregate(previous).ranking() == aggregate(&entry).ranking() =>
+            {
+                *place
+            }
+            _ => idx + 1,
+        };
+        ranked.push((place, entry));
+    }
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests;
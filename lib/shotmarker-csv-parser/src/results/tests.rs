@@ -0,0 +1,105 @@
+use super::*;
+use crate::string::shot::{
+    ShotScore,
+    ShotTags,
+};
+
+struct Shot {
+    score: ShotScore,
+    tags:  ShotTags,
+}
+
+impl ScoredShot for Shot {
+    fn score(&self) -> ShotScore { self.score }
+
+    fn tags(&self) -> &ShotTags { &self.tags }
+}
+
+/// Parses shots written like `X 10 9 sighter:X`, in the order they were fired.
+fn shots(shots: &str) -> Vec<Shot> {
+    shots
+        .split_whitespace()
+        .map(|shot| {
+            let (tags, score) = shot.split_once(':').unwrap_or(("", shot));
+            let score = match score {
+                "X" => ShotScore::X,
+                ring => ShotScore::Numeric(ring.parse().unwrap()),
+            };
+            Shot {
+                score,
+                tags: tags.into(),
+            }
+        })
+        .collect()
+}
+
+fn aggregate(strings: &[&str]) -> Aggregate {
+    let mut aggregate = Aggregate::new(ScoringConvention::Nra);
+    for string in strings {
+        let shots = shots(string);
+        let score = crate::validation::computed_score(&shots, &aggregate.score);
+        aggregate.add_string(&score, &shots);
+    }
+
+    aggregate
+}
+
+#[test]
+fn aggregate_totals_strings() {
+    let aggregate = aggregate(&["sighter:9 X 10 9", "10 X 8 hidden:7"]);
+
+    assert_eq!(aggregate.string_count, 2);
+    assert_eq!(aggregate.score.points, 57);
+    assert_eq!(aggregate.score.x_count, 2);
+    assert_eq!(
+        aggregate.count_back(),
+        CountBack {
+            last_ten:  57,
+            last_five: 47,
+            last_shot: 8,
+        }
+    );
+}
+
+#[test]
+fn rank_breaks_ties_by_x_count_then_count_back() {
+    let entries = vec![
+        ("fewer X's", aggregate(&["10 10 10 10 10 10 10 10 10 10 X 9"])),
+        ("weaker last five", aggregate(&["X X 10 10 10 10 10 9 10 10 10 10"])),
+        ("lower total", aggregate(&["X X X X X X X X X X X 8"])),
+        ("stronger last ten", aggregate(&["9 X X 10 10 10 10 10 10 10 10 10"])),
+        ("weaker last shot", aggregate(&["X X 10 10 10 10 10 10 10 10 10 9"])),
+        ("stronger last five", aggregate(&["X X 10 10 9 10 10 10 10 10 10 10"])),
+    ];
+
+    let ranked: Vec<_> = rank(entries, |(_, aggregate)| aggregate)
+        .into_iter()
+        .map(|(place, (name, _))| (place, name))
+        .collect();
+
+    assert_eq!(
+        ranked,
+        vec![
+            (1, "stronger last ten"),
+            (2, "stronger last five"),
+            (3, "weaker last five"),
+            (4, "weaker last shot"),
+            (5, "fewer X's"),
+            (6, "lower total"),
+        ]
+    );
+}
+
+#[test]
+fn rank_shares_places_for_unbroken_ties() {
+    let entries = vec![
+        ("first", aggregate(&["X 10 9"])),
+        ("second", aggregate(&["10 X", "9"])),
+        ("third", aggregate(&["9 9 9"])),
+    ];
+
+    let places: Vec<_> =
+        rank(entries, |(_, aggregate)| aggregate).into_iter().map(|(place, _)| place).collect();
+
+    assert_eq!(places, vec![1, 1, 3]);
+}
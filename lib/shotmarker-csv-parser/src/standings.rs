@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::string::{
+    ScoringConvention,
+    StringScore,
+};
+
+/// How a league's matches are combined into its season standings. Shooters are ranked by the
+/// total points of the matches that count, with ties broken by `tie_breakers` in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StandingsRules {
+    /// How many of a shooter's best matches count, or `None` for all of them.
+    pub best_matches: Option<usize>,
+    /// How many of a shooter's worst matches are dropped before the best are counted. A shooter's
+    /// best match is never dropped.
+    pub drop_worst:   usize,
+    /// How many matches a shooter must have shot to be placed. Shooters with fewer are listed
+    /// after those placed.
+    pub min_matches:  usize,
+    pub tie_breakers: Vec<TieBreaker>,
+}
+
+impl Default for StandingsRules {
+    fn default() -> Self {
+        Self {
+            best_matches: None,
+            drop_worst:   0,
+            min_matches:  0,
+            tie_breakers: vec![TieBreaker::XCount, TieBreaker::BestMatch],
+        }
+    }
+}
+
+/// A way of ordering shooters whose counted matches total the same points. Higher is better for
+/// each of them.
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TieBreaker {
+    /// The score of the shooter's best counted match.
+    BestMatch,
+    /// The number of matches the shooter shot, counted or not.
+    MatchesShot,
+    /// The total X (or V-bull) count of the shooter's counted matches.
+    XCount,
+}
+
+/// A shooter's season under a league's [`StandingsRules`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonTotal {
+    /// The total of the counted matches.
+    pub score:        StringScore,
+    /// Whether each of the match scores the total was made from counts towards it.
+    pub counted:      Vec<bool>,
+    pub matches_shot: usize,
+    pub qualified:    bool,
+    best_match:       Option<StringScore>,
+}
+
+impl StandingsRules {
+    /// Totals a shooter's match `scores`, dropping those that don't count.
+    pub fn total(&self, scores: &[StringScore], convention: ScoringConvention) -> SeasonTotal {
+        let mut best_first: Vec<_> = (0..scores.len()).collect();
+        // Stable, so that of equal scores the earlier matches are the ones counted.
+        best_first.sort_by(|&a, &b| scores[b].cmp(&scores[a]));
+        let kept = scores.len() - self.drop_worst.min(scores.len().saturating_sub(1));
+        let counted_count = self.best_matches.map_or(kept, |best| best.min(kept));
+
+        let mut counted = vec![false; scores.len()];
+        let mut score = StringScore {
+            points: 0,
+            x_count: 0,
+            convention,
+        };
+        for &idx in &best_first[..counted_count] {
+            counted[idx] = true;
+            score.points += scores[idx].points;
+            score.x_count += scores[idx].x_count;
+        }
+
+        SeasonTotal {
+            score,
+            counted,
+            matches_shot: scores.len(),
+            qualified: scores.len() >= self.min_matches,
+            best_match: best_first
+                .first()
+                .filter(|_| counted_count > 0)
+                .map(|&idx| scores[idx].clone()),
+        }
+    }
+
+    /// Orders two season totals by points and then the tie-breakers, with the better greater.
+    pub fn compare(&self, a: &SeasonTotal, b: &SeasonTotal) -> Ordering {
+        let tie_breaks = self.tie_breakers.iter().map(|tie_breaker| {
+            match tie_breaker {
+                TieBreaker::BestMatch => a.best_match.cmp(&b.best_match),
+                TieBreaker::MatchesShot => a.matches_shot.cmp(&b.matches_shot),
+                TieBreaker::XCount => a.score.x_count.cmp(&b.score.x_count),
+            }
+        });
+
+        a.score.points.cmp(&b.score.points).then(tie_breaks.fold(Ordering::Equal, Ordering::then))
+    }
+
+    /// Ranks `entries` best first by their season totals, with qualified shooters placed ahead of
+    /// those who aren't, who are left without a place. Shooters still tied after the tie-breakers
+    /// share a place, and the places after them are skipped.
+    pub fn rank<T>(
+        &self,
+        mut entries: Vec<T>,
+        total: impl Fn(&T) -> &SeasonTotal,
+    ) -> Vec<(Option<usize>, T)> {
+        let order = |a: &SeasonTotal, b: &SeasonTotal| {
+            a.qualified.cmp(&b.qualified).then_with(|| self.compare(a, b))
+        };
+        entries.sort_by(|a, b| order(total(b), total(a)));
+
+        let mut ranked: Vec<(Option<usize>, T)> = Vec::with_capacity(entries.len());
+        for (idx, entry) in entries.into_iter().enumerate() {
+            let place = match ranked.last() {
+                _ if !total(&entry).qualified => None,
+                Some((place, previous)) if order(total(previous), total(&entry)).is_eq() => *place,
+                _ => Some(idx + 1),
+            };
+            ranked.push((place, entry));
+        }
+
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests;
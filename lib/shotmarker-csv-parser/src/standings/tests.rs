@@ -0,0 +1,84 @@
+use super::*;
+
+/// Parses match scores written like `598-31`.
+fn scores(scores: &str) -> Vec<StringScore> {
+    scores
+        .split_whitespace()
+        .map(|score| {
+            let (points, x_count) = score.split_once('-').unwrap();
+            StringScore {
+                points:     points.parse().unwrap(),
+                x_count:    x_count.parse().unwrap(),
+                convention: ScoringConvention::Nra,
+            }
+        })
+        .collect()
+}
+
+fn total(rules: &StandingsRules, match_scores: &str) -> SeasonTotal {
+    rules.total(&scores(match_scores), ScoringConvention::Nra)
+}
+
+#[test]
+fn total_drops_worst_then_caps_to_best() {
+    let rules = StandingsRules {
+        best_matches: Some(2),
+        drop_worst: 1,
+        ..StandingsRules::default()
+    };
+
+    let season = total(&rules, "590-20 598-31 570-10 595-25");
+    assert_eq!(season.score.points, 1193);
+    assert_eq!(season.score.x_count, 56);
+    assert_eq!(season.counted, vec![false, true, false, true]);
+
+    // Dropping never leaves a shooter with nothing counted.
+    let season = total(&rules, "590-20");
+    assert_eq!(season.counted, vec![true]);
+    assert_eq!(season.score.points, 590);
+}
+
+#[test]
+fn total_counts_earlier_of_equal_matches() {
+    let rules = StandingsRules {
+        best_matches: Some(1),
+        ..StandingsRules::default()
+    };
+
+    assert_eq!(total(&rules, "590-20 590-20").counted, vec![true, false]);
+}
+
+#[test]
+fn rank_applies_tie_breakers_in_order() {
+    let rules = StandingsRules {
+        min_matches: 2,
+        tie_breakers: vec![TieBreaker::BestMatch, TieBreaker::MatchesShot],
+        ..StandingsRules::default()
+    };
+    let entries = vec![
+        ("too few matches", total(&rules, "600-60")),
+        ("weaker best match", total(&rules, "590-20 590-20")),
+        ("stronger best match", total(&rules, "595-20 585-20")),
+        ("fewer matches", total(&rules, "290-0 290-0")),
+        ("more matches", total(&rules, "290-0 145-0 145-0")),
+        ("tied", total(&rules, "590-20 590-20")),
+    ];
+
+    let ranked: Vec<_> = rules
+        .rank(entries, |(_, total)| total)
+        .into_iter()
+        .map(|(place, (name, _))| (place, name))
+        .collect();
+
+    assert_eq!(
+        ranked,
+        vec![
+            (Some(1), "stronger best match"),
+            (Some(2), "weaker best match"),
+            (Some(2), "tied"),
+            (Some(4), "more matches"),
+            (Some(5), "fewer matches"),
+            (None, "too few matches"),
+        ]
+    );
+}
@@ -1,32 +1,99 @@
 use std::cmp::Ordering;
 
-use crate::string::shot::ShotMarkerShot;
+use crate::string::{
+    column::ShotColumns,
+    distance::StringDistance,
+    shot::{
+        ShotMarkerShot,
+        ShotPosition,
+    },
+    target::StringTarget,
+};
 use chrono::NaiveDate;
 use serde::{
     Deserialize,
     Serialize,
 };
 
+pub mod column;
+pub mod distance;
 pub mod shot;
+pub mod target;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ShotMarkerStringMetrics {}
+/// ShotMarker's own summary of a shot string, taken from the `avg:` and `SD:` rows that follow
+/// the individual shots.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShotMarkerStringMetrics {
+    pub average:       ShotMarkerMetricValues,
+    pub std_deviation: ShotMarkerMetricValues,
+}
+
+/// One row of string metrics, covering the same columns as an individual shot from `x (mm)`
+/// through `quality`.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShotMarkerMetricValues {
+    pub position: ShotPosition,
+    pub velocity: ShotMarkerMetricVelocity,
+    pub yaw:      f64,
+    pub pitch:    f64,
+    pub quality:  Option<f64>,
+}
+
+/// Unlike [`shot::ShotVelocity`], the averaged fps is not rounded to a whole number.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShotMarkerMetricVelocity {
+    pub ms:  f64,
+    pub fps: f64,
+}
+
+/// How a league's matches are scored, which decides how string totals are written.
+#[remain::sorted]
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum ScoringConvention {
+    /// 5-point rings with V-bulls, totalled as `75.10V`.
+    Icfra,
+    /// 10-point rings with X's, totalled as `200-16X`.
+    #[default]
+    Nra,
+}
+
+impl std::fmt::Display for ScoringConvention {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Icfra => write!(f, "ICFRA"),
+            Self::Nra => write!(f, "NRA"),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StringScore {
-    pub points:  u32,
-    pub x_count: u32,
+    pub points:     u32,
+    /// The number of X's, or of V-bulls under [`ScoringConvention::Icfra`].
+    pub x_count:    u32,
+    #[serde(default)]
+    pub convention: ScoringConvention,
 }
 
 impl std::fmt::Display for StringScore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}-{}X", self.points, self.x_count)
+        match self.convention {
+            ScoringConvention::Icfra => write!(f, "{}.{}V", self.points, self.x_count),
+            ScoringConvention::Nra => write!(f, "{}-{}X", self.points, self.x_count),
+        }
     }
 }
 
+/// Scores under different conventions can't be compared by their points, so they're ordered by
+/// convention first, and `75.10V` is never equal to `75-10X`.
 impl Ord for StringScore {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.points.cmp(&other.points).then(self.x_count.cmp(&other.x_count))
+        self.convention
+            .cmp(&other.convention)
+            .then(self.points.cmp(&other.points))
+            .then(self.x_count.cmp(&other.x_count))
     }
 }
 
@@ -35,9 +102,7 @@ impl PartialOrd for StringScore {
 }
 
 impl PartialEq for StringScore {
-    fn eq(&self, other: &Self) -> bool {
-        self.points == other.points && self.x_count == other.x_count
-    }
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
 }
 
 impl Eq for StringScore {}
@@ -46,9 +111,12 @@ impl Eq for StringScore {}
 pub struct ShotMarkerShotString {
     pub date:     NaiveDate,
     pub name:     String,
-    pub target:   String,
-    pub distance: String,
+    pub target:   StringTarget,
+    pub distance: StringDistance,
     pub score:    StringScore,
     pub shots:    Vec<ShotMarkerShot>,
     pub metrics:  Option<ShotMarkerStringMetrics>,
+    /// The string's column header, which the shot and metrics rows were decoded by.
+    #[serde(default)]
+    pub columns:  ShotColumns,
 }
@@ -0,0 +1,182 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// A shot row column this parser knows how to decode, identified by its name in the per-string
+/// column header.
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ShotColumn {
+    Id,
+    InchX,
+    InchY,
+    MilX,
+    MilY,
+    MmX,
+    MmY,
+    MoaX,
+    MoaY,
+    Pitch,
+    Quality,
+    Score,
+    Tags,
+    Time,
+    VelocityFps,
+    VelocityMs,
+    Yaw,
+}
+
+impl ShotColumn {
+    /// Every known column, in the order ShotMarker writes them.
+    pub const ALL: [Self; 17] = [
+        Self::Time,
+        Self::Id,
+        Self::Tags,
+        Self::Score,
+        Self::MmX,
+        Self::MmY,
+        Self::InchX,
+        Self::InchY,
+        Self::MoaX,
+        Self::MoaY,
+        Self::MilX,
+        Self::MilY,
+        Self::VelocityMs,
+        Self::VelocityFps,
+        Self::Yaw,
+        Self::Pitch,
+        Self::Quality,
+    ];
+
+    /// The column's name as written in the header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::InchX => "x (inch)",
+            Self::InchY => "y (inch)",
+            Self::MilX => "x (mil)",
+            Self::MilY => "y (mil)",
+            Self::MmX => "x (mm)",
+            Self::MmY => "y (mm)",
+            Self::MoaX => "x (moa)",
+            Self::MoaY => "y (moa)",
+            Self::Pitch => "pitch (deg)",
+            Self::Quality => "quality",
+            Self::Score => "score",
+            Self::Tags => "tags",
+            Self::Time => "time",
+            Self::VelocityFps => "v (fps)",
+            Self::VelocityMs => "v (m/s)",
+            Self::Yaw => "yaw (deg)",
+        }
+    }
+
+    /// Whether a string can be parsed without this column. Tags and quality may simply be left
+    /// out, inches can be worked out from millimeters, and only one of the two velocity columns
+    /// is needed. See [`ShotColumns::missing_required`].
+    pub fn is_optional(&self) -> bool {
+        matches!(
+            self,
+            Self::InchX
+                | Self::InchY
+                | Self::Quality
+                | Self::Tags
+                | Self::VelocityFps
+                | Self::VelocityMs
+        )
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL.into_iter().find(|column| column.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl std::fmt::Display for ShotColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The columns of a string's shot rows, in the order given by its column header. This excludes
+/// the blank first column, which holds the string header and `avg:`/`SD:` rows rather than shot
+/// data.
+///
+/// Columns that aren't a known [`ShotColumn`] are kept by name, so their values can be carried
+/// along in [`super::shot::ShotMarkerShot::extras`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<String>", into = "Vec<String>")]
+pub struct ShotColumns {
+    names:   Vec<String>,
+    columns: Vec<Option<ShotColumn>>,
+}
+
+impl ShotColumns {
+    /// Names are kept as written, including any surrounding whitespace (ShotMarker writes
+    /// `, pitch (deg)`), so the header can be written back out unchanged.
+    pub fn new(names: Vec<String>) -> Self {
+        let columns = names.iter().map(|name| ShotColumn::from_name(name)).collect();
+
+        Self {
+            names,
+            columns,
+        }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> { self.names.iter().map(String::as_str) }
+
+    /// Each column's name as written, along with the [`ShotColumn`] it was recognised as.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<ShotColumn>)> {
+        self.names.iter().map(String::as_str).zip(self.columns.iter().copied())
+    }
+
+    pub fn len(&self) -> usize { self.names.len() }
+
+    pub fn is_empty(&self) -> bool { self.names.is_empty() }
+
+    /// The index of `column` within a shot row, if the header has it.
+    pub fn position(&self, column: ShotColumn) -> Option<usize> {
+        self.columns.iter().position(|candidate| *candidate == Some(column))
+    }
+
+    pub fn contains(&self, column: ShotColumn) -> bool { self.position(column).is_some() }
+
+    /// Columns that aren't a known [`ShotColumn`], along with their index within a shot row. Names
+    /// are trimmed, as used for [`super::shot::ShotMarkerShot::extras`].
+    pub fn extras(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.iter()
+            .enumerate()
+            .filter(|(_idx, (name, column))| column.is_none() && !name.trim().is_empty())
+            .map(|(idx, (name, _column))| (idx, name.trim()))
+    }
+
+    /// Required columns the header doesn't have. When neither velocity column is present,
+    /// [`ShotColumn::VelocityFps`] is reported as missing.
+    pub fn missing_required(&self) -> Vec<ShotColumn> {
+        let mut missing: Vec<ShotColumn> = ShotColumn::ALL
+            .into_iter()
+            .filter(|column| !column.is_optional() && !self.contains(*column))
+            .collect();
+        if !self.contains(ShotColumn::VelocityMs) && !self.contains(ShotColumn::VelocityFps) {
+            missing.push(ShotColumn::VelocityFps);
+        }
+
+        missing
+    }
+}
+
+/// The column layout ShotMarker has always exported.
+impl Default for ShotColumns {
+    fn default() -> Self {
+        Self::new(ShotColumn::ALL.iter().map(|column| column.name().to_string()).collect())
+    }
+}
+
+impl From<Vec<String>> for ShotColumns {
+    fn from(value: Vec<String>) -> Self { Self::new(value) }
+}
+
+impl From<ShotColumns> for Vec<String> {
+    fn from(value: ShotColumns) -> Self { value.names }
+}
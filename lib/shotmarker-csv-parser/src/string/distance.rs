@@ -0,0 +1,141 @@
+use std::cmp::Ordering;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::units::{
+    METERS_PER_YARD,
+    Meters,
+    Yards,
+};
+
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DistanceUnit {
+    Meters,
+    Yards,
+}
+
+impl DistanceUnit {
+    /// The suffix ShotMarker writes after the distance, as in `600y` or `300m`.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::Meters => "m",
+            Self::Yards => "y",
+        }
+    }
+}
+
+/// A distance to the target, in the unit the ShotMarker was set up with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Distance {
+    pub value: u32,
+    pub unit:  DistanceUnit,
+}
+
+impl Distance {
+    pub fn yards(&self) -> Yards {
+        match self.unit {
+            DistanceUnit::Meters => f64::from(self.value) / METERS_PER_YARD,
+            DistanceUnit::Yards => f64::from(self.value),
+        }
+    }
+
+    pub fn meters(&self) -> Meters {
+        match self.unit {
+            DistanceUnit::Meters => f64::from(self.value),
+            DistanceUnit::Yards => f64::from(self.value) * METERS_PER_YARD,
+        }
+    }
+}
+
+impl std::fmt::Display for Distance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.suffix())
+    }
+}
+
+/// The distance column of a string header. Anything other than ShotMarker's own `600y` or `300m`
+/// is kept verbatim as [`Self::Unknown`], the same as it is (de)serialized.
+#[remain::sorted]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum StringDistance {
+    Distance(Distance),
+    Unknown(String),
+}
+
+impl StringDistance {
+    pub fn distance(&self) -> Option<Distance> {
+        match self {
+            Self::Distance(distance) => Some(*distance),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    pub fn yards(&self) -> Option<Yards> { self.distance().map(|distance| distance.yards()) }
+
+    pub fn meters(&self) -> Option<Meters> { self.distance().map(|distance| distance.meters()) }
+}
+
+impl std::fmt::Display for StringDistance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Distance(distance) => write!(f, "{distance}"),
+            Self::Unknown(distance) => write!(f, "{distance}"),
+        }
+    }
+}
+
+/// Orders known distances from nearest to furthest, whatever their unit, followed by unknown ones.
+impl Ord for StringDistance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Distance(a), Self::Distance(b)) => {
+                a.meters().total_cmp(&b.meters()).then(a.unit.suffix().cmp(b.unit.suffix()))
+            }
+            (Self::Distance(_), Self::Unknown(_)) => Ordering::Less,
+            (Self::Unknown(_), Self::Distance(_)) => Ordering::Greater,
+            (Self::Unknown(a), Self::Unknown(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for StringDistance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl From<&str> for StringDistance {
+    fn from(value: &str) -> Self {
+        let distance = [DistanceUnit::Yards, DistanceUnit::Meters].into_iter().find_map(|unit| {
+            let digits = value.strip_suffix(unit.suffix())?;
+            // Only take digits written the way `Display` writes them back out.
+            if digits.is_empty()
+                || !digits.bytes().all(|digit| digit.is_ascii_digit())
+                || (digits.len() > 1 && digits.starts_with('0'))
+            {
+                return None;
+            }
+
+            Some(Distance {
+                value: digits.parse().ok()?,
+                unit,
+            })
+        });
+
+        match distance {
+            Some(distance) => Self::Distance(distance),
+            None => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for StringDistance {
+    fn from(value: String) -> Self { value.as_str().into() }
+}
+
+impl From<StringDistance> for String {
+    fn from(value: StringDistance) -> Self { value.to_string() }
+}
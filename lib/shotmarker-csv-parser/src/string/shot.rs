@@ -1,12 +1,25 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+};
 
-use crate::units::{
-    Inches,
-    Mil,
-    Millimeters,
-    Moa,
+use crate::{
+    string::ScoringConvention,
+    units::{
+        Inches,
+        Mil,
+        Millimeters,
+        Moa,
+    },
+};
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    NaiveTime,
+    TimeZone,
+    Utc,
 };
-use chrono::NaiveTime;
+use chrono_tz::Tz;
 use serde::{
     Deserialize,
     Serialize,
@@ -20,15 +33,143 @@ pub struct ShotVelocity {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ShotMarkerShot {
-    pub time:     NaiveTime,
-    pub id:       String,
-    pub tags:     String,
-    pub score:    ShotScore,
-    pub position: ShotPosition,
-    pub velocity: ShotVelocity,
-    pub yaw:      f64,
-    pub pitch:    f64,
-    pub quality:  Option<f64>,
+    pub time:      NaiveTime,
+    /// The local time of the shot on the date it was fired. ShotMarker only records the time of
+    /// day, so the date comes from the string header, moved forward a day each time the times
+    /// go backwards (a string fired across midnight).
+    pub date_time: NaiveDateTime,
+    pub id:        String,
+    pub tags:      ShotTags,
+    pub score:     ShotScore,
+    pub position:  ShotPosition,
+    pub velocity:  ShotVelocity,
+    pub yaw:       f64,
+    pub pitch:     f64,
+    pub quality:   Option<f64>,
+    /// Values from columns this parser doesn't know about, keyed by column name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extras:    BTreeMap<String, String>,
+}
+
+impl ShotMarkerShot {
+    pub fn is_sighter(&self) -> bool { self.tags.is_sighter() }
+
+    pub fn is_hidden(&self) -> bool { self.tags.is_hidden() }
+
+    /// Converts the shot's local time to UTC, given the time zone the ShotMarker clock was set to.
+    ///
+    /// Returns `None` if the local time doesn't exist in `time_zone` (skipped by a DST change).
+    /// When it is ambiguous (repeated by a DST change), the earlier instant is used.
+    pub fn utc_date_time(&self, time_zone: &Tz) -> Option<DateTime<Utc>> {
+        time_zone
+            .from_local_datetime(&self.date_time)
+            .earliest()
+            .map(|date_time| date_time.with_timezone(&Utc))
+    }
+}
+
+#[remain::sorted]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShotTag {
+    Hidden,
+    Sighter,
+    /// A tag this version of the parser doesn't know about, kept verbatim so it isn't lost.
+    Unknown(String),
+    WarningAngle,
+    WarningQuality,
+    WarningVelocity,
+}
+
+impl ShotTag {
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::WarningAngle | Self::WarningQuality | Self::WarningVelocity)
+    }
+}
+
+impl std::fmt::Display for ShotTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hidden => write!(f, "hidden"),
+            Self::Sighter => write!(f, "sighter"),
+            Self::Unknown(tag) => write!(f, "{tag}"),
+            Self::WarningAngle => write!(f, "warning-angle"),
+            Self::WarningQuality => write!(f, "warning-quality"),
+            Self::WarningVelocity => write!(f, "warning-velocity"),
+        }
+    }
+}
+
+impl From<&str> for ShotTag {
+    fn from(value: &str) -> Self {
+        match value {
+            "hidden" => Self::Hidden,
+            "sighter" => Self::Sighter,
+            "warning-angle" => Self::WarningAngle,
+            "warning-quality" => Self::WarningQuality,
+            "warning-velocity" => Self::WarningVelocity,
+            unknown => Self::Unknown(unknown.to_string()),
+        }
+    }
+}
+
+/// The set of tags on a shot. ShotMarker joins multiple tags with `/`, as in
+/// `hidden/warning-angle`, and the same format is used when (de)serializing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct ShotTags(Vec<ShotTag>);
+
+impl ShotTags {
+    pub fn new(tags: Vec<ShotTag>) -> Self { Self(tags) }
+
+    pub fn contains(&self, tag: &ShotTag) -> bool { self.0.contains(tag) }
+
+    /// Adds `tag`, unless the shot already has it.
+    pub fn insert(&mut self, tag: ShotTag) {
+        if !self.contains(&tag) {
+            self.0.push(tag);
+        }
+    }
+
+    pub fn remove(&mut self, tag: &ShotTag) { self.0.retain(|existing| existing != tag) }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    pub fn is_hidden(&self) -> bool { self.contains(&ShotTag::Hidden) }
+
+    pub fn is_sighter(&self) -> bool { self.contains(&ShotTag::Sighter) }
+
+    pub fn has_warning(&self) -> bool { self.0.iter().any(ShotTag::is_warning) }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ShotTag> { self.0.iter() }
+}
+
+impl std::fmt::Display for ShotTags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, tag) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "/")?;
+            }
+            write!(f, "{tag}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for ShotTags {
+    fn from(value: &str) -> Self {
+        Self(
+            value.split('/').map(str::trim).filter(|tag| !tag.is_empty()).map(Into::into).collect(),
+        )
+    }
+}
+
+impl From<String> for ShotTags {
+    fn from(value: String) -> Self { value.as_str().into() }
+}
+
+impl From<ShotTags> for String {
+    fn from(value: ShotTags) -> Self { value.to_string() }
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
@@ -63,36 +204,59 @@ pub struct ShotPosition {
     pub mil:  ShotXYmil,
 }
 
+/// A single shot's score. NRA faces score 10 down, with an X inside the 10 ring, while ICFRA faces
+/// score 5 down, with a V-bull inside the 5 ring.
 #[remain::sorted]
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum ShotScore {
     None,
     Numeric(u8),
+    V,
     X,
 }
 
+impl ShotScore {
+    /// The points the shot is worth. An X is worth the ring it sits inside: the 10 ring under
+    /// [`ScoringConvention::Nra`], and the 6 ring of the ICFRA F-Class face otherwise.
+    pub fn points(&self, convention: ScoringConvention) -> u32 {
+        match (self, convention) {
+            (Self::None, _) => 0,
+            (Self::Numeric(n), _) => u32::from(*n),
+            (Self::V, _) => 5,
+            (Self::X, ScoringConvention::Icfra) => 6,
+            (Self::X, ScoringConvention::Nra) => 10,
+        }
+    }
+
+    /// Whether the shot counts towards a string's X (or V-bull) count.
+    pub fn is_centre(&self) -> bool { matches!(self, Self::V | Self::X) }
+}
+
 impl std::fmt::Display for ShotScore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::None => write!(f, "None"),
             Self::Numeric(n) => write!(f, "{}", n),
+            Self::V => write!(f, "V"),
             Self::X => write!(f, "X"),
         }
     }
 }
 
+/// Misses sort first and ring scores by value, followed by V-bulls and then X's. Strings aren't
+/// scored with both, so the order between those two is only there to keep the ordering total.
 impl Ord for ShotScore {
     fn cmp(&self, other: &Self) -> Ordering {
-        match (self, other) {
-            (ShotScore::None, ShotScore::None) | (ShotScore::X, ShotScore::X) => Ordering::Equal,
-            (ShotScore::Numeric(a), ShotScore::Numeric(b)) => a.cmp(b),
-            (ShotScore::None, ShotScore::Numeric(_))
-            | (ShotScore::None, ShotScore::X)
-            | (ShotScore::Numeric(_), ShotScore::X) => Ordering::Less,
-            (ShotScore::Numeric(_), ShotScore::None)
-            | (ShotScore::X, ShotScore::None)
-            | (ShotScore::X, ShotScore::Numeric(_)) => Ordering::Greater,
+        fn rank(score: &ShotScore) -> (u8, u8) {
+            match score {
+                ShotScore::None => (0, 0),
+                ShotScore::Numeric(n) => (1, *n),
+                ShotScore::V => (2, 0),
+                ShotScore::X => (3, 0),
+            }
         }
+
+        rank(self).cmp(&rank(other))
     }
 }
 
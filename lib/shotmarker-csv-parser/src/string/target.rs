@@ -0,0 +1,57 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The target column of a string header, which is the number of the target (lane) the string was
+/// fired on. Anything else is kept verbatim as [`Self::Unknown`], the same as it is
+/// (de)serialized.
+///
+/// Lanes sort numerically, followed by unknown targets.
+#[remain::sorted]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum StringTarget {
+    Lane(u32),
+    Unknown(String),
+}
+
+impl StringTarget {
+    pub fn lane(&self) -> Option<u32> {
+        match self {
+            Self::Lane(lane) => Some(*lane),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for StringTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lane(lane) => write!(f, "{lane}"),
+            Self::Unknown(target) => write!(f, "{target}"),
+        }
+    }
+}
+
+impl From<&str> for StringTarget {
+    fn from(value: &str) -> Self {
+        // Only take lanes written the way `Display` writes them back out.
+        let is_lane = !value.is_empty()
+            && value.bytes().all(|digit| digit.is_ascii_digit())
+            && (value.len() == 1 || !value.starts_with('0'));
+
+        match value.parse() {
+            Ok(lane) if is_lane => Self::Lane(lane),
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for StringTarget {
+    fn from(value: String) -> Self { value.as_str().into() }
+}
+
+impl From<StringTarget> for String {
+    fn from(value: StringTarget) -> Self { value.to_string() }
+}
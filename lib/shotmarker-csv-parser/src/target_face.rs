@@ -0,0 +1,245 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    string::{
+        distance::{
+            Distance,
+            DistanceUnit,
+        },
+        shot::{
+            ShotMarkerShot,
+            ShotPosition,
+            ShotScore,
+        },
+    },
+    units::{
+        MILLIMETERS_PER_INCH,
+        Millimeters,
+    },
+};
+
+/// How a shot whose bullet hole touches a ring's edge is scored.
+#[remain::sorted]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RingEdge {
+    /// Only the centre of the shot counts, so the bullet's diameter is ignored.
+    Centre,
+    /// A bullet hole touching a ring's edge scores that ring, as under NRA and ICFRA rules.
+    CountsUp,
+}
+
+/// One scoring ring, worth `score` for any shot within `diameter`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoringRing {
+    pub score:    ShotScore,
+    pub diameter: Millimeters,
+}
+
+impl ScoringRing {
+    pub fn radius(&self) -> Millimeters { self.diameter / 2.0 }
+}
+
+/// A target face, with its scoring rings and the rules they are scored by.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetFace {
+    pub name:            String,
+    /// The distance the face's ring sizes are for. See [`Self::at_distance`].
+    pub distance:        Distance,
+    /// From the centre ring outwards. A shot outside every ring is a miss.
+    pub rings:           Vec<ScoringRing>,
+    pub ring_edge:       RingEdge,
+    /// The bullet diameter used with [`RingEdge::CountsUp`]. ShotMarker scores with the
+    /// calibre it is set up with.
+    pub bullet_diameter: Millimeters,
+}
+
+/// .308 calibre, in millimeters.
+const DEFAULT_BULLET_DIAMETER: Millimeters = 0.308 * MILLIMETERS_PER_INCH;
+
+impl TargetFace {
+    fn new(name: &str, distance: Distance, rings: &[(ShotScore, Millimeters)]) -> Self {
+        Self {
+            name: name.to_string(),
+            distance,
+            rings: rings
+                .iter()
+                .map(|&(score, diameter)| {
+                    ScoringRing {
+                        score,
+                        diameter,
+                    }
+                })
+                .collect(),
+            ring_edge: RingEdge::CountsUp,
+            bullet_diameter: DEFAULT_BULLET_DIAMETER,
+        }
+    }
+
+    fn nra(name: &str, yards: u32, inches: [f64; 7]) -> Self {
+        let scores = [
+            ShotScore::X,
+            ShotScore::Numeric(10),
+            ShotScore::Numeric(9),
+            ShotScore::Numeric(8),
+            ShotScore::Numeric(7),
+            ShotScore::Numeric(6),
+            ShotScore::Numeric(5),
+        ];
+        let rings: Vec<_> = scores
+            .into_iter()
+            .zip(inches)
+            .map(|(score, diameter)| (score, diameter * MILLIMETERS_PER_INCH))
+            .collect();
+
+        Self::new(
+            name,
+            Distance {
+                value: yards,
+                unit:  DistanceUnit::Yards,
+            },
+            &rings,
+        )
+    }
+
+    fn icfra(name: &str, rings: &[(ShotScore, Millimeters)]) -> Self {
+        Self::new(
+            name,
+            Distance {
+                value: 1000,
+                unit:  DistanceUnit::Yards,
+            },
+            rings,
+        )
+    }
+
+    /// NRA F-Class 600 yard face.
+    pub fn nra_mr63fc() -> Self {
+        Self::nra("NRA MR-63FC", 600, [3.0, 6.0, 12.0, 18.0, 24.0, 36.0, 48.0])
+    }
+
+    /// NRA F-Class 800 yard face.
+    pub fn nra_mr65fc() -> Self {
+        Self::nra("NRA MR-65FC", 800, [4.0, 8.0, 16.0, 24.0, 32.0, 48.0, 64.0])
+    }
+
+    /// NRA F-Class 1000 yard face.
+    pub fn nra_mr1fc() -> Self {
+        Self::nra("NRA MR-1FC", 1000, [5.0, 10.0, 20.0, 30.0, 44.0, 60.0, 72.0])
+    }
+
+    /// ICFRA F-Class long range face, scored with an X inside the 6 ring and 6 down to 1.
+    pub fn icfra_f_class_long_range() -> Self {
+        Self::icfra(
+            "ICFRA F-Class long range",
+            &[
+                (ShotScore::X, 127.0),
+                (ShotScore::Numeric(6), 254.0),
+                (ShotScore::Numeric(5), 508.0),
+                (ShotScore::Numeric(4), 762.0),
+                (ShotScore::Numeric(3), 1118.0),
+                (ShotScore::Numeric(2), 1626.0),
+                (ShotScore::Numeric(1), 1829.0),
+            ],
+        )
+    }
+
+    /// ICFRA target rifle long range face, scored with a V-bull inside the 5 ring and 5 down to 2.
+    /// It's the F-Class face without the X and 4 rings, so its rings are the F-Class 6, 5, 3, 2 and
+    /// 1 rings.
+    pub fn icfra_target_rifle_long_range() -> Self {
+        Self::icfra(
+            "ICFRA TR long range",
+            &[
+                (ShotScore::V, 254.0),
+                (ShotScore::Numeric(5), 508.0),
+                (ShotScore::Numeric(4), 1118.0),
+                (ShotScore::Numeric(3), 1626.0),
+                (ShotScore::Numeric(2), 1829.0),
+            ],
+        )
+    }
+
+    /// Every built-in face.
+    pub fn standard_faces() -> Vec<Self> {
+        vec![
+            Self::nra_mr63fc(),
+            Self::nra_mr65fc(),
+            Self::nra_mr1fc(),
+            Self::icfra_f_class_long_range(),
+            Self::icfra_target_rifle_long_range(),
+        ]
+    }
+
+    /// The NRA F-Class face for `distance`: the face for the nearest of 600, 800, and 1000 yards
+    /// at or beyond it, scaled to `distance` (see [`Self::at_distance`]).
+    pub fn nra_f_class(distance: Distance) -> Self {
+        let yards = distance.yards();
+        let face = if yards <= 600.0 {
+            Self::nra_mr63fc()
+        } else if yards <= 800.0 {
+            Self::nra_mr65fc()
+        } else {
+            Self::nra_mr1fc()
+        };
+
+        face.at_distance(distance)
+    }
+
+    /// The face reduced (or enlarged) for shooting at `distance` rather than [`Self::distance`],
+    /// scaling each ring so it covers the same angle.
+    pub fn at_distance(&self, distance: Distance) -> Self {
+        let scale = distance.meters() / self.distance.meters();
+
+        Self {
+            distance,
+            rings: self
+                .rings
+                .iter()
+                .map(|ring| {
+                    ScoringRing {
+                        score:    ring.score,
+                        diameter: ring.diameter * scale,
+                    }
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    pub fn ring(&self, score: ShotScore) -> Option<&ScoringRing> {
+        self.rings.iter().find(|ring| ring.score == score)
+    }
+}
+
+/// Scores a shot at `position` on `face`, independently of whatever score ShotMarker recorded for
+/// it. A shot outside every ring scores [`ShotScore::None`].
+pub fn score_shot(position: &ShotPosition, face: &TargetFace) -> ShotScore {
+    let from_centre = position.mm.x.hypot(position.mm.y);
+    let from_centre = match face.ring_edge {
+        RingEdge::Centre => from_centre,
+        RingEdge::CountsUp => from_centre - face.bullet_diameter / 2.0,
+    };
+
+    face.rings
+        .iter()
+        .find(|ring| from_centre <= ring.radius())
+        .map_or(ShotScore::None, |ring| ring.score)
+}
+
+/// Shots whose recorded score differs from scoring them on `face`, along with the score from
+/// `face`.
+pub fn score_disagreements<'a>(
+    shots: impl IntoIterator<Item = &'a ShotMarkerShot>,
+    face: &TargetFace,
+) -> impl Iterator<Item = (&'a ShotMarkerShot, ShotScore)> {
+    shots.into_iter().filter_map(move |shot| {
+        let score = score_shot(&shot.position, face);
+        (score != shot.score).then_some((shot, score))
+    })
+}
+
+#[cfg(test)]
+mod tests;
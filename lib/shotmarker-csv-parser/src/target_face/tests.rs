@@ -0,0 +1,128 @@
+use super::*;
+use crate::{
+    parser::export_parser,
+    string::shot::{
+        ShotXYinch,
+        ShotXYmil,
+        ShotXYmm,
+        ShotXYmoa,
+    },
+};
+
+fn position(x: Millimeters, y: Millimeters) -> ShotPosition {
+    ShotPosition {
+        mm:   ShotXYmm {
+            x,
+            y,
+        },
+        inch: ShotXYinch {
+            x: x / MILLIMETERS_PER_INCH,
+            y: y / MILLIMETERS_PER_INCH,
+        },
+        moa:  ShotXYmoa {
+            x: 0.0,
+            y: 0.0,
+        },
+        mil:  ShotXYmil {
+            x: 0.0,
+            y: 0.0,
+        },
+    }
+}
+
+#[test]
+fn score_shot_counts_ring_edges_up() {
+    let face = TargetFace::nra_mr63fc();
+    let x_ring = face.ring(ShotScore::X).unwrap().radius();
+    let bullet_radius = face.bullet_diameter / 2.0;
+
+    assert_eq!(score_shot(&position(0.0, 0.0), &face), ShotScore::X);
+    assert_eq!(score_shot(&position(x_ring + bullet_radius - 0.1, 0.0), &face), ShotScore::X);
+    assert_eq!(
+        score_shot(&position(0.0, x_ring + bullet_radius + 0.1), &face),
+        ShotScore::Numeric(10)
+    );
+
+    let centre_only = TargetFace {
+        ring_edge: RingEdge::Centre,
+        ..face
+    };
+    assert_eq!(
+        score_shot(&position(x_ring + bullet_radius - 0.1, 0.0), &centre_only),
+        ShotScore::Numeric(10)
+    );
+}
+
+#[test]
+fn score_shot_outside_every_ring_is_a_miss() {
+    let face = TargetFace::nra_mr63fc();
+    let five_ring = face.ring(ShotScore::Numeric(5)).unwrap().radius();
+
+    assert_eq!(score_shot(&position(-five_ring, 0.0), &face), ShotScore::Numeric(5));
+    assert_eq!(score_shot(&position(-five_ring - 10.0, 0.0), &face), ShotScore::None);
+}
+
+#[test]
+fn nra_f_class_faces_by_distance() {
+    let yards = |value| {
+        Distance {
+            value,
+            unit: DistanceUnit::Yards,
+        }
+    };
+
+    assert_eq!(TargetFace::nra_f_class(yards(600)), TargetFace::nra_mr63fc());
+    assert_eq!(TargetFace::nra_f_class(yards(800)), TargetFace::nra_mr65fc());
+    assert_eq!(TargetFace::nra_f_class(yards(1000)), TargetFace::nra_mr1fc());
+
+    let reduced = TargetFace::nra_f_class(yards(300));
+    assert_eq!(reduced.name, "NRA MR-63FC");
+    let ten_ring = reduced.ring(ShotScore::Numeric(10)).unwrap();
+    assert!((ten_ring.diameter - 3.0 * MILLIMETERS_PER_INCH).abs() < 1e-9);
+}
+
+#[test]
+fn icfra_target_rifle_rings_match_f_class_rings() {
+    let f_class = TargetFace::icfra_f_class_long_range();
+    let target_rifle = TargetFace::icfra_target_rifle_long_range();
+    let shared_rings = [
+        (ShotScore::V, ShotScore::Numeric(6)),
+        (ShotScore::Numeric(5), ShotScore::Numeric(5)),
+        (ShotScore::Numeric(4), ShotScore::Numeric(3)),
+        (ShotScore::Numeric(3), ShotScore::Numeric(2)),
+        (ShotScore::Numeric(2), ShotScore::Numeric(1)),
+    ];
+
+    for (target_rifle_score, f_class_score) in shared_rings {
+        assert_eq!(
+            target_rifle.ring(target_rifle_score).unwrap().diameter,
+            f_class.ring(f_class_score).unwrap().diameter,
+            "TR {target_rifle_score} ring differs from F-Class {f_class_score} ring"
+        );
+    }
+}
+
+#[test]
+fn example_file_scores_agree_with_device() {
+    let (_rest, export) =
+        export_parser(include_str!("../../example-files/SM_shotslog_Jan_18.csv")).unwrap();
+
+    let mut shot_count = 0;
+    let mut disagreement_count = 0;
+    // Sling strings are shot on the high power face rather than F-Class.
+    for shot_string in export.strings.iter().filter(|string| !string.name.contains("sling")) {
+        let face = TargetFace::nra_f_class(shot_string.distance.distance().unwrap());
+        shot_count += shot_string.shots.len();
+        disagreement_count += score_disagreements(&shot_string.shots, &face).count();
+    }
+
+    // Positions are exported to the nearest millimeter, so shots right on a ring's edge can go
+    // either way.
+    assert!(
+        disagreement_count * 50 < shot_count,
+        "{disagreement_count} of {shot_count} shots scored differently"
+    );
+    let first_string = &export.strings[0];
+    let face = TargetFace::nra_f_class(first_string.distance.distance().unwrap());
+    assert_eq!(score_disagreements(&first_string.shots, &face).count(), 0);
+}
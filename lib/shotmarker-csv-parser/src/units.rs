@@ -2,3 +2,11 @@ pub type Millimeters = f64;
 pub type Inches = f64;
 pub type Moa = f64;
 pub type Mil = f64;
+
+pub const MILLIMETERS_PER_INCH: Millimeters = 25.4;
+pub const FEET_PER_METER: f64 = 3.280_84;
+
+pub type Yards = f64;
+pub type Meters = f64;
+
+pub const METERS_PER_YARD: Meters = 0.9144;
@@ -0,0 +1,121 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::string::{
+    ShotMarkerShotString,
+    StringScore,
+    shot::{
+        ShotMarkerShot,
+        ShotScore,
+        ShotTags,
+    },
+};
+
+/// A shot that can be totalled into a string's score. Implemented for shots parsed from an
+/// export, as well as for shots as stored by the server, so that scores can be recomputed after
+/// a string has been edited.
+pub trait ScoredShot {
+    fn score(&self) -> ShotScore;
+
+    fn tags(&self) -> &ShotTags;
+
+    /// Whether the shot counts towards the string's score, rather than being a sighter or hidden.
+    fn is_scored(&self) -> bool { !self.tags().is_sighter() && !self.tags().is_hidden() }
+}
+
+impl ScoredShot for ShotMarkerShot {
+    fn score(&self) -> ShotScore { self.score }
+
+    fn tags(&self) -> &ShotTags { &self.tags }
+}
+
+/// Something about a shot string that doesn't add up, and that a match official should review
+/// before the string's score is used.
+#[remain::sorted]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationFinding {
+    /// The score in the string header doesn't match the total of the string's scored shots.
+    ScoreMismatch {
+        recorded: StringScore,
+        computed: StringScore,
+    },
+    /// The string has a different number of scored shots than the match's course of fire.
+    ShotCountMismatch {
+        expected: usize,
+        found:    usize,
+    },
+}
+
+impl std::fmt::Display for ValidationFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ScoreMismatch {
+                recorded,
+                computed,
+            } => {
+                write!(f, "Recorded score {recorded} doesn't match the shots' total of {computed}")
+            }
+            Self::ShotCountMismatch {
+                expected,
+                found,
+            } => write!(f, "Expected {expected} scored shots, found {found}"),
+        }
+    }
+}
+
+/// Totals the shots that count towards a string's score (those that aren't sighters or hidden),
+/// under the same scoring convention as `recorded`.
+pub fn computed_score<S: ScoredShot>(shots: &[S], recorded: &StringScore) -> StringScore {
+    let mut score = StringScore {
+        points:     0,
+        x_count:    0,
+        convention: recorded.convention,
+    };
+    for shot in shots.iter().filter(|shot| shot.is_scored()) {
+        score.points += shot.score().points(recorded.convention);
+        score.x_count += u32::from(shot.score().is_centre());
+    }
+
+    score
+}
+
+/// Checks the score in a string's header against its shots, along with the number of scored
+/// shots when the course of fire is known.
+pub fn validate_shot_string(
+    shot_string: &ShotMarkerShotString,
+    expected_shot_count: Option<usize>,
+) -> Vec<ValidationFinding> {
+    validate_score(&shot_string.score, &shot_string.shots, expected_shot_count)
+}
+
+/// Checks a string's `recorded` score against its `shots`. See [`validate_shot_string`].
+pub fn validate_score<S: ScoredShot>(
+    recorded: &StringScore,
+    shots: &[S],
+    expected_shot_count: Option<usize>,
+) -> Vec<ValidationFinding> {
+    let mut findings = Vec::new();
+
+    let computed = computed_score(shots, recorded);
+    if computed != *recorded {
+        findings.push(ValidationFinding::ScoreMismatch {
+            recorded: recorded.clone(),
+            computed,
+        });
+    }
+
+    let found = shots.iter().filter(|shot| shot.is_scored()).count();
+    if let Some(expected) = expected_shot_count.filter(|&expected| expected != found) {
+        findings.push(ValidationFinding::ShotCountMismatch {
+            expected,
+            found,
+        });
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests;
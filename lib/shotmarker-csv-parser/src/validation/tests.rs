@@ -0,0 +1,109 @@
+use super::*;
+use crate::{
+    parser::export_parser,
+    string::{
+        ScoringConvention,
+        shot::ShotTag,
+    },
+};
+
+const FINDINGS_INPUT: &str = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 strings from Jan 31 of 1 total in archive
+
+Jan 31 2025,Late Shooter,3,600y,30-2X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:10 pm,S1,sighter,X,12,10,0.47,0.39,0.07,0.06,0.02,0.02,611.1,2005,-0.6,-3.2,,
+,11:59:40 pm,1,,9,72,10,2.85,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,11:59:55 pm,2,,X,22,70,0.85,2.76,0.14,0.44,0.04,0.13,620.8,2037,-0.5,-3.2,,
+,12:00:10 am,3,hidden,10,40,12,1.57,0.47,0.25,0.08,0.07,0.02,615.4,2019,-0.5,-3.2,,
+";
+
+#[test]
+fn validate_example_files() {
+    let example_file_contents = [
+        include_str!("../../example-files/SM_shotslog_Jan_07.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_14.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_18.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_21.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_28.csv"),
+    ];
+    for example_file_content in example_file_contents {
+        let (_rest, export) = export_parser(example_file_content).unwrap();
+        for shot_string in &export.strings {
+            assert_eq!(validate_shot_string(shot_string, None), vec![], "{}", shot_string.name);
+        }
+    }
+}
+
+#[test]
+fn validate_shot_string_findings() {
+    let (_rest, export) = export_parser(FINDINGS_INPUT).unwrap();
+    let shot_string = &export.strings[0];
+
+    assert_eq!(
+        validate_shot_string(shot_string, Some(2)),
+        vec![ValidationFinding::ScoreMismatch {
+            recorded: shot_string.score.clone(),
+            computed: StringScore {
+                points:     19,
+                x_count:    1,
+                convention: ScoringConvention::Nra,
+            },
+        }]
+    );
+    assert_eq!(
+        validate_shot_string(shot_string, Some(20))[1],
+        ValidationFinding::ShotCountMismatch {
+            expected: 20,
+            found:    2,
+        }
+    );
+    assert_eq!(
+        validate_shot_string(shot_string, None)[0].to_string(),
+        "Recorded score 30-2X doesn't match the shots' total of 19-1X"
+    );
+}
+
+#[test]
+fn computed_score_after_editing_tags() {
+    let (_rest, export) = export_parser(FINDINGS_INPUT).unwrap();
+    let mut shots = export.strings[0].shots.clone();
+    let recorded = &export.strings[0].score;
+
+    shots[0].tags.remove(&ShotTag::Sighter);
+    shots[3].tags.remove(&ShotTag::Hidden);
+    assert_eq!(computed_score(&shots, recorded).to_string(), "39-2X");
+    assert_eq!(
+        validate_score(recorded, &shots, Some(4)),
+        vec![ValidationFinding::ScoreMismatch {
+            recorded: recorded.clone(),
+            computed: computed_score(&shots, recorded),
+        }]
+    );
+
+    shots[1].tags.insert(ShotTag::Hidden);
+    shots[1].tags.insert(ShotTag::Hidden);
+    assert_eq!(shots[1].tags.to_string(), "hidden");
+    assert_eq!(computed_score(&shots, recorded), *recorded);
+}
+
+#[test]
+fn computed_score_icfra_v_bulls() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 strings from Jan 31 of 1 total in archive
+
+Jan 31 2025,Late Shooter,3,1000y,14.2V
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,4,472,10,18.58,0.39,1.62,0.03,0.47,0.01,611.1,2005,-0.6,-3.2,,
+,11:59:55 pm,2,,V,22,70,0.85,2.76,0.08,0.24,0.02,0.07,620.8,2037,-0.5,-3.2,,
+,12:00:10 am,3,,V,-40,12,-1.57,0.47,-0.14,0.04,-0.04,0.01,615.4,2019,-0.5,-3.2,,
+";
+    let (_rest, export) = export_parser(input).unwrap();
+    let shot_string = &export.strings[0];
+
+    let computed = computed_score(&shot_string.shots, &shot_string.score);
+    assert_eq!(computed.to_string(), "14.2V");
+    assert_eq!(validate_shot_string(shot_string, Some(3)), vec![]);
+}
@@ -0,0 +1,192 @@
+use std::io::{
+    self,
+    Write,
+};
+
+use crate::{
+    ShotMarkerExport,
+    ShotMarkerExportHeader,
+    string::{
+        ShotMarkerMetricValues,
+        ShotMarkerShotString,
+        column::ShotColumn,
+        shot::{
+            ShotMarkerShot,
+            ShotScore,
+        },
+    },
+};
+
+/// Writes a complete export in ShotMarker's archived data CSV layout. An export parsed from a
+/// ShotMarker file is written back out byte for byte, except for any padding around the date,
+/// target, distance and score in a string's header, which is dropped when they're parsed. String
+/// names keep theirs.
+///
+/// The number of exported strings is taken from `export.strings` rather than
+/// [`ShotMarkerExport::string_count`], so that an export with strings removed or merged still
+/// reads back in.
+pub fn write_export<W: Write>(writer: &mut W, export: &ShotMarkerExport) -> io::Result<()> {
+    let header = ShotMarkerExportHeader {
+        string_count: export.strings.len(),
+        ..export.header()
+    };
+
+    write_export_header(writer, &header)?;
+    for shot_string in &export.strings {
+        write_shot_string(writer, shot_string)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a complete export to a string. See [`write_export`].
+pub fn export_to_string(export: &ShotMarkerExport) -> String {
+    let mut buffer = Vec::new();
+    write_export(&mut buffer, export).expect("Writing to a Vec can't fail");
+
+    String::from_utf8(buffer).expect("Exports are written as UTF-8")
+}
+
+/// Writes the lines at the top of an export, such as:
+///   ShotMarker Archived Data (generated Jan 07 2025)
+///   Exported 53 strings from Jan 07 of 53 total in archive
+///
+/// Follow it with [`write_shot_string`] for each of the `header.string_count` strings.
+pub fn write_export_header<W: Write>(
+    writer: &mut W,
+    header: &ShotMarkerExportHeader,
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        "ShotMarker Archived Data (generated {})",
+        header.generated_date.format("%b %d %Y")
+    )?;
+    writeln!(
+        writer,
+        "Exported {} strings from {} of {} total in archive",
+        header.string_count,
+        header.string_date.format("%b %d"),
+        header.archive_string_count,
+    )
+}
+
+/// Writes a shot string, along with the blank line that separates it from whatever comes before
+/// it. The shot and metrics rows are laid out by the string's [`ShotMarkerShotString::columns`].
+pub fn write_shot_string<W: Write>(
+    writer: &mut W,
+    shot_string: &ShotMarkerShotString,
+) -> io::Result<()> {
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{},{},{},{},{}",
+        shot_string.date.format("%b %d %Y"),
+        shot_string.name,
+        shot_string.target,
+        shot_string.distance,
+        shot_string.score,
+    )?;
+    writeln!(writer)?;
+
+    for name in shot_string.columns.names() {
+        write!(writer, ",{name}")?;
+    }
+    writeln!(writer)?;
+
+    for shot in &shot_string.shots {
+        write_row(writer, shot_string, |name, column| shot_cell(shot, name, column))?;
+    }
+    writeln!(writer)?;
+
+    if let Some(metrics) = &shot_string.metrics {
+        write_row(writer, shot_string, |_name, column| {
+            metric_cell(&metrics.average, "avg:", column)
+        })?;
+        write_row(writer, shot_string, |_name, column| {
+            metric_cell(&metrics.std_deviation, "SD:", column)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Writes a row with a cell for each of the string's columns. Like ShotMarker, rows begin with the
+/// blank first column and end with a trailing `,`.
+fn write_row<W, F>(
+    writer: &mut W,
+    shot_string: &ShotMarkerShotString,
+    mut cell: F,
+) -> io::Result<()>
+where
+    W: Write,
+    F: FnMut(&str, Option<ShotColumn>) -> String,
+{
+    write!(writer, ",")?;
+    for (name, column) in shot_string.columns.iter() {
+        write!(writer, "{},", cell(name.trim(), column))?;
+    }
+
+    writeln!(writer)
+}
+
+fn shot_cell(shot: &ShotMarkerShot, name: &str, column: Option<ShotColumn>) -> String {
+    let Some(column) = column else {
+        return shot.extras.get(name).cloned().unwrap_or_default();
+    };
+
+    match column {
+        ShotColumn::Id => shot.id.clone(),
+        ShotColumn::InchX => shot.position.inch.x.to_string(),
+        ShotColumn::InchY => shot.position.inch.y.to_string(),
+        ShotColumn::MilX => shot.position.mil.x.to_string(),
+        ShotColumn::MilY => shot.position.mil.y.to_string(),
+        ShotColumn::MmX => shot.position.mm.x.to_string(),
+        ShotColumn::MmY => shot.position.mm.y.to_string(),
+        ShotColumn::MoaX => shot.position.moa.x.to_string(),
+        ShotColumn::MoaY => shot.position.moa.y.to_string(),
+        ShotColumn::Pitch => shot.pitch.to_string(),
+        ShotColumn::Quality => shot.quality.map(|quality| quality.to_string()).unwrap_or_default(),
+        ShotColumn::Score => {
+            match shot.score {
+                ShotScore::None => String::new(),
+                score => score.to_string(),
+            }
+        }
+        ShotColumn::Tags => shot.tags.to_string(),
+        ShotColumn::Time => shot.time.format("%-I:%M:%S %P").to_string(),
+        ShotColumn::VelocityFps => shot.velocity.fps.to_string(),
+        ShotColumn::VelocityMs => shot.velocity.ms.to_string(),
+        ShotColumn::Yaw => shot.yaw.to_string(),
+    }
+}
+
+/// An `avg:` or `SD:` row's cell, which carries `label` in the score column and nothing in the
+/// columns before it.
+fn metric_cell(values: &ShotMarkerMetricValues, label: &str, column: Option<ShotColumn>) -> String {
+    let Some(column) = column else {
+        return String::new();
+    };
+
+    match column {
+        ShotColumn::Id | ShotColumn::Tags | ShotColumn::Time => String::new(),
+        ShotColumn::InchX => values.position.inch.x.to_string(),
+        ShotColumn::InchY => values.position.inch.y.to_string(),
+        ShotColumn::MilX => values.position.mil.x.to_string(),
+        ShotColumn::MilY => values.position.mil.y.to_string(),
+        ShotColumn::MmX => values.position.mm.x.to_string(),
+        ShotColumn::MmY => values.position.mm.y.to_string(),
+        ShotColumn::MoaX => values.position.moa.x.to_string(),
+        ShotColumn::MoaY => values.position.moa.y.to_string(),
+        ShotColumn::Pitch => values.pitch.to_string(),
+        ShotColumn::Quality => {
+            values.quality.map(|quality| quality.to_string()).unwrap_or_default()
+        }
+        ShotColumn::Score => label.to_string(),
+        ShotColumn::VelocityFps => values.velocity.fps.to_string(),
+        ShotColumn::VelocityMs => values.velocity.ms.to_string(),
+        ShotColumn::Yaw => values.yaw.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,112 @@
+use super::*;
+use crate::parser::export_parser;
+
+#[test]
+fn write_export_round_trips_example_files() {
+    let example_file_contents = [
+        include_str!("../../example-files/SM_shotslog_Jan_07.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_14.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_18.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_21.csv"),
+        include_str!("../../example-files/SM_shotslog_Jan_28.csv"),
+    ];
+    for (idx, example_file_content) in example_file_contents.iter().enumerate() {
+        let (_rest, export) = export_parser(example_file_content).unwrap();
+        let written = export_to_string(&export);
+
+        // Compare line by line first, so a mismatch points at the line that differs.
+        for (line_idx, (written_line, original_line)) in
+            written.lines().zip(example_file_content.lines()).enumerate()
+        {
+            assert_eq!(
+                written_line,
+                original_line,
+                "Example file {idx} differs on line {}",
+                line_idx + 1
+            );
+        }
+        assert_eq!(written, *example_file_content, "Example file {idx} differs");
+    }
+}
+
+#[test]
+fn write_export_round_trips_extra_columns() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 strings from Jan 31 of 12 total in archive
+
+Jan 31 2025, Late Shooter ,3,600y,20-1X
+
+,id,time,score,x (mm),y (mm),x (moa),y (moa),x (mil),y (mil),v (fps),yaw (deg), pitch (deg),wind (mph),quality
+,1,11:59:40 pm,10,72,10,0.45,0.06,0.13,0.02,2005,-0.6,-3.2,7,,
+,2,12:00:05 am,X,22,70,0.14,0.44,0.04,0.13,2037,-0.5,-3.2,,0.4,
+
+,,,avg:,47,40,0.3,0.25,0.09,0.08,2021,-0.55,-3.2,,0.4,
+,,,SD:,35.36,42.43,0.22,0.27,0.06,0.08,22.6,0.07,0,,0,
+";
+    let (_rest, export) = export_parser(input).unwrap();
+    assert_eq!(export.archive_string_count, 12);
+    assert_eq!(export.strings[0].name, " Late Shooter ");
+
+    assert_eq!(export_to_string(&export), input);
+}
+
+#[test]
+fn write_export_drops_padding_around_typed_header_fields() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 strings from Jan 31 of 1 total in archive
+
+Jan 31 2025, Late Shooter , 3 , 600y , 20-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,10,72,10,2.83,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,11:59:55 pm,2,,X,22,70,0.87,2.76,0.14,0.44,0.04,0.13,620.8,2037,-0.5,-3.2,,
+
+";
+    let (_rest, export) = export_parser(input).unwrap();
+
+    let unpadded = input.replace(" 3 , 600y , 20-1X", "3,600y,20-1X");
+    assert_eq!(export_to_string(&export), unpadded);
+}
+
+#[test]
+fn write_export_round_trips_icfra_scores() {
+    let input = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 1 strings from Jan 31 of 1 total in archive
+
+Jan 31 2025,Late Shooter,3,1000y,10.1V
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,11:59:40 pm,1,,5,172,10,6.77,0.39,0.65,0.04,0.19,0.01,611.1,2005,-0.6,-3.2,,
+,11:59:55 pm,2,,V,22,70,0.85,2.76,0.08,0.26,0.02,0.08,620.8,2037,-0.5,-3.2,,
+
+";
+    let (_rest, export) = export_parser(input).unwrap();
+
+    assert_eq!(export_to_string(&export), input);
+}
+
+#[test]
+fn write_export_without_sighters() {
+    let (_rest, mut export) =
+        export_parser(include_str!("../../example-files/SM_shotslog_Jan_21.csv")).unwrap();
+    let shot_count = export.strings.iter().map(|string| string.shots.len()).sum::<usize>();
+    for shot_string in &mut export.strings {
+        shot_string.shots.retain(|shot| !shot.is_sighter());
+    }
+    export.strings.retain(|shot_string| !shot_string.shots.is_empty());
+
+    let written = export_to_string(&export);
+    let (rest, filtered) = export_parser(&written).unwrap();
+
+    assert!(rest.is_empty());
+    assert_eq!(filtered.string_count, export.strings.len());
+    assert_eq!(filtered.archive_string_count, export.archive_string_count);
+    assert!(
+        filtered.strings.iter().flat_map(|string| &string.shots).all(|shot| !shot.is_sighter())
+    );
+    assert!(
+        filtered.strings.iter().map(|string| string.shots.len()).sum::<usize>() < shot_count,
+        "Expected sighters to be removed"
+    );
+    assert_eq!(format!("{:?}", filtered.strings), format!("{:?}", export.strings));
+}
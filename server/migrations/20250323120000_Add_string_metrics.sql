@@ -0,0 +1,2 @@
+ALTER TABLE strings
+ADD COLUMN metrics TEXT;
@@ -0,0 +1,11 @@
+ALTER TABLE shots
+ADD COLUMN shot_date_time TEXT;
+
+-- Shots imported before this column existed get their string's date. Strings fired across
+-- midnight need to be re-imported to pick up the day rollover.
+UPDATE shots
+SET shot_date_time = (
+    SELECT strings.string_date || ' ' || shots.shot_time
+    FROM strings
+    WHERE strings.id = shots.string_id
+  );
@@ -0,0 +1,3 @@
+-- Values from shot columns the parser doesn't know about, as a JSON object keyed by column name.
+ALTER TABLE shots
+ADD COLUMN extras TEXT;
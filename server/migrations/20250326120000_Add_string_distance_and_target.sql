@@ -0,0 +1,27 @@
+-- Typed copies of the raw distance and target text, so strings can be filtered and grouped by them.
+-- NULL where the raw text isn't a distance such as `600y`/`300m`, or a target lane number.
+ALTER TABLE strings
+ADD COLUMN distance_yards REAL;
+
+ALTER TABLE strings
+ADD COLUMN distance_meters REAL;
+
+ALTER TABLE strings
+ADD COLUMN target_lane INTEGER;
+
+UPDATE strings
+SET distance_yards = CAST(substr(distance, 1, length(distance) - 1) AS REAL),
+  distance_meters = CAST(substr(distance, 1, length(distance) - 1) AS REAL) * 0.9144
+WHERE distance GLOB '[0-9]*y'
+  AND substr(distance, 1, length(distance) - 1) NOT GLOB '*[^0-9]*';
+
+UPDATE strings
+SET distance_yards = CAST(substr(distance, 1, length(distance) - 1) AS REAL) / 0.9144,
+  distance_meters = CAST(substr(distance, 1, length(distance) - 1) AS REAL)
+WHERE distance GLOB '[0-9]*m'
+  AND substr(distance, 1, length(distance) - 1) NOT GLOB '*[^0-9]*';
+
+UPDATE strings
+SET target_lane = CAST(target AS INTEGER)
+WHERE target GLOB '[0-9]*'
+  AND target NOT GLOB '*[^0-9]*';
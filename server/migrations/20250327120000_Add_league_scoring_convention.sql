@@ -0,0 +1,4 @@
+-- How the league's strings are scored: NRA (10 down with X's) or ICFRA (5 down with V-bulls).
+-- Stored as JSON, like the scores themselves.
+ALTER TABLE leagues
+ADD COLUMN scoring_convention TEXT NOT NULL DEFAULT '"Nra"';
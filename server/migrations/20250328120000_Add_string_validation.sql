@@ -0,0 +1,8 @@
+-- The number of scored shots in each string of the match's course of fire, if known.
+ALTER TABLE matches
+ADD COLUMN shots_per_string INTEGER;
+
+-- JSON array of findings from checking the string's recorded score against its shots on upload.
+-- NULL for strings uploaded before the check existed.
+ALTER TABLE strings
+ADD COLUMN validation_findings TEXT;
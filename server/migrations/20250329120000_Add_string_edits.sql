@@ -0,0 +1,7 @@
+-- The values as uploaded, kept when a string is edited so the edits can be reverted. NULL while
+-- the string or shot hasn't been edited.
+ALTER TABLE strings
+ADD COLUMN original_score TEXT;
+
+ALTER TABLE shots
+ADD COLUMN original_tags TEXT;
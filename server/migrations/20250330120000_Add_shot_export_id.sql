@@ -0,0 +1,11 @@
+-- The export each shot was uploaded in, which stays with the shot when strings are merged or
+-- split.
+ALTER TABLE shots
+ADD COLUMN export_id TEXT REFERENCES exports(id) ON DELETE CASCADE;
+
+UPDATE shots
+SET export_id = (
+    SELECT strings.export_id
+    FROM strings
+    WHERE strings.id = shots.string_id
+  );
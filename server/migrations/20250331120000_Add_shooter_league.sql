@@ -0,0 +1,39 @@
+-- Shooters belong to a league, as the classes they default to do. Existing shooters are moved into
+-- the league of their default class or, without one, the league they last shot a string in.
+ALTER TABLE shooters
+ADD COLUMN league_id TEXT REFERENCES leagues(id) ON DELETE CASCADE;
+
+UPDATE shooters
+SET league_id = (
+    SELECT classes.league_id
+    FROM classes
+    WHERE classes.id = shooters.default_class_id
+  );
+
+UPDATE shooters
+SET league_id = (
+    SELECT matches.league_id
+    FROM strings
+      INNER JOIN exports ON exports.id = strings.export_id
+      INNER JOIN matches ON matches.id = exports.match_id
+    WHERE strings.shooter_id = shooters.id
+    ORDER BY matches.event_date DESC
+    LIMIT 1
+  )
+WHERE league_id IS NULL;
+
+-- Shooters who've done neither go in the oldest league, where they can be renamed, moved to a
+-- class, or deleted.
+UPDATE shooters
+SET league_id = (
+    SELECT id
+    FROM leagues
+    ORDER BY created_at,
+      id
+    LIMIT 1
+  )
+WHERE league_id IS NULL;
+
+-- Without any leagues, there's nowhere for them to be.
+DELETE FROM shooters
+WHERE league_id IS NULL;
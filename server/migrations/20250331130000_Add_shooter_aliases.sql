@@ -0,0 +1,12 @@
+-- Other names a shooter's strings have been given, such as misspellings, which the names of
+-- uploaded strings are matched against.
+CREATE TABLE shooter_aliases (
+  shooter_id TEXT NOT NULL REFERENCES shooters(id) ON DELETE CASCADE,
+  alias TEXT NOT NULL,
+  PRIMARY KEY (shooter_id, alias)
+);
+
+-- The shooter a string's name most likely belongs to, when it wasn't a close enough match to be
+-- assigned without review.
+ALTER TABLE strings
+ADD COLUMN shooter_proposal TEXT;
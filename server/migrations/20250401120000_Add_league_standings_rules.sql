@@ -0,0 +1,4 @@
+-- How the league's matches are combined into its season standings: which count, who qualifies,
+-- and how ties are broken. Stored as JSON; missing fields take their defaults.
+ALTER TABLE leagues
+ADD COLUMN standings_rules TEXT NOT NULL DEFAULT '{}';
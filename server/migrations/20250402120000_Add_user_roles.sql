@@ -0,0 +1,19 @@
+-- What each user may do. Site admins can do anything; the other roles are scoped to one league,
+-- and are removed along with it.
+CREATE TABLE user_roles (
+  user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  role TEXT NOT NULL,
+  league_id TEXT REFERENCES leagues(id) ON DELETE CASCADE
+);
+
+CREATE INDEX user_roles_user_id ON user_roles (user_id);
+
+-- Anyone could register before roles, so only the first user, by the order users were added in,
+-- is made a site admin. Everyone else needs to be granted roles.
+INSERT INTO user_roles (user_id, role, league_id)
+SELECT id,
+  'SiteAdmin',
+  NULL
+FROM users
+ORDER BY rowid
+LIMIT 1;
@@ -0,0 +1,12 @@
+-- Disabled users can't log in, and are logged out of the sessions they have.
+ALTER TABLE users
+ADD COLUMN active BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- Codes that let someone register when registration is invite-only. Each can be used once.
+CREATE TABLE invites (
+  code TEXT PRIMARY KEY,
+  created_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+  created_at TEXT NOT NULL,
+  used_by TEXT REFERENCES users(id) ON DELETE SET NULL,
+  used_at TEXT
+);
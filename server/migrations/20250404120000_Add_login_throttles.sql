@@ -0,0 +1,10 @@
+-- Failed logins and registrations, by the username or address they came from. Once there have
+-- been a few, each further attempt has to wait out a lockout that doubles with each failure.
+CREATE TABLE login_throttles (
+  kind TEXT NOT NULL,
+  subject TEXT NOT NULL,
+  failures INTEGER NOT NULL,
+  last_failure_at TEXT NOT NULL,
+  locked_until TEXT,
+  PRIMARY KEY (kind, subject)
+);
@@ -0,0 +1,4 @@
+-- The instant each shot was fired, when the time zone ShotMarker clocks are set to is known.
+-- Shots imported before then only have the ShotMarker's local time.
+ALTER TABLE shots
+ADD COLUMN shot_utc_date_time TEXT;
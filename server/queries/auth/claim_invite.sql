@@ -0,0 +1,5 @@
+UPDATE invites
+SET used_by = $2,
+  used_at = DATETIME()
+WHERE code = $1
+  AND used_at IS NULL;
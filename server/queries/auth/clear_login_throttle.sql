@@ -0,0 +1,3 @@
+DELETE FROM login_throttles
+WHERE kind = $1
+  AND subject = $2;
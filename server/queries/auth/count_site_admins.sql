@@ -0,0 +1,5 @@
+SELECT COUNT(*) AS "count!: i64"
+FROM user_roles
+  INNER JOIN users ON users.id = user_roles.user_id
+WHERE user_roles.role = 'SiteAdmin'
+  AND users.active;
@@ -0,0 +1,4 @@
+INSERT INTO invites (code, created_by, created_at)
+VALUES ($1, $2, DATETIME())
+RETURNING code AS 'code!: String',
+  created_at AS 'created_at!: DateTime<Utc>';
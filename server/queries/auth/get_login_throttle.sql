@@ -0,0 +1,6 @@
+SELECT failures AS 'failures!: i64',
+  last_failure_at AS 'last_failure_at!: DateTime<Utc>',
+  locked_until AS 'locked_until: DateTime<Utc>'
+FROM login_throttles
+WHERE kind = $1
+  AND subject = $2;
@@ -1,5 +1,6 @@
 SELECT id AS 'id!: Uuid',
   username AS 'username!: String',
-  password_hash AS 'password_hash!: String'
+  password_hash AS 'password_hash!: String',
+  active AS 'active!: bool'
 FROM users
 WHERE username = $1;
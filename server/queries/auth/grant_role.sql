@@ -0,0 +1,11 @@
+INSERT INTO user_roles (user_id, role, league_id)
+SELECT $1,
+  $2,
+  $3
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM user_roles
+    WHERE user_id = $1
+      AND role = $2
+      AND league_id IS $3
+  );
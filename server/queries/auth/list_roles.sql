@@ -0,0 +1,6 @@
+SELECT role AS "role!: String",
+  league_id AS "league_id: Uuid"
+FROM user_roles
+WHERE user_id = $1
+ORDER BY role,
+  league_id;
@@ -0,0 +1,6 @@
+SELECT id AS 'id!: Uuid',
+  username AS 'username!: String',
+  password_hash AS 'password_hash!: String',
+  active AS 'active!: bool'
+FROM users
+ORDER BY username;
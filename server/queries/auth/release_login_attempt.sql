@@ -0,0 +1,4 @@
+UPDATE login_throttles
+SET failures = MAX(failures - 1, 0)
+WHERE kind = $1
+  AND subject = $2;
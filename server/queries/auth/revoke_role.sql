@@ -0,0 +1,4 @@
+DELETE FROM user_roles
+WHERE user_id = $1
+  AND role = $2
+  AND league_id IS $3;
@@ -0,0 +1,3 @@
+UPDATE users
+SET active = $2
+WHERE id = $1;
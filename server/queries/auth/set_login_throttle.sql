@@ -0,0 +1,12 @@
+INSERT INTO login_throttles (
+    kind,
+    subject,
+    failures,
+    last_failure_at,
+    locked_until
+  )
+VALUES ($1, $2, $3, $4, $5) ON CONFLICT (kind, subject) DO
+UPDATE
+SET failures = excluded.failures,
+  last_failure_at = excluded.last_failure_at,
+  locked_until = excluded.locked_until;
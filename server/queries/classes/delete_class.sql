@@ -0,0 +1,3 @@
+DELETE FROM classes
+WHERE league_id = $1
+  AND id = $2;
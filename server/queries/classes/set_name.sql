@@ -0,0 +1,4 @@
+UPDATE classes
+SET class_name = $3
+WHERE league_id = $1
+  AND id = $2;
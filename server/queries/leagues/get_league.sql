@@ -3,6 +3,8 @@ SELECT id AS "id!: Uuid",
   description AS "description: String",
   start_date AS "start_date: NaiveDate",
   end_date AS "end_date: NaiveDate",
+  scoring_convention AS "scoring_convention!: sqlx::types::Json<ScoringConvention>",
+  standings_rules AS "standings_rules!: sqlx::types::Json<StandingsRules>",
   created_at AS "created_at!: DateTime<Utc>"
 FROM leagues
 WHERE id = $1;
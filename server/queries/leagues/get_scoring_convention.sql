@@ -0,0 +1,3 @@
+SELECT scoring_convention AS "scoring_convention!: sqlx::types::Json<ScoringConvention>"
+FROM leagues
+WHERE id = $1;
@@ -0,0 +1,3 @@
+UPDATE leagues
+SET description = $2
+WHERE id = $1;
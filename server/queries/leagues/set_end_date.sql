@@ -0,0 +1,3 @@
+UPDATE leagues
+SET end_date = $2
+WHERE id = $1;
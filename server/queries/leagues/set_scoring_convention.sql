@@ -0,0 +1,3 @@
+UPDATE leagues
+SET scoring_convention = $2
+WHERE id = $1;
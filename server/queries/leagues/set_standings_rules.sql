@@ -0,0 +1,3 @@
+UPDATE leagues
+SET standings_rules = $2
+WHERE id = $1;
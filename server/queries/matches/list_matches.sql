@@ -1,6 +1,7 @@
 SELECT id AS "id!: uuid::Uuid",
   match_name AS "name!: String",
-  event_date AS "event_date!: NaiveDate"
+  event_date AS "event_date!: NaiveDate",
+  shots_per_string AS "shots_per_string: u32"
 FROM matches
 WHERE league_id = $1
 ORDER BY event_date
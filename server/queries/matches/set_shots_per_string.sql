@@ -0,0 +1,4 @@
+UPDATE matches
+SET shots_per_string = $3
+WHERE league_id = $1
+  AND id = $2;
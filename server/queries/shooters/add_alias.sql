@@ -0,0 +1,2 @@
+INSERT OR IGNORE INTO shooter_aliases (shooter_id, alias)
+VALUES ($1, $2);
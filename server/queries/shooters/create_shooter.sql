@@ -0,0 +1,2 @@
+INSERT INTO shooters (id, shooter_name, default_class_id, league_id)
+VALUES ($1, $2, $3, $4);
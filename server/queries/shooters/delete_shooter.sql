@@ -0,0 +1,3 @@
+DELETE FROM shooters
+WHERE league_id = $1
+  AND id = $2;
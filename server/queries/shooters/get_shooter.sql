@@ -0,0 +1,12 @@
+SELECT id AS 'id!: Uuid',
+  shooter_name AS 'name!: String',
+  default_class_id AS 'default_class_id: Uuid',
+  league_id AS 'league_id!: Uuid',
+  (
+    SELECT json_group_array(alias)
+    FROM shooter_aliases
+    WHERE shooter_aliases.shooter_id = shooters.id
+  ) AS 'aliases!: sqlx::types::Json<Vec<String>>'
+FROM shooters
+WHERE league_id = $1
+  AND id = $2;
@@ -0,0 +1,3 @@
+DELETE FROM shooter_aliases
+WHERE shooter_id = $1
+  AND alias = $2;
@@ -0,0 +1,4 @@
+UPDATE shooters
+SET default_class_id = $3
+WHERE league_id = $1
+  AND id = $2;
@@ -0,0 +1,4 @@
+UPDATE shooters
+SET shooter_name = $3
+WHERE league_id = $1
+  AND id = $2;
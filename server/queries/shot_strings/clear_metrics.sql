@@ -0,0 +1,3 @@
+UPDATE strings
+SET metrics = NULL
+WHERE id = $1;
@@ -0,0 +1,36 @@
+INSERT INTO strings (
+    id,
+    string_date,
+    string_name,
+    target,
+    distance,
+    score,
+    original_score,
+    metrics,
+    export_id,
+    shooter_id,
+    class_id,
+    distance_yards,
+    distance_meters,
+    target_lane,
+    validation_findings,
+    shooter_proposal
+  )
+SELECT $2,
+  string_date,
+  string_name,
+  target,
+  distance,
+  score,
+  COALESCE(original_score, score),
+  NULL,
+  export_id,
+  shooter_id,
+  class_id,
+  distance_yards,
+  distance_meters,
+  target_lane,
+  validation_findings,
+  shooter_proposal
+FROM strings
+WHERE id = $1;
@@ -5,6 +5,11 @@ INSERT INTO strings (
     target,
     distance,
     score,
-    export_id
+    metrics,
+    export_id,
+    distance_yards,
+    distance_meters,
+    target_lane,
+    validation_findings
   )
-VALUES ($1, $2, $3, $4, $5, $6, $7);
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12);
@@ -0,0 +1,2 @@
+DELETE FROM strings
+WHERE id = $1;
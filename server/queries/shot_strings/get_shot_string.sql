@@ -4,9 +4,13 @@ SELECT strings.id AS 'id!: Uuid',
   strings.target AS 'target!: String',
   strings.distance AS 'distance!: String',
   strings.score AS 'score!: sqlx::types::Json<StringScore>',
+  strings.metrics AS 'metrics: sqlx::types::Json<ShotMarkerStringMetrics>',
   strings.export_id AS 'export_id!: Uuid',
   strings.shooter_id AS 'shooter_id: Uuid',
-  strings.class_id AS 'class_id: Uuid'
+  strings.class_id AS 'class_id: Uuid',
+  strings.validation_findings AS 'validation_findings: sqlx::types::Json<Vec<ValidationFinding>>',
+  strings.original_score IS NOT NULL AS 'edited!: bool',
+  strings.shooter_proposal AS 'shooter_proposal: sqlx::types::Json<ShooterProposal>'
 FROM "strings"
   INNER JOIN exports ON exports.id = strings.export_id
 WHERE exports.match_id = $1
@@ -0,0 +1,4 @@
+UPDATE strings
+SET score = COALESCE(original_score, score),
+  original_score = NULL
+WHERE id = $1;
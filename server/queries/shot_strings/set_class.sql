@@ -0,0 +1,3 @@
+UPDATE strings
+SET class_id = $2
+WHERE id = $1;
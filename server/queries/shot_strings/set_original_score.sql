@@ -0,0 +1,3 @@
+UPDATE strings
+SET original_score = $2
+WHERE id = $1;
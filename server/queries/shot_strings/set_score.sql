@@ -0,0 +1,4 @@
+UPDATE strings
+SET original_score = COALESCE(original_score, score),
+  score = $2
+WHERE id = $1;
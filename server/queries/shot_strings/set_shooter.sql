@@ -0,0 +1,5 @@
+UPDATE strings
+SET shooter_id = $2,
+  class_id = $3,
+  shooter_proposal = NULL
+WHERE id = $1;
@@ -0,0 +1,3 @@
+UPDATE strings
+SET shooter_proposal = $2
+WHERE id = $1;
@@ -0,0 +1,3 @@
+UPDATE strings
+SET validation_findings = $2
+WHERE id = $1;
@@ -0,0 +1,4 @@
+SELECT COUNT(*) AS "count!: i64"
+FROM shots
+WHERE string_id = $1
+  AND original_tags IS NOT NULL;
@@ -1,6 +1,8 @@
 INSERT INTO shots (
     id,
     shot_time,
+    shot_date_time,
+    shot_utc_date_time,
     shot_id,
     tags,
     score,
@@ -9,6 +11,24 @@ INSERT INTO shots (
     yaw,
     pitch,
     quality,
-    string_id
+    extras,
+    string_id,
+    export_id
   )
-VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11);
+VALUES (
+    $1,
+    $2,
+    $3,
+    $4,
+    $5,
+    $6,
+    $7,
+    $8,
+    $9,
+    $10,
+    $11,
+    $12,
+    $13,
+    $14,
+    $15
+  );
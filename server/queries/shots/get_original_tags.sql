@@ -0,0 +1,3 @@
+SELECT COALESCE(original_tags, tags) AS "tags!: String"
+FROM shots
+WHERE id = $1;
@@ -1,5 +1,7 @@
 SELECT shots.id AS "id!: Uuid",
   shots.shot_time AS "shot_time!: NaiveTime",
+  shots.shot_date_time AS "shot_date_time!: NaiveDateTime",
+  shots.shot_utc_date_time AS "shot_utc_date_time: DateTime<Utc>",
   shots.shot_id AS "shot_id!: String",
   shots.tags AS "tags!: String",
   shots.score AS "score!: sqlx::types::Json<ShotScore>",
@@ -8,7 +10,9 @@ SELECT shots.id AS "id!: Uuid",
   shots.yaw AS "yaw!: f64",
   shots.pitch AS "pitch!: f64",
   shots.quality AS "quality?: String",
-  shots.string_id AS "shot_string_id!: Uuid"
+  shots.extras AS "extras?: sqlx::types::Json<BTreeMap<String, String>>",
+  shots.string_id AS "shot_string_id!: Uuid",
+  shots.export_id AS "export_id!: Uuid"
 FROM shots
   INNER JOIN strings ON strings.id = shots.string_id
   INNER JOIN exports ON exports.id = strings.export_id
@@ -16,4 +20,4 @@ FROM shots
 WHERE matches.league_id = $1
   AND matches.id = $2
   AND strings.id = $3
-ORDER BY shots.shot_time;
+ORDER BY shots.shot_date_time;
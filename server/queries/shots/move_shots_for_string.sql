@@ -0,0 +1,3 @@
+UPDATE shots
+SET string_id = $2
+WHERE string_id = $1;
@@ -0,0 +1,5 @@
+UPDATE shots
+SET tags = COALESCE(original_tags, tags),
+  original_tags = NULL
+WHERE string_id = $1
+  AND id = $2;
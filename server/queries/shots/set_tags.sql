@@ -0,0 +1,5 @@
+UPDATE shots
+SET original_tags = COALESCE(original_tags, tags),
+  tags = $3
+WHERE string_id = $1
+  AND id = $2;
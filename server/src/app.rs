@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 use axum::{
     Json,
     Router,
@@ -22,6 +24,7 @@ use axum_session::{
 };
 use axum_session_auth::AuthConfig;
 use axum_session_sqlx::SessionSqlitePool;
+use chrono_tz::Tz;
 use serde_json::json;
 use sqlx::{
     Sqlite,
@@ -39,7 +42,10 @@ use tower_http::{
 use uuid::Uuid;
 
 use crate::{
-    app::auth::AppAuthSessionLayer,
+    app::auth::{
+        AppAuthSessionLayer,
+        RegistrationMode,
+    },
     error::AppError,
 };
 
@@ -48,12 +54,24 @@ pub mod classes;
 pub mod export;
 pub mod leagues;
 pub mod matches;
+pub mod results;
+pub mod shooters;
 pub mod shots;
+pub mod standings;
 pub mod strings;
 
 #[derive(Debug, Clone)]
 pub struct AppState {
-    pub db_pool: SqlitePool,
+    pub db_pool:           SqlitePool,
+    pub registration_mode: RegistrationMode,
+    /// Proxies trusted to say who they're forwarding requests for, set by the `TRUSTED_PROXIES`
+    /// environment variable as a comma-separated list of addresses. By default, those on the same
+    /// machine, such as the UI's development server.
+    pub trusted_proxies:   Vec<IpAddr>,
+    /// The time zone ShotMarker clocks are set to, set by the `SHOTMARKER_TIME_ZONE` environment
+    /// variable as a name such as `America/Los_Angeles`. Without it, shots are only stored with the
+    /// ShotMarker's local time.
+    pub time_zone:         Option<Tz>,
 }
 
 pub struct DbTransaction<'a>(Transaction<'a, Sqlite>);
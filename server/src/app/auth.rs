@@ -1,5 +1,6 @@
+use std::str::FromStr;
+
 use anyhow::{
-    Context,
     Result,
     anyhow,
 };
@@ -19,6 +20,7 @@ use axum_session_auth::{
     HasPermission,
 };
 use axum_session_sqlx::SessionSqlitePool;
+use log::error;
 use password_hash::{
     PasswordHash,
     PasswordVerifier,
@@ -33,14 +35,20 @@ use sqlx::SqlitePool;
 use thiserror::Error;
 use uuid::Uuid;
 
+pub mod admin;
+pub mod roles;
 pub mod routes;
+pub mod throttle;
 
 use crate::{
-    app::AppState,
-    error::{
-        AppError,
-        HttpResponse,
+    app::{
+        AppState,
+        auth::roles::{
+            Permission,
+            fetch_roles,
+        },
     },
+    error::AppError,
 };
 
 pub type AppAuthSession = AuthSession<User, Uuid, SessionSqlitePool, SqlitePool>;
@@ -60,10 +68,10 @@ where
             |(status_code, error_body)| anyhow!("Auth failed ({status_code}): {error_body}"),
         )?;
 
-        if auth_session.current_user.is_some() {
-            Ok(Self(auth_session))
-        } else {
-            Err(AuthError::Unauthorized).context(HttpResponse::Unauthorized).map_err(Into::into)
+        match &auth_session.current_user {
+            Some(user) if user.is_active() => Ok(Self(auth_session)),
+            Some(_) => Err(AuthError::AccountDisabled.into()),
+            None => Err(AuthError::NotLoggedIn.into()),
         }
     }
 }
@@ -85,23 +93,76 @@ where
         if auth_session.current_user.is_none() {
             Ok(Self(auth_session))
         } else {
-            Err(AuthError::Unauthorized).context(HttpResponse::Unauthorized).map_err(Into::into)
+            Err(AuthError::AlreadyLoggedIn.into())
         }
     }
 }
 
 #[derive(Debug, Error)]
 pub enum AuthError {
+    #[error("Account disabled")]
+    AccountDisabled,
+    #[error("Already logged in")]
+    AlreadyLoggedIn,
+    #[error("Not permitted: {permission}")]
+    Forbidden {
+        permission: Permission,
+    },
+    #[error("Invalid username or password")]
+    InvalidCredentials,
+    #[error("Invalid or already used invite code")]
+    InvalidInvite,
+    #[error("Can't revoke or disable the last active site admin")]
+    LastSiteAdmin,
     #[error("DB connection pool not found")]
     NoDbConnectionPool,
     #[error("User not logged in")]
     NotLoggedIn,
-    #[error("Unauthorized")]
-    Unauthorized,
+    #[error("Registration is closed; ask an admin for an account")]
+    RegistrationClosed,
+    #[error("Too many failed attempts; try again in {retry_after} seconds")]
+    TooManyAttempts {
+        retry_after: u64,
+    },
     #[error("User not found: {userid}")]
     UserNotFound {
         userid: Uuid,
     },
+    #[error("No user with the username: {username}")]
+    UsernameNotFound {
+        username: String,
+    },
+    #[error("Username already taken: {username}")]
+    UsernameTaken {
+        username: String,
+    },
+}
+
+/// Who may register an account, set by the `REGISTRATION_MODE` environment variable as
+/// `admin-only`, `invite-code` or `open`. Whatever the mode, the first user may register, as
+/// there's nobody yet to make them an account or invite them.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum RegistrationMode {
+    /// Only admins make accounts.
+    AdminOnly,
+    /// Anyone with an unused invite code from an admin may register.
+    InviteCode,
+    /// Anyone may register.
+    #[default]
+    Open,
+}
+
+impl FromStr for RegistrationMode {
+    type Err = anyhow::Error;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode.to_ascii_lowercase().as_str() {
+            "admin-only" => Ok(RegistrationMode::AdminOnly),
+            "invite-code" => Ok(RegistrationMode::InviteCode),
+            "open" => Ok(RegistrationMode::Open),
+            _ => Err(anyhow!("Unknown registration mode: {mode}")),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +171,7 @@ pub struct User {
     pub username:  String,
     #[serde(skip_serializing)]
     password_hash: String,
+    pub active:    bool,
 }
 
 impl User {
@@ -120,6 +182,7 @@ impl User {
             id,
             username: username.to_owned(),
             password_hash: String::new(),
+            active: true,
         };
         new_user.set_password(password)?;
 
@@ -170,14 +233,40 @@ impl Authentication<User, Uuid, SqlitePool> for User {
 
     fn is_authenticated(&self) -> bool { true }
 
-    fn is_active(&self) -> bool { true }
+    fn is_active(&self) -> bool { self.active }
 
     fn is_anonymous(&self) -> bool { false }
 }
 
+/// Permissions are granted by the user's roles, which are looked up afresh for each check so that
+/// changes to them apply straight away.
 #[async_trait]
 impl HasPermission<SqlitePool> for User {
-    async fn has(&self, _perm: &str, _pool: &Option<&SqlitePool>) -> bool { false }
+    async fn has(&self, perm: &str, pool: &Option<&SqlitePool>) -> bool {
+        let Some(pool) = pool else {
+            error!("No DB connection pool to check {perm} for {}", self.username);
+            return false;
+        };
+        let permission = match perm.parse::<Permission>() {
+            Ok(permission) => permission,
+            Err(error) => {
+                error!("{error}");
+                return false;
+            }
+        };
+
+        let roles = match pool.acquire().await {
+            Ok(mut conn) => fetch_roles(&mut conn, self.id).await,
+            Err(error) => Err(error.into()),
+        };
+        match roles {
+            Ok(roles) => roles.iter().any(|role| permission.is_granted_by(role)),
+            Err(AppError(error)) => {
+                error!("Unable to load roles for {}: {error:#}", self.username);
+                false
+            }
+        }
+    }
 }
 
 impl From<User> for shared_types::response::User {
@@ -185,6 +274,8 @@ impl From<User> for shared_types::response::User {
         shared_types::response::User {
             id:       value.id,
             username: value.username,
+            active:   value.active,
+            roles:    Vec::new(),
         }
     }
 }
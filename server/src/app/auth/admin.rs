@@ -0,0 +1,171 @@
+use axum::{
+    Json,
+    Router,
+    extract::State,
+    routing::{
+        get,
+        post,
+    },
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use log::info;
+use shared_types::{
+    request::UserOperation,
+    response::{
+        Invite,
+        User,
+    },
+};
+use sqlx::SqliteConnection;
+use uuid::Uuid;
+
+use crate::{
+    app::{
+        AppState,
+        DbTransaction,
+        auth::{
+            AuthError,
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                count_site_admins,
+                require_permission,
+            },
+            routes::{
+                create_user,
+                with_roles,
+            },
+        },
+    },
+    error::AppError,
+};
+
+pub fn router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/invite", post(create_invite))
+        .route("/operation", post(handle_user_operation))
+        .route("/users", get(list_users))
+        .with_state(app_state)
+}
+
+pub async fn list_users(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+) -> Result<Json<Vec<User>>, AppError> {
+    require_permission(&auth_session, &app_state.db_pool, Permission::ManageSite).await?;
+
+    let auth_users = sqlx::query_file_as!(crate::app::auth::User, "queries/auth/list_users.sql")
+        .fetch_all(&mut *txn)
+        .await?;
+    let mut users = Vec::with_capacity(auth_users.len());
+    for auth_user in auth_users {
+        users.push(with_roles(&mut txn, auth_user).await?);
+    }
+
+    Ok(Json(users))
+}
+
+async fn fetch_user(
+    txn: &mut SqliteConnection,
+    username: &str,
+) -> Result<crate::app::auth::User, AppError> {
+    let maybe_user = sqlx::query_file_as!(
+        crate::app::auth::User,
+        "queries/auth/get_user_by_username.sql",
+        username
+    )
+    .fetch_optional(&mut *txn)
+    .await?;
+
+    maybe_user.ok_or_else(|| {
+        AuthError::UsernameNotFound {
+            username: username.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Makes, disables, enables or resets the password of an account, returning it afterwards.
+pub async fn handle_user_operation(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+    Json(operation): Json<UserOperation>,
+) -> Result<Json<User>, AppError> {
+    let user = &auth_session.current_user;
+    let session_id = auth_session.session.get_session_id();
+    info!("Handling user operation for {user:?} (Session: {session_id:?})");
+
+    require_permission(&auth_session, &app_state.db_pool, Permission::ManageSite).await?;
+
+    let auth_user = match &operation {
+        UserOperation::Create {
+            username,
+            password,
+        } => create_user(&mut txn, username, password).await?,
+        UserOperation::Disable {
+            username,
+        }
+        | UserOperation::Enable {
+            username,
+        } => {
+            let mut auth_user = fetch_user(&mut txn, username).await?;
+            auth_user.active = matches!(operation, UserOperation::Enable { .. });
+            sqlx::query_file!("queries/auth/set_active.sql", auth_user.id, auth_user.active)
+                .execute(&mut *txn)
+                .await?;
+            // Without an active site admin, nobody could manage the site again.
+            if !auth_user.active && count_site_admins(&mut txn).await? == 0 {
+                return Err(AuthError::LastSiteAdmin.into());
+            }
+            auth_user
+        }
+        UserOperation::ResetPassword {
+            username,
+            password,
+        } => {
+            let mut auth_user = fetch_user(&mut txn, username).await?;
+            auth_user.set_password(password)?;
+            sqlx::query_file!(
+                "queries/auth/set_password.sql",
+                auth_user.id,
+                auth_user.password_hash
+            )
+            .execute(&mut *txn)
+            .await?;
+            auth_user
+        }
+    };
+
+    let user = with_roles(&mut txn, auth_user).await?;
+    txn.commit().await?;
+    if matches!(operation, UserOperation::Disable { .. } | UserOperation::Enable { .. }) {
+        // The user's cached for their sessions, so they'd stay logged in as they were. Cleared
+        // once committed, or a request in the meantime could cache them as they were again.
+        auth_session.cache_clear_user(user.id).await;
+    }
+    Ok(Json(user))
+}
+
+pub async fn create_invite(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+) -> Result<Json<Invite>, AppError> {
+    require_permission(&auth_session, &app_state.db_pool, Permission::ManageSite).await?;
+    let Some(user) = &auth_session.current_user else {
+        return Err(AuthError::NotLoggedIn.into());
+    };
+
+    let code = Uuid::new_v4().simple().to_string();
+    let invite = sqlx::query_file_as!(Invite, "queries/auth/create_invite.sql", code, user.id)
+        .fetch_one(&mut *txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(Json(invite))
+}
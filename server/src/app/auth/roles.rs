@@ -0,0 +1,287 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use axum::{
+    Json,
+    extract::State,
+    http::Method,
+};
+use axum_session_auth::{
+    Auth,
+    Rights,
+};
+use log::info;
+use shared_types::{
+    request::RoleOperation,
+    response::Role,
+};
+use sqlx::{
+    SqliteConnection,
+    SqlitePool,
+};
+use uuid::Uuid;
+
+use crate::{
+    app::{
+        AppState,
+        DbTransaction,
+        auth::{
+            AppAuthSession,
+            AuthError,
+            AuthenticatedUser,
+            User,
+        },
+    },
+    error::AppError,
+};
+
+/// Something a user may be permitted to do, which they're granted by their roles.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Permission {
+    /// Changing a league's details, classes, matches and shooters, and granting roles in it.
+    ManageLeague {
+        league_id: Uuid,
+    },
+    /// Creating and deleting leagues, and granting site-wide roles.
+    ManageSite,
+    /// Uploading a league's exports, and editing and assigning their strings.
+    ScoreLeague {
+        league_id: Uuid,
+    },
+}
+
+impl Permission {
+    /// The permission needed to grant or revoke `role`.
+    pub fn to_grant(role: &Role) -> Self {
+        match role {
+            Role::LeagueOfficer {
+                league_id,
+            }
+            | Role::Scorer {
+                league_id,
+            }
+            | Role::Shooter {
+                league_id,
+            } => {
+                Permission::ManageLeague {
+                    league_id: *league_id,
+                }
+            }
+            Role::SiteAdmin => Permission::ManageSite,
+        }
+    }
+
+    pub fn is_granted_by(&self, role: &Role) -> bool {
+        match (role, self) {
+            (Role::SiteAdmin, _) => true,
+            (
+                Role::LeagueOfficer {
+                    league_id: role_league_id,
+                },
+                Permission::ManageLeague {
+                    league_id,
+                }
+                | Permission::ScoreLeague {
+                    league_id,
+                },
+            )
+            | (
+                Role::Scorer {
+                    league_id: role_league_id,
+                },
+                Permission::ScoreLeague {
+                    league_id,
+                },
+            ) => role_league_id == league_id,
+            _ => false,
+        }
+    }
+}
+
+/// Permissions are named in `HasPermission` checks like `ManageLeague:<league id>`.
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Permission::ManageLeague {
+                league_id,
+            } => write!(f, "ManageLeague:{league_id}"),
+            Permission::ManageSite => write!(f, "ManageSite"),
+            Permission::ScoreLeague {
+                league_id,
+            } => write!(f, "ScoreLeague:{league_id}"),
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = anyhow::Error;
+
+    fn from_str(permission: &str) -> Result<Self, Self::Err> {
+        let permission = match permission.split_once(':') {
+            None if permission == "ManageSite" => Permission::ManageSite,
+            Some(("ManageLeague", league_id)) => {
+                Permission::ManageLeague {
+                    league_id: league_id.parse()?,
+                }
+            }
+            Some(("ScoreLeague", league_id)) => {
+                Permission::ScoreLeague {
+                    league_id: league_id.parse()?,
+                }
+            }
+            _ => return Err(anyhow!("Unknown permission: {permission}")),
+        };
+
+        Ok(permission)
+    }
+}
+
+/// Checks that the logged in user has `permission`, through `axum_session_auth`'s
+/// `HasPermission`.
+pub(crate) async fn require_permission(
+    auth_session: &AppAuthSession,
+    db_pool: &SqlitePool,
+    permission: Permission,
+) -> Result<(), AppError> {
+    let Some(user) = &auth_session.current_user else {
+        return Err(AuthError::NotLoggedIn.into());
+    };
+
+    let permitted = Auth::<User, Uuid, SqlitePool>::build([Method::POST], true)
+        .requires(Rights::permission(&permission.to_string()))
+        .validate(user, &Method::POST, Some(db_pool))
+        .await;
+    if !permitted {
+        return Err(AuthError::Forbidden {
+            permission,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// A role as it's stored: its name, and the league it's in unless it's site-wide.
+fn role_row(role: &Role) -> (&'static str, Option<Uuid>) {
+    match role {
+        Role::LeagueOfficer {
+            league_id,
+        } => ("LeagueOfficer", Some(*league_id)),
+        Role::Scorer {
+            league_id,
+        } => ("Scorer", Some(*league_id)),
+        Role::Shooter {
+            league_id,
+        } => ("Shooter", Some(*league_id)),
+        Role::SiteAdmin => ("SiteAdmin", None),
+    }
+}
+
+fn role_from_row(name: &str, league_id: Option<Uuid>) -> Option<Role> {
+    let role = match (name, league_id) {
+        ("LeagueOfficer", Some(league_id)) => {
+            Role::LeagueOfficer {
+                league_id,
+            }
+        }
+        ("Scorer", Some(league_id)) => {
+            Role::Scorer {
+                league_id,
+            }
+        }
+        ("Shooter", Some(league_id)) => {
+            Role::Shooter {
+                league_id,
+            }
+        }
+        ("SiteAdmin", None) => Role::SiteAdmin,
+        _ => return None,
+    };
+
+    Some(role)
+}
+
+pub(crate) async fn fetch_roles(
+    txn: &mut SqliteConnection,
+    user_id: Uuid,
+) -> Result<Vec<Role>, AppError> {
+    let rows =
+        sqlx::query_file!("queries/auth/list_roles.sql", user_id).fetch_all(&mut *txn).await?;
+
+    Ok(rows.into_iter().filter_map(|row| role_from_row(&row.role, row.league_id)).collect())
+}
+
+pub(crate) async fn grant_role(
+    txn: &mut SqliteConnection,
+    user_id: Uuid,
+    role: &Role,
+) -> Result<(), AppError> {
+    let (name, league_id) = role_row(role);
+    sqlx::query_file!("queries/auth/grant_role.sql", user_id, name, league_id)
+        .execute(&mut *txn)
+        .await?;
+
+    Ok(())
+}
+
+/// Site admins whose accounts aren't disabled.
+pub(crate) async fn count_site_admins(txn: &mut SqliteConnection) -> Result<i64, AppError> {
+    Ok(sqlx::query_file_scalar!("queries/auth/count_site_admins.sql").fetch_one(&mut *txn).await?)
+}
+
+/// Grants or revokes a role, returning the roles the user has afterwards.
+pub async fn handle_role_operation(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+    Json(operation): Json<RoleOperation>,
+) -> Result<Json<Vec<Role>>, AppError> {
+    let user = &auth_session.current_user;
+    let session_id = auth_session.session.get_session_id();
+    info!("Handling role operation for {user:?} (Session: {session_id:?}): {operation:?}");
+
+    let (RoleOperation::Grant {
+        username,
+        role,
+    }
+    | RoleOperation::Revoke {
+        username,
+        role,
+    }) = &operation;
+    require_permission(&auth_session, &app_state.db_pool, Permission::to_grant(role)).await?;
+
+    let maybe_user = sqlx::query_file_as!(User, "queries/auth/get_user_by_username.sql", username)
+        .fetch_optional(&mut *txn)
+        .await?;
+    let Some(user) = maybe_user else {
+        return Err(AuthError::UsernameNotFound {
+            username: username.clone(),
+        }
+        .into());
+    };
+
+    match &operation {
+        RoleOperation::Grant {
+            ..
+        } => grant_role(&mut txn, user.id, role).await?,
+        RoleOperation::Revoke {
+            ..
+        } => {
+            let (name, league_id) = role_row(role);
+            sqlx::query_file!("queries/auth/revoke_role.sql", user.id, name, league_id)
+                .execute(&mut *txn)
+                .await?;
+            // Without a site admin, nobody could create leagues or grant roles again.
+            if *role == Role::SiteAdmin && count_site_admins(&mut txn).await? == 0 {
+                return Err(AuthError::LastSiteAdmin.into());
+            }
+        }
+    }
+
+    let roles = fetch_roles(&mut txn, user.id).await?;
+    txn.commit().await?;
+    Ok(Json(roles))
+}
@@ -1,14 +1,34 @@
+use std::{
+    net::{
+        IpAddr,
+        SocketAddr,
+    },
+    sync::LazyLock,
+};
+
 use anyhow::Context;
 use axum::{
     Json,
     Router,
+    extract::{
+        ConnectInfo,
+        State,
+    },
+    http::HeaderMap,
     routing::{
         get,
         post,
     },
 };
 use log::error;
-use shared_types::response::User;
+use shared_types::response::{
+    Role,
+    User,
+};
+use sqlx::{
+    SqliteConnection,
+    SqlitePool,
+};
 use uuid::Uuid;
 
 use crate::{
@@ -19,12 +39,24 @@ use crate::{
             AppAuthSession,
             AuthError,
             AuthenticatedUser,
+            RegistrationMode,
+            admin,
+            roles::{
+                count_site_admins,
+                fetch_roles,
+                grant_role,
+                handle_role_operation,
+            },
+            throttle::{
+                Subject,
+                clear_throttle,
+                client_address,
+                release_attempt,
+                reserve_attempt,
+            },
         },
     },
-    error::{
-        AppError,
-        HttpResponse,
-    },
+    error::AppError,
 };
 
 pub fn router(app_state: AppState) -> Router<AppState> {
@@ -33,25 +65,70 @@ pub fn router(app_state: AppState) -> Router<AppState> {
         .route("/login", post(login))
         .route("/logout", post(logout))
         .route("/register", post(register))
+        .route("/role/operation", post(handle_role_operation))
+        .nest("/admin", admin::router(app_state.clone()))
         .with_state(app_state)
 }
 
 pub async fn get_current_user(
+    DbTransaction(mut txn): DbTransaction<'_>,
     AuthenticatedUser(auth_session): AuthenticatedUser,
 ) -> Result<Json<User>, AppError> {
     let Some(user) = auth_session.current_user else {
-        return Err(AuthError::NotLoggedIn).context(HttpResponse::Unauthorized).map_err(Into::into);
+        return Err(AuthError::NotLoggedIn.into());
     };
 
-    Ok(Json(user.into()))
+    Ok(Json(with_roles(&mut txn, user).await?))
+}
+
+pub(crate) async fn with_roles(
+    txn: &mut SqliteConnection,
+    user: crate::app::auth::User,
+) -> Result<User, AppError> {
+    let roles = fetch_roles(txn, user.id).await?;
+
+    Ok(User {
+        roles,
+        ..user.into()
+    })
 }
 
 pub async fn login(
     DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     auth_session: AppAuthSession,
     Json(login_info): Json<shared_types::request::Login>,
 ) -> Result<Json<User>, AppError> {
+    let address = client_address(peer.ip(), &headers, &app_state.trusted_proxies);
+    let user = authenticate(&mut txn, &app_state.db_pool, address, &login_info).await?;
+    auth_session.login_user(user.id);
+    auth_session.remember_user(true);
+
+    let user = with_roles(&mut txn, user).await?;
+    txn.commit().await?;
+    Ok(Json(user))
+}
+
+/// A user nobody can log in as, with a password hashed the same way as everyone else's.
+static NOBODY: LazyLock<crate::app::auth::User> = LazyLock::new(|| {
+    crate::app::auth::User::new("", &Uuid::new_v4().to_string())
+        .expect("Failed to hash a password for unknown usernames")
+});
+
+/// Returns the user `login_info` names if the password is theirs. The attempt is counted against
+/// the username and `address` until it succeeds.
+async fn authenticate(
+    txn: &mut SqliteConnection,
+    db_pool: &SqlitePool,
+    address: IpAddr,
+    login_info: &shared_types::request::Login,
+) -> Result<crate::app::auth::User, AppError> {
     let username = &login_info.username;
+    let subjects = [Subject::Username(username), Subject::Address(address)];
+    reserve_attempt(db_pool, &subjects).await?;
+
     let maybe_user = sqlx::query_file_as!(
         crate::app::auth::User,
         "queries/auth/get_user_by_username.sql",
@@ -59,26 +136,27 @@ pub async fn login(
     )
     .fetch_optional(&mut *txn)
     .await?;
-
-    let Some(user) = maybe_user else {
-        return Err(AuthError::Unauthorized)
-            .context(HttpResponse::Unauthorized)
-            .map_err(Into::into);
-    };
-
-    if user
+    // An unknown username's checked against a password too, so that it takes as long to reject
+    // as a known one, and can't be told apart by how long it takes.
+    let verifying_user = maybe_user.as_ref().unwrap_or(&NOBODY);
+    let verified = verifying_user
         .verify_password(&login_info.password)
-        .context(AuthError::Unauthorized)
-        .context(HttpResponse::Unauthorized)?
-    {
-        auth_session.login_user(user.id);
-        auth_session.remember_user(true);
+        .context(AuthError::InvalidCredentials)?;
 
-        return Ok(Json(user.into()));
+    let Some(user) = maybe_user.filter(|_| verified) else {
+        error!("Invalid username or password for user: {}", username);
+        return Err(AuthError::InvalidCredentials.into());
+    };
+    if !user.active {
+        return Err(AuthError::AccountDisabled.into());
     }
-    error!("Invalid password for user: {}", username);
 
-    Err(AuthError::Unauthorized).context(HttpResponse::Unauthorized).map_err(Into::into)
+    // On the request's transaction, which has read the user, as a write through the pool would
+    // wait for it to finish.
+    clear_throttle(txn, &Subject::Username(username)).await?;
+    release_attempt(txn, &Subject::Address(address)).await?;
+
+    Ok(user)
 }
 
 pub async fn logout(
@@ -89,16 +167,16 @@ pub async fn logout(
 
         Ok(Json("Logout Successful".to_string()))
     } else {
-        Err(AuthError::Unauthorized).context(HttpResponse::Unauthorized).map_err(Into::into)
+        Err(AuthError::NotLoggedIn.into())
     }
 }
 
-pub async fn register(
-    DbTransaction(mut txn): DbTransaction<'_>,
-    auth_session: AppAuthSession,
-    Json(register_info): Json<shared_types::request::RegisterUser>,
-) -> Result<Json<User>, AppError> {
-    let username = register_info.username.clone();
+/// Makes an account, unless the username's already taken.
+pub(crate) async fn create_user(
+    txn: &mut SqliteConnection,
+    username: &str,
+    password: &str,
+) -> Result<crate::app::auth::User, AppError> {
     let maybe_user = sqlx::query_file_as!(
         crate::app::auth::User,
         "queries/auth/get_user_by_username.sql",
@@ -108,20 +186,72 @@ pub async fn register(
     .await?;
 
     if maybe_user.is_some() {
-        return Err(AuthError::Unauthorized)
-            .context(HttpResponse::Unauthorized)
-            .map_err(Into::into);
+        return Err(AuthError::UsernameTaken {
+            username: username.to_string(),
+        }
+        .into());
     }
 
-    let auth_user = crate::app::auth::User::new(&username, &register_info.password)?;
+    let auth_user = crate::app::auth::User::new(username, password)?;
     let username = &auth_user.username;
     let password_hash = &auth_user.password_hash;
 
     sqlx::query_file!("queries/auth/create_user.sql", auth_user.id, username, password_hash,)
         .execute(&mut *txn)
         .await?;
+
+    Ok(auth_user)
+}
+
+pub async fn register(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    auth_session: AppAuthSession,
+    Json(register_info): Json<shared_types::request::RegisterUser>,
+) -> Result<Json<User>, AppError> {
+    let address = client_address(peer.ip(), &headers, &app_state.trusted_proxies);
+    // Successful registrations are counted too, so that one address can't make endless accounts.
+    reserve_attempt(&app_state.db_pool, &[Subject::Address(address)]).await?;
+
+    let auth_user = register_user(&mut txn, app_state.registration_mode, &register_info).await?;
     auth_session.login_user(auth_user.id);
 
+    let user = with_roles(&mut txn, auth_user).await?;
     txn.commit().await?;
-    Ok(Json(auth_user.into()))
+    Ok(Json(user))
+}
+
+async fn register_user(
+    txn: &mut SqliteConnection,
+    registration_mode: RegistrationMode,
+    register_info: &shared_types::request::RegisterUser,
+) -> Result<crate::app::auth::User, AppError> {
+    // The first user runs the site, as there's nobody else yet to grant them a role, and may
+    // register however registration is set up.
+    let first_user = count_site_admins(txn).await? == 0;
+    if !first_user && registration_mode == RegistrationMode::AdminOnly {
+        return Err(AuthError::RegistrationClosed.into());
+    }
+
+    let auth_user = create_user(txn, &register_info.username, &register_info.password).await?;
+    if first_user {
+        grant_role(txn, auth_user.id, &Role::SiteAdmin).await?;
+    } else if registration_mode == RegistrationMode::InviteCode {
+        let Some(invite_code) = &register_info.invite_code else {
+            return Err(AuthError::InvalidInvite.into());
+        };
+        let claimed = sqlx::query_file!("queries/auth/claim_invite.sql", invite_code, auth_user.id)
+            .execute(&mut *txn)
+            .await?;
+        if claimed.rows_affected() == 0 {
+            return Err(AuthError::InvalidInvite.into());
+        }
+    }
+
+    Ok(auth_user)
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+use std::net::Ipv4Addr;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use shared_types::request::Login;
+
+use super::*;
+
+const ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+
+fn login_info(password: &str) -> Login {
+    Login {
+        username: "alex".to_string(),
+        password: password.to_string(),
+    }
+}
+
+async fn failures(pool: &SqlitePool, kind: &str, subject: &str) -> Option<i64> {
+    let maybe_throttle = sqlx::query_file!("queries/auth/get_login_throttle.sql", kind, subject)
+        .fetch_optional(pool)
+        .await
+        .unwrap();
+
+    maybe_throttle.map(|throttle| throttle.failures)
+}
+
+#[sqlx::test]
+async fn logging_in_stops_counting_the_attempt(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    create_user(&mut conn, "alex", "correct horse").await.unwrap();
+
+    let mut txn = pool.begin().await.unwrap();
+    let error =
+        authenticate(&mut txn, &pool, ADDRESS, &login_info("battery staple")).await.unwrap_err();
+    assert!(matches!(error.0.downcast_ref::<AuthError>(), Some(AuthError::InvalidCredentials)));
+    drop(txn);
+
+    let mut txn = pool.begin().await.unwrap();
+    let user = authenticate(&mut txn, &pool, ADDRESS, &login_info("correct horse")).await.unwrap();
+    txn.commit().await.unwrap();
+
+    assert_eq!(user.username, "alex");
+    assert_eq!(failures(&pool, "username", "alex").await, None);
+    // The failed attempt still counts against the address.
+    assert_eq!(failures(&pool, "address", "192.168.1.20").await, Some(1));
+}
+
+#[sqlx::test]
+async fn logging_in_as_an_unknown_user_is_an_invalid_login(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+
+    let error =
+        authenticate(&mut conn, &pool, ADDRESS, &login_info("correct horse")).await.unwrap_err();
+    assert!(matches!(error.0.downcast_ref::<AuthError>(), Some(AuthError::InvalidCredentials)));
+}
@@ -0,0 +1,234 @@
+use std::{
+    fmt,
+    net::IpAddr,
+};
+
+use axum::http::HeaderMap;
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use log::warn;
+use sqlx::{
+    SqliteConnection,
+    SqlitePool,
+};
+
+use crate::{
+    app::auth::AuthError,
+    error::AppError,
+};
+
+/// Failed attempts at a username before each further attempt has to wait.
+const USERNAME_FREE_ATTEMPTS: i64 = 3;
+/// Club members share the clubhouse Wi-Fi's address, so it's allowed more failures between them.
+const ADDRESS_FREE_ATTEMPTS: i64 = 20;
+/// The wait after the first failure past the free attempts, which doubles with each one after.
+const FIRST_LOCKOUT: TimeDelta = TimeDelta::seconds(5);
+const MAX_LOCKOUT: TimeDelta = TimeDelta::minutes(15);
+/// Failures are forgotten once there haven't been any for this long.
+const FAILURE_MEMORY: TimeDelta = TimeDelta::hours(1);
+
+/// Who failed attempts are counted against.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Subject<'a> {
+    Address(IpAddr),
+    Username(&'a str),
+}
+
+impl Subject<'_> {
+    /// The subject as it's stored: its kind, and the username or address.
+    fn row(&self) -> (&'static str, String) {
+        match self {
+            Subject::Address(address) => ("address", address.to_string()),
+            Subject::Username(username) => ("username", username.to_string()),
+        }
+    }
+
+    fn free_attempts(&self) -> i64 {
+        match self {
+            Subject::Address(_) => ADDRESS_FREE_ATTEMPTS,
+            Subject::Username(_) => USERNAME_FREE_ATTEMPTS,
+        }
+    }
+
+    /// How long attempts wait after `failures` failures in a row, if they have to at all.
+    fn lockout(&self, failures: i64) -> Option<TimeDelta> {
+        let doublings = failures - self.free_attempts();
+        if doublings < 0 {
+            return None;
+        }
+
+        // Past 8 doublings, the lockout's already longer than the longest there is.
+        let lockout = FIRST_LOCKOUT * 2_i32.pow(doublings.min(8) as u32);
+
+        Some(lockout.min(MAX_LOCKOUT))
+    }
+}
+
+impl fmt::Display for Subject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subject::Address(address) => write!(f, "address {address}"),
+            Subject::Username(username) => write!(f, "username {username}"),
+        }
+    }
+}
+
+/// The address a request came from, for counting attempts against. That's its peer's, unless
+/// the peer is one of `trusted_proxies`, when it's the last address in `X-Forwarded-For` that
+/// isn't a trusted proxy. A trusted proxy that doesn't say who it's forwarding for is counted
+/// against itself, so that attempts through it are still limited, if shared by all its clients.
+pub(crate) fn client_address(
+    peer: IpAddr,
+    headers: &HeaderMap,
+    trusted_proxies: &[IpAddr],
+) -> IpAddr {
+    let peer = peer.to_canonical();
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+
+    forwarded_for(headers, trusted_proxies).unwrap_or_else(|| {
+        warn!(
+            "Trusted proxy {peer} didn't say who it's forwarding for in X-Forwarded-For, so \
+             attempts through it are counted against it"
+        );
+        peer
+    })
+}
+
+/// The last address in `X-Forwarded-For` that isn't one of `trusted_proxies`, if it can be told.
+fn forwarded_for(headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    // Each proxy appends the address it got the request from, so only the addresses after the
+    // last untrusted one were added by trusted proxies.
+    let forwarded_for: Vec<_> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+    for address in forwarded_for.into_iter().rev() {
+        let address = address.parse::<IpAddr>().ok()?.to_canonical();
+        if !trusted_proxies.contains(&address) {
+            return Some(address);
+        }
+    }
+
+    None
+}
+
+/// Counts an attempt against each of `subjects` as failed until it's known not to be, rejecting it
+/// with [`AuthError::TooManyAttempts`] instead if any of them is locked out. It's counted apart
+/// from the request's transaction, so that attempts made in parallel are counted before any of
+/// them has its password checked, rather than all getting in while none has failed yet.
+pub(crate) async fn reserve_attempt(
+    db_pool: &SqlitePool,
+    subjects: &[Subject<'_>],
+) -> Result<(), AppError> {
+    // Taking the write lock before reading makes parallel attempts wait to be counted in turn.
+    let mut txn = db_pool.begin_with("BEGIN IMMEDIATE").await?;
+    check_throttle(&mut txn, subjects).await?;
+    for subject in subjects {
+        record_failure(&mut txn, subject).await?;
+    }
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Rejects the attempt if any of `subjects` is locked out, saying how long until they all aren't.
+async fn check_throttle(
+    txn: &mut SqliteConnection,
+    subjects: &[Subject<'_>],
+) -> Result<(), AppError> {
+    let now = Utc::now();
+    let mut locked_until: Option<DateTime<Utc>> = None;
+    for subject in subjects {
+        let (kind, name) = subject.row();
+        let maybe_throttle = sqlx::query_file!("queries/auth/get_login_throttle.sql", kind, name)
+            .fetch_optional(&mut *txn)
+            .await?;
+        let subject_locked_until =
+            maybe_throttle.and_then(|throttle| throttle.locked_until).filter(|until| *until > now);
+        locked_until = locked_until.max(subject_locked_until);
+    }
+
+    match locked_until {
+        Some(locked_until) => {
+            // Rounded up, so that retrying right when told to isn't still too early.
+            let retry_after = ((locked_until - now).num_milliseconds() as u64).div_ceil(1000);
+            Err(AuthError::TooManyAttempts {
+                retry_after,
+            }
+            .into())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Counts a failed attempt against `subject`, locking it out once it's had too many.
+async fn record_failure(txn: &mut SqliteConnection, subject: &Subject<'_>) -> Result<(), AppError> {
+    let now = Utc::now();
+    let (kind, name) = subject.row();
+    let maybe_throttle = sqlx::query_file!("queries/auth/get_login_throttle.sql", kind, name)
+        .fetch_optional(&mut *txn)
+        .await?;
+    let failures = match maybe_throttle {
+        Some(throttle) if now - throttle.last_failure_at < FAILURE_MEMORY => throttle.failures + 1,
+        _ => 1,
+    };
+
+    let lockout = subject.lockout(failures);
+    if let Some(lockout) = lockout {
+        warn!(
+            "Locking out {subject} for {} seconds after {failures} attempts",
+            lockout.num_seconds()
+        );
+    }
+    let locked_until = lockout.map(|lockout| now + lockout);
+    sqlx::query_file!(
+        "queries/auth/set_login_throttle.sql",
+        kind,
+        name,
+        failures,
+        now,
+        locked_until
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
+
+/// Forgets the failed attempts against `subject`, such as once its user logs in.
+pub(crate) async fn clear_throttle(
+    txn: &mut SqliteConnection,
+    subject: &Subject<'_>,
+) -> Result<(), AppError> {
+    let (kind, name) = subject.row();
+    sqlx::query_file!("queries/auth/clear_login_throttle.sql", kind, name)
+        .execute(&mut *txn)
+        .await?;
+
+    Ok(())
+}
+
+/// Stops counting a reserved attempt against `subject` once it's succeeded, while still counting
+/// the others, such as those from an address other users have failed from.
+pub(crate) async fn release_attempt(
+    txn: &mut SqliteConnection,
+    subject: &Subject<'_>,
+) -> Result<(), AppError> {
+    let (kind, name) = subject.row();
+    sqlx::query_file!("queries/auth/release_login_attempt.sql", kind, name)
+        .execute(&mut *txn)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,76 @@
+use std::net::{
+    IpAddr,
+    Ipv4Addr,
+};
+
+use axum::http::{
+    HeaderMap,
+    HeaderValue,
+};
+use sqlx::SqlitePool;
+
+use super::*;
+
+const PROXY: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+
+fn forwarded_for_headers(addresses: &[&str]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for &address in addresses {
+        headers.append("x-forwarded-for", HeaderValue::from_str(address).unwrap());
+    }
+
+    headers
+}
+
+#[test]
+fn client_address_of_a_direct_request_is_its_peer() {
+    // A client that isn't a trusted proxy can't say it's someone else.
+    let headers = forwarded_for_headers(&["10.0.0.1"]);
+    assert_eq!(client_address(CLIENT, &headers, &[PROXY]), CLIENT);
+}
+
+#[test]
+fn client_address_through_a_trusted_proxy() {
+    let headers = forwarded_for_headers(&["192.168.1.20"]);
+    assert_eq!(client_address(PROXY, &headers, &[PROXY]), CLIENT);
+
+    // Whatever the client put in the header itself comes before the address the proxy added.
+    let headers = forwarded_for_headers(&["10.0.0.1, 192.168.1.20"]);
+    assert_eq!(client_address(PROXY, &headers, &[PROXY]), CLIENT);
+
+    let headers = forwarded_for_headers(&["10.0.0.1", "192.168.1.20, 127.0.0.1"]);
+    assert_eq!(client_address(PROXY, &headers, &[PROXY]), CLIENT);
+}
+
+#[test]
+fn client_address_through_a_trusted_proxy_that_doesnt_say() {
+    // Attempts through the proxy are still limited, if all together.
+    assert_eq!(client_address(PROXY, &HeaderMap::new(), &[PROXY]), PROXY);
+    assert_eq!(client_address(PROXY, &forwarded_for_headers(&["unknown"]), &[PROXY]), PROXY);
+    assert_eq!(client_address(PROXY, &forwarded_for_headers(&["127.0.0.1"]), &[PROXY]), PROXY);
+}
+
+#[sqlx::test]
+async fn parallel_attempts_are_counted_before_any_is_checked(pool: SqlitePool) {
+    let attempts: Vec<_> = (0..10)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move { reserve_attempt(&pool, &[Subject::Username("alex")]).await })
+        })
+        .collect();
+
+    let mut reserved = 0;
+    for attempt in attempts {
+        match attempt.await.unwrap() {
+            Ok(()) => reserved += 1,
+            Err(error) => {
+                assert!(matches!(
+                    error.0.downcast_ref::<AuthError>(),
+                    Some(AuthError::TooManyAttempts { .. })
+                ))
+            }
+        }
+    }
+    assert_eq!(reserved, USERNAME_FREE_ATTEMPTS);
+}
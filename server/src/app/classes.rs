@@ -1,16 +1,21 @@
 use axum::{
     Json,
     Router,
-    extract::Path,
+    extract::{
+        Path,
+        State,
+    },
     routing::{
         get,
         post,
     },
 };
+use log::info;
 use shared_types::{
     request::ClassOperation,
     response::Class,
 };
+use sqlx::SqliteConnection;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -18,12 +23,19 @@ use crate::{
     app::{
         AppState,
         DbTransaction,
+        auth::{
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                require_permission,
+            },
+        },
     },
     error::AppError,
 };
 
 #[derive(Debug, Error)]
-pub enum MatchError {
+pub enum ClassError {
     #[error("Class not found for league ({league_id}): {class_id}")]
     NotFound {
         league_id: Uuid,
@@ -54,26 +66,47 @@ pub async fn get_class(
     DbTransaction(mut txn): DbTransaction<'_>,
     Path((league_id, class_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<Class>, AppError> {
+    Ok(Json(fetch_class(&mut txn, league_id, class_id).await?))
+}
+
+async fn fetch_class(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    class_id: Uuid,
+) -> Result<Class, AppError> {
     let result = sqlx::query_file_as!(Class, "queries/classes/get_class.sql", league_id, class_id)
         .fetch_optional(&mut *txn)
         .await?;
 
     let Some(class) = result else {
-        return Err(MatchError::NotFound {
+        return Err(ClassError::NotFound {
             league_id,
             class_id,
         }
         .into());
     };
 
-    Ok(Json(class))
+    Ok(class)
 }
 
 pub async fn handle_class_operation(
     DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
     Path(league_id): Path<Uuid>,
     Json(operation): Json<ClassOperation>,
 ) -> Result<Json<Class>, AppError> {
+    let user = &auth_session.current_user;
+    info!("Handling class operation for {user:?} in league {league_id}: {operation:?}");
+    require_permission(
+        &auth_session,
+        &app_state.db_pool,
+        Permission::ManageLeague {
+            league_id,
+        },
+    )
+    .await?;
+
     let class = match operation {
         ClassOperation::Create {
             name,
@@ -96,15 +129,55 @@ pub async fn handle_class_operation(
         }
         ClassOperation::Delete {
             id,
-        } => todo!(),
+        } => {
+            let class = fetch_class(&mut txn, league_id, id).await?;
+            sqlx::query_file!("queries/classes/delete_class.sql", league_id, id)
+                .execute(&mut *txn)
+                .await?;
+
+            class
+        }
         ClassOperation::SetDescription {
             id,
             description,
-        } => todo!(),
+        } => {
+            let updated = sqlx::query_file!(
+                "queries/classes/set_description.sql",
+                league_id,
+                id,
+                description
+            )
+            .execute(&mut *txn)
+            .await?
+            .rows_affected();
+            if updated == 0 {
+                return Err(ClassError::NotFound {
+                    league_id,
+                    class_id: id,
+                }
+                .into());
+            }
+
+            fetch_class(&mut txn, league_id, id).await?
+        }
         ClassOperation::SetName {
             id,
             name,
-        } => todo!(),
+        } => {
+            let updated = sqlx::query_file!("queries/classes/set_name.sql", league_id, id, name)
+                .execute(&mut *txn)
+                .await?
+                .rows_affected();
+            if updated == 0 {
+                return Err(ClassError::NotFound {
+                    league_id,
+                    class_id: id,
+                }
+                .into());
+            }
+
+            fetch_class(&mut txn, league_id, id).await?
+        }
     };
 
     txn.commit().await?;
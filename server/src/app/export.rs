@@ -4,6 +4,7 @@ use axum::{
     extract::{
         DefaultBodyLimit,
         Path,
+        State,
     },
     routing::{
         get,
@@ -11,11 +12,28 @@ use axum::{
     },
 };
 use chrono::Utc;
-use log::info;
+use chrono_tz::Tz;
+use log::{
+    info,
+    warn,
+};
 use shared_types::{
     request::SmCsvExportUpload,
-    response::ShotMarkerExport,
+    response::{
+        ExportWarning,
+        ShotMarkerExport,
+        SmCsvExportUploaded,
+    },
 };
+use shotmarker_csv_parser::{
+    parser::{
+        ParseMode,
+        ParseOptions,
+        reader::ShotMarkerExportReader,
+    },
+    validation::validate_shot_string,
+};
+use sqlx::SqliteConnection;
 use thiserror::Error;
 use tower_http::limit::RequestBodyLimitLayer;
 use uuid::Uuid;
@@ -24,6 +42,15 @@ use crate::{
     app::{
         AppState,
         DbTransaction,
+        auth::{
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                require_permission,
+            },
+        },
+        matches::fetch_match,
+        shooters::ShooterMatcher,
     },
     error::AppError,
 };
@@ -87,27 +114,74 @@ pub async fn get_export(
 
 pub async fn upload_export(
     DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
     Path((league_id, match_id)): Path<(Uuid, Uuid)>,
     Json(upload): Json<SmCsvExportUpload>,
-) -> Result<(), crate::error::AppError> {
+) -> Result<Json<SmCsvExportUploaded>, crate::error::AppError> {
+    require_permission(
+        &auth_session,
+        &app_state.db_pool,
+        Permission::ScoreLeague {
+            league_id,
+        },
+    )
+    .await?;
+
     let file_name = upload.filename;
-    let export_data = upload.content;
+    let uploaded = store_export(
+        &mut txn,
+        league_id,
+        match_id,
+        &file_name,
+        &upload.content,
+        app_state.time_zone,
+    )
+    .await?;
+
+    txn.commit().await?;
+    Ok(Json(uploaded))
+}
+
+/// Stores an export's strings and shots in the match, skipping what can't be parsed. Shots are
+/// also stored as UTC instants when the `time_zone` ShotMarker clocks are set to is known.
+pub(crate) async fn store_export(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    match_id: Uuid,
+    file_name: &str,
+    export_data: &str,
+    time_zone: Option<Tz>,
+) -> Result<SmCsvExportUploaded, AppError> {
+    // A scorer's permission is for the league in the path, so the match has to be in it too.
+    let match_object = fetch_match(txn, league_id, match_id).await?;
     info!(
         "Received export for league {league_id} and match {match_id}: {file_name} ({})",
         export_data.len()
     );
 
-    let (_rest, export) = shotmarker_csv_parser::parser::export_parser(&export_data)?;
+    // Read the strings one at a time, rather than building the whole export up front, so that full
+    // archives don't need to be held in memory twice.
+    let mut export = ShotMarkerExportReader::new(
+        export_data.as_bytes(),
+        ParseOptions {
+            mode: ParseMode::Lenient,
+        },
+    )?;
+    let header = export.header().clone();
+
+    let expected_shot_count = match_object.shots_per_string.map(|count| count as usize);
+    let shooter_matcher = ShooterMatcher::load(txn, league_id).await?;
 
     let export_id = Uuid::new_v4();
-    let string_count = i32::try_from(export.string_count)?;
+    let string_count = i32::try_from(header.string_count)?;
     sqlx::query_file!(
         "queries/export/create_export.sql",
         export_id,
         file_name,
-        export.generated_date,
+        header.generated_date,
         string_count,
-        export.string_date,
+        header.string_date,
         match_id,
     )
     .execute(&mut *txn)
@@ -126,12 +200,23 @@ pub async fn upload_export(
     .execute(&mut *txn)
     .await?;
 
-    for shot_string in &export.strings {
+    for shot_string in &mut export {
+        let shot_string = shot_string?;
         let shot_string_id = Uuid::new_v4();
-        let name = shot_string.name.clone();
-        let target = shot_string.target.clone();
-        let distance = shot_string.distance.clone();
+        // The export keeps any padding ShotMarker wrote around the name, which isn't wanted here.
+        let name = shot_string.name.trim();
+        let target = shot_string.target.to_string();
+        let distance = shot_string.distance.to_string();
+        let distance_yards = shot_string.distance.yards();
+        let distance_meters = shot_string.distance.meters();
+        let target_lane = shot_string.target.lane().map(i64::from);
         let score = serde_json::to_value(shot_string.score.clone())?;
+        let metrics = shot_string.metrics.map(serde_json::to_value).transpose()?;
+        let findings = validate_shot_string(&shot_string, expected_shot_count);
+        if !findings.is_empty() {
+            info!("Shot string \"{name}\" in {file_name} needs review: {findings:?}");
+        }
+        let validation_findings = serde_json::to_value(findings)?;
         sqlx::query_file!(
             "queries/shot_strings/create_shot_string.sql",
             shot_string_id,
@@ -140,22 +225,41 @@ pub async fn upload_export(
             target,
             distance,
             score,
+            metrics,
             export_id,
+            distance_yards,
+            distance_meters,
+            target_lane,
+            validation_findings,
         )
         .execute(&mut *txn)
         .await?;
+        shooter_matcher.attribute(txn, shot_string_id, name).await?;
 
         for shot in &shot_string.shots {
             let shot_id = Uuid::new_v4();
             let string_shot_id = shot.id.clone();
-            let tags = shot.tags.clone();
+            let tags = shot.tags.to_string();
             let serialized_score = serde_json::to_value(shot.score)?;
             let serialized_position = serde_json::to_value(shot.position)?;
             let serialized_velocity = serde_json::to_value(shot.velocity)?;
+            let serialized_extras = serde_json::to_value(&shot.extras)?;
+            let utc_date_time = time_zone.and_then(|time_zone| {
+                let utc_date_time = shot.utc_date_time(&time_zone);
+                if utc_date_time.is_none() {
+                    warn!(
+                        "Shot {} in {file_name} was fired at {}, which daylight saving skipped in {time_zone}",
+                        shot.id, shot.date_time
+                    );
+                }
+                utc_date_time
+            });
             sqlx::query_file!(
                 "queries/shots/create_shot.sql",
                 shot_id,
                 shot.time,
+                shot.date_time,
+                utc_date_time,
                 string_shot_id,
                 tags,
                 serialized_score,
@@ -164,13 +268,22 @@ pub async fn upload_export(
                 shot.yaw,
                 shot.pitch,
                 shot.quality,
+                serialized_extras,
                 shot_string_id,
+                export_id,
             )
             .execute(&mut *txn)
             .await?;
         }
     }
 
-    txn.commit().await?;
-    Ok(())
+    let warnings = export.warnings();
+    for warning in warnings {
+        warn!("Export {file_name} for match {match_id}: {warning}");
+    }
+
+    Ok(SmCsvExportUploaded {
+        export_id,
+        warnings: warnings.iter().map(ExportWarning::from).collect(),
+    })
 }
@@ -1,7 +1,10 @@
 use axum::{
     Json,
     Router,
-    extract::Path,
+    extract::{
+        Path,
+        State,
+    },
     routing::{
         get,
         post,
@@ -15,8 +18,13 @@ use chrono::{
 use log::info;
 use shared_types::{
     request::LeagueOperation,
-    response::League,
+    response::{
+        League,
+        ScoringConvention,
+        StandingsRules,
+    },
 };
+use sqlx::SqliteConnection;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -24,13 +32,25 @@ use crate::{
     app::{
         AppState,
         DbTransaction,
-        auth::AuthenticatedUser,
+        auth::{
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                require_permission,
+            },
+        },
     },
     error::AppError,
 };
 
 #[derive(Debug, Error)]
 pub enum LeagueError {
+    #[error("League {league_id} can't end ({end_date}) before it starts ({start_date})")]
+    InvalidDateRange {
+        league_id:  Uuid,
+        start_date: NaiveDate,
+        end_date:   NaiveDate,
+    },
     #[error("League not found: {league_id}")]
     NotFound {
         league_id: Uuid,
@@ -50,39 +70,76 @@ fn single_league_router(app_state: AppState) -> Router<AppState> {
         .route("/", get(get_league))
         .nest("/match", crate::app::matches::league_match_router(app_state.clone()))
         .nest("/class", crate::app::classes::router(app_state.clone()))
+        .nest("/shooter", crate::app::shooters::router(app_state.clone()))
+        .route("/standings", get(crate::app::standings::get_league_standings))
         .with_state(app_state)
 }
 
+#[derive(sqlx::FromRow)]
+struct SqlxLeague {
+    id:                 Uuid,
+    name:               String,
+    description:        Option<String>,
+    created_at:         DateTime<Utc>,
+    start_date:         Option<NaiveDate>,
+    end_date:           Option<NaiveDate>,
+    scoring_convention: sqlx::types::Json<ScoringConvention>,
+    standings_rules:    sqlx::types::Json<StandingsRules>,
+}
+
+impl From<SqlxLeague> for League {
+    fn from(value: SqlxLeague) -> Self {
+        League {
+            id:                 value.id,
+            name:               value.name,
+            description:        value.description,
+            created_at:         value.created_at,
+            start_date:         value.start_date,
+            end_date:           value.end_date,
+            scoring_convention: value.scoring_convention.0,
+            standings_rules:    value.standings_rules.0,
+        }
+    }
+}
+
 pub async fn list_leagues(
     DbTransaction(mut txn): DbTransaction<'_>,
 ) -> Result<Json<Vec<League>>, AppError> {
-    let leagues = sqlx::query_file_as!(League, "queries/leagues/list_leagues.sql")
+    let leagues = sqlx::query_file_as!(SqlxLeague, "queries/leagues/list_leagues.sql")
         .fetch_all(&mut *txn)
         .await?;
 
-    Ok(Json(leagues))
+    Ok(Json(leagues.into_iter().map(Into::into).collect()))
 }
 
 pub async fn get_league(
     DbTransaction(mut txn): DbTransaction<'_>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<League>, AppError> {
-    let league = sqlx::query_file_as!(League, "queries/leagues/get_league.sql", id)
+    Ok(Json(fetch_league(&mut txn, id).await?))
+}
+
+pub(crate) async fn fetch_league(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+) -> Result<League, AppError> {
+    let league = sqlx::query_file_as!(SqlxLeague, "queries/leagues/get_league.sql", league_id)
         .fetch_optional(&mut *txn)
         .await?;
 
     let Some(league) = league else {
         return Err(LeagueError::NotFound {
-            league_id: id,
+            league_id,
         }
         .into());
     };
 
-    Ok(Json(league))
+    Ok(league.into())
 }
 
 pub async fn handle_league_operation(
     DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
     AuthenticatedUser(auth_session): AuthenticatedUser,
     Json(operation): Json<LeagueOperation>,
 ) -> Result<Json<League>, AppError> {
@@ -90,6 +147,44 @@ pub async fn handle_league_operation(
     let session_id = auth_session.session.get_session_id();
     info!("Handling league operation for {user:?} (Session: {session_id:?}): {operation:?}");
 
+    let permission = match &operation {
+        LeagueOperation::Create {
+            ..
+        }
+        | LeagueOperation::Delete {
+            ..
+        } => Permission::ManageSite,
+        LeagueOperation::SetDescription {
+            id,
+            ..
+        }
+        | LeagueOperation::SetEndDate {
+            id,
+            ..
+        }
+        | LeagueOperation::SetName {
+            id,
+            ..
+        }
+        | LeagueOperation::SetScoringConvention {
+            id,
+            ..
+        }
+        | LeagueOperation::SetStandingsRules {
+            id,
+            ..
+        }
+        | LeagueOperation::SetStartDate {
+            id,
+            ..
+        } => {
+            Permission::ManageLeague {
+                league_id: *id,
+            }
+        }
+    };
+    require_permission(&auth_session, &app_state.db_pool, permission).await?;
+
     let result = match operation {
         LeagueOperation::Create {
             league_name,
@@ -98,16 +193,18 @@ pub async fn handle_league_operation(
             sqlx::query_file!("queries/leagues/create_league.sql", league_id, league_name)
                 .execute(&mut *txn)
                 .await?;
-            sqlx::query_file_as!(League, "queries/leagues/get_league.sql", league_id)
+            sqlx::query_file_as!(SqlxLeague, "queries/leagues/get_league.sql", league_id)
                 .fetch_one(&mut *txn)
                 .await?
+                .into()
         }
         LeagueOperation::Delete {
             id,
         } => {
-            let maybe_league = sqlx::query_file_as!(League, "queries/leagues/get_league.sql", id)
-                .fetch_optional(&mut *txn)
-                .await?;
+            let maybe_league =
+                sqlx::query_file_as!(SqlxLeague, "queries/leagues/get_league.sql", id)
+                    .fetch_optional(&mut *txn)
+                    .await?;
             let Some(league) = maybe_league else {
                 return Err(LeagueError::NotFound {
                     league_id: id,
@@ -116,16 +213,44 @@ pub async fn handle_league_operation(
             };
             sqlx::query_file!("queries/leagues/delete_league.sql", id).execute(&mut *txn).await?;
 
-            league
+            league.into()
         }
         LeagueOperation::SetDescription {
-            id: _id,
-            description: _description,
-        } => todo!(),
+            id,
+            description,
+        } => {
+            let updated = sqlx::query_file!("queries/leagues/set_description.sql", id, description)
+                .execute(&mut *txn)
+                .await?
+                .rows_affected();
+            if updated == 0 {
+                return Err(LeagueError::NotFound {
+                    league_id: id,
+                }
+                .into());
+            }
+
+            fetch_league(&mut txn, id).await?
+        }
         LeagueOperation::SetEndDate {
-            id: _id,
-            end_date: _end_date,
-        } => todo!(),
+            id,
+            end_date,
+        } => {
+            let league = fetch_league(&mut txn, id).await?;
+            check_date_range(id, league.start_date, end_date)?;
+            let updated = sqlx::query_file!("queries/leagues/set_end_date.sql", id, end_date)
+                .execute(&mut *txn)
+                .await?
+                .rows_affected();
+            if updated == 0 {
+                return Err(LeagueError::NotFound {
+                    league_id: id,
+                }
+                .into());
+            }
+
+            fetch_league(&mut txn, id).await?
+        }
         LeagueOperation::SetName {
             id,
             league_name,
@@ -133,9 +258,32 @@ pub async fn handle_league_operation(
             sqlx::query_file!("queries/leagues/set_name.sql", id, league_name)
                 .execute(&mut *txn)
                 .await?;
-            let maybe_league = sqlx::query_file_as!(League, "queries/leagues/get_league.sql", id)
-                .fetch_optional(&mut *txn)
+            let maybe_league =
+                sqlx::query_file_as!(SqlxLeague, "queries/leagues/get_league.sql", id)
+                    .fetch_optional(&mut *txn)
+                    .await?;
+
+            let Some(league) = maybe_league else {
+                return Err(LeagueError::NotFound {
+                    league_id: id,
+                }
+                .into());
+            };
+
+            league.into()
+        }
+        LeagueOperation::SetScoringConvention {
+            id,
+            scoring_convention,
+        } => {
+            let scoring_convention = serde_json::to_value(scoring_convention)?;
+            sqlx::query_file!("queries/leagues/set_scoring_convention.sql", id, scoring_convention)
+                .execute(&mut *txn)
                 .await?;
+            let maybe_league =
+                sqlx::query_file_as!(SqlxLeague, "queries/leagues/get_league.sql", id)
+                    .fetch_optional(&mut *txn)
+                    .await?;
 
             let Some(league) = maybe_league else {
                 return Err(LeagueError::NotFound {
@@ -144,14 +292,58 @@ pub async fn handle_league_operation(
                 .into());
             };
 
-            league
+            league.into()
+        }
+        LeagueOperation::SetStandingsRules {
+            id,
+            standings_rules,
+        } => {
+            let standings_rules = serde_json::to_value(standings_rules)?;
+            sqlx::query_file!("queries/leagues/set_standings_rules.sql", id, standings_rules)
+                .execute(&mut *txn)
+                .await?;
+
+            fetch_league(&mut txn, id).await?
         }
         LeagueOperation::SetStartDate {
-            id: _id,
-            start_date: _start_date,
-        } => todo!(),
+            id,
+            start_date,
+        } => {
+            let league = fetch_league(&mut txn, id).await?;
+            check_date_range(id, start_date, league.end_date)?;
+            let updated = sqlx::query_file!("queries/leagues/set_start_date.sql", id, start_date)
+                .execute(&mut *txn)
+                .await?
+                .rows_affected();
+            if updated == 0 {
+                return Err(LeagueError::NotFound {
+                    league_id: id,
+                }
+                .into());
+            }
+
+            fetch_league(&mut txn, id).await?
+        }
     };
 
     txn.commit().await?;
     Ok(Json(result))
 }
+
+/// Checks that a league's season doesn't end before it starts. Either end may be left open.
+fn check_date_range(
+    league_id: Uuid,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<(), LeagueError> {
+    match (start_date, end_date) {
+        (Some(start_date), Some(end_date)) if end_date < start_date => {
+            Err(LeagueError::InvalidDateRange {
+                league_id,
+                start_date,
+                end_date,
+            })
+        }
+        _ => Ok(()),
+    }
+}
@@ -1,17 +1,22 @@
 use axum::{
     Json,
     Router,
-    extract::Path,
+    extract::{
+        Path,
+        State,
+    },
     routing::{
         get,
         post,
     },
 };
 use chrono::NaiveDate;
+use log::info;
 use shared_types::{
     request::MatchOperation,
     response::Match,
 };
+use sqlx::SqliteConnection;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -19,6 +24,13 @@ use crate::{
     app::{
         AppState,
         DbTransaction,
+        auth::{
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                require_permission,
+            },
+        },
     },
     error::AppError,
 };
@@ -44,6 +56,7 @@ pub fn league_match_router(app_state: AppState) -> Router<AppState> {
 fn single_league_match_router(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", get(get_league_match))
+        .route("/results", get(crate::app::results::get_match_results))
         .nest("/export", crate::app::export::router(app_state.clone()))
         .nest("/string", crate::app::strings::router(app_state.clone()))
         .with_state(app_state)
@@ -64,6 +77,15 @@ async fn get_league_match(
     DbTransaction(mut txn): DbTransaction<'_>,
     Path((league_id, match_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<Match>, AppError> {
+    Ok(Json(fetch_match(&mut txn, league_id, match_id).await?))
+}
+
+/// The match, if it's in the league.
+pub(crate) async fn fetch_match(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    match_id: Uuid,
+) -> Result<Match, AppError> {
     let result = sqlx::query_file_as!(Match, "queries/matches/get_match.sql", league_id, match_id)
         .fetch_optional(&mut *txn)
         .await?;
@@ -74,13 +96,27 @@ async fn get_league_match(
         .into());
     };
 
-    Ok(Json(match_object))
+    Ok(match_object)
 }
+
 async fn handle_league_match_operation(
     DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
     Path(league_id): Path<Uuid>,
     Json(operation): Json<MatchOperation>,
 ) -> Result<Json<Match>, AppError> {
+    let user = &auth_session.current_user;
+    info!("Handling match operation for {user:?} in league {league_id}: {operation:?}");
+    require_permission(
+        &auth_session,
+        &app_state.db_pool,
+        Permission::ManageLeague {
+            league_id,
+        },
+    )
+    .await?;
+
     let result = match operation {
         MatchOperation::Create {
             name,
@@ -90,17 +126,12 @@ async fn handle_league_match_operation(
             sqlx::query_file!("queries/matches/create_match.sql", id, league_id, name, event_date)
                 .execute(&mut *txn)
                 .await?;
-            sqlx::query_file_as!(Match, "queries/matches/get_match.sql", league_id, id)
-                .fetch_one(&mut *txn)
-                .await?
+            fetch_match(&mut txn, league_id, id).await?
         }
         MatchOperation::Delete {
             id,
         } => {
-            let match_object =
-                sqlx::query_file_as!(Match, "queries/matches/get_match.sql", league_id, id)
-                    .fetch_one(&mut *txn)
-                    .await?;
+            let match_object = fetch_match(&mut txn, league_id, id).await?;
             sqlx::query_file!("queries/matches/delete_match.sql", id).execute(&mut *txn).await?;
             match_object
         }
@@ -108,23 +139,36 @@ async fn handle_league_match_operation(
             id,
             event_date,
         } => {
+            fetch_match(&mut txn, league_id, id).await?;
             sqlx::query_file!("queries/matches/set_date.sql", league_id, id, event_date)
                 .execute(&mut *txn)
                 .await?;
-            sqlx::query_file_as!(Match, "queries/matches/get_match.sql", league_id, id)
-                .fetch_one(&mut *txn)
-                .await?
+            fetch_match(&mut txn, league_id, id).await?
         }
         MatchOperation::SetName {
             id,
             name,
         } => {
+            fetch_match(&mut txn, league_id, id).await?;
             sqlx::query_file!("queries/matches/set_name.sql", league_id, id, name)
                 .execute(&mut *txn)
                 .await?;
-            sqlx::query_file_as!(Match, "queries/matches/get_match.sql", league_id, id)
-                .fetch_one(&mut *txn)
-                .await?
+            fetch_match(&mut txn, league_id, id).await?
+        }
+        MatchOperation::SetShotsPerString {
+            id,
+            shots_per_string,
+        } => {
+            fetch_match(&mut txn, league_id, id).await?;
+            sqlx::query_file!(
+                "queries/matches/set_shots_per_string.sql",
+                league_id,
+                id,
+                shots_per_string
+            )
+            .execute(&mut *txn)
+            .await?;
+            fetch_match(&mut txn, league_id, id).await?
         }
     };
 
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    extract::Path,
+};
+use shared_types::response::{
+    Class,
+    ClassResults,
+    MatchResults,
+    ResultEntry,
+    ScoringConvention,
+    Shooter,
+    ShotMarkerShot,
+    ShotMarkerShotString,
+};
+use shotmarker_csv_parser::results::{
+    Aggregate,
+    rank,
+};
+use sqlx::SqliteConnection;
+use uuid::Uuid;
+
+use crate::{
+    app::{
+        DbTransaction,
+        leagues::LeagueError,
+        matches::fetch_match,
+        shooters::fetch_shooters,
+        shots::list_shots,
+        strings::list_strings,
+    },
+    error::AppError,
+};
+
+/// A string, along with its shots in the order they were fired.
+type FiredString = (ShotMarkerShotString, Vec<ShotMarkerShot>);
+
+/// A shooter's strings in a class, and their total over them.
+struct Entry {
+    shooter_id: Uuid,
+    aggregate:  Aggregate,
+    string_ids: Vec<Uuid>,
+}
+
+pub async fn get_match_results(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    Path((league_id, match_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MatchResults>, AppError> {
+    fetch_match(&mut txn, league_id, match_id).await?;
+
+    let convention =
+        sqlx::query_file_scalar!("queries/leagues/get_scoring_convention.sql", league_id)
+            .fetch_optional(&mut *txn)
+            .await?;
+    let Some(sqlx::types::Json(convention)) = convention else {
+        return Err(LeagueError::NotFound {
+            league_id,
+        }
+        .into());
+    };
+
+    let mut classes = sqlx::query_file_as!(Class, "queries/classes/list_classes.sql", league_id)
+        .fetch_all(&mut *txn)
+        .await?;
+    classes.sort_by(|a, b| a.name.cmp(&b.name));
+    let shooters = fetch_shooters(&mut txn, league_id).await?;
+
+    let results =
+        match_results(&mut txn, league_id, match_id, convention, &classes, &shooters).await?;
+
+    Ok(Json(results))
+}
+
+/// Ranks a match's shooters within each of `classes`, which must be in name order, followed by
+/// those whose strings aren't in a class.
+pub(crate) async fn match_results(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    match_id: Uuid,
+    convention: ScoringConvention,
+    classes: &[Class],
+    shooters: &[Shooter],
+) -> Result<MatchResults, AppError> {
+    // Each shooter's strings, by class and then shooter.
+    let mut fired: BTreeMap<Option<Uuid>, BTreeMap<Uuid, Vec<FiredString>>> = BTreeMap::new();
+    let mut unattributed_strings = 0;
+    for shot_string in list_strings(&mut *txn, match_id).await? {
+        let Some(shooter_id) = shot_string.shooter_id else {
+            unattributed_strings += 1;
+            continue;
+        };
+        let shots = list_shots(&mut *txn, league_id, match_id, shot_string.id).await?;
+        fired
+            .entry(shot_string.class_id)
+            .or_default()
+            .entry(shooter_id)
+            .or_default()
+            .push((shot_string, shots));
+    }
+
+    let class_results = classes
+        .iter()
+        .cloned()
+        .map(Some)
+        .chain(std::iter::once(None))
+        .filter_map(|class| {
+            let class_id = class.as_ref().map(|class| class.id);
+            let shooter_strings = fired.remove(&class_id)?;
+            let entries = shooter_strings
+                .into_iter()
+                .map(|(shooter_id, strings)| tally(shooter_id, strings, convention))
+                .collect();
+            let entries = rank(entries, |entry: &Entry| &entry.aggregate)
+                .into_iter()
+                .map(|(place, entry)| {
+                    let shooter_name = shooters
+                        .iter()
+                        .find(|shooter| shooter.id == entry.shooter_id)
+                        .map(|shooter| shooter.name.clone())
+                        .unwrap_or_default();
+                    ResultEntry {
+                        place,
+                        shooter_id: entry.shooter_id,
+                        shooter_name,
+                        count_back: entry.aggregate.count_back(),
+                        score: entry.aggregate.score,
+                        string_ids: entry.string_ids,
+                    }
+                })
+                .collect();
+
+            Some(ClassResults {
+                class,
+                entries,
+            })
+        })
+        .collect();
+
+    Ok(MatchResults {
+        match_id,
+        classes: class_results,
+        unattributed_strings,
+    })
+}
+
+/// Totals a shooter's strings, counting back over their shots in the order they were fired.
+fn tally(shooter_id: Uuid, mut strings: Vec<FiredString>, convention: ScoringConvention) -> Entry {
+    strings.sort_by_key(|(shot_string, shots)| {
+        (shot_string.string_date, shots.first().map(|shot| shot.shot_date_time))
+    });
+
+    let mut aggregate = Aggregate::new(convention);
+    let mut string_ids = Vec::with_capacity(strings.len());
+    for (shot_string, shots) in &strings {
+        aggregate.add_string(&shot_string.score, shots);
+        string_ids.push(shot_string.id);
+    }
+
+    Entry {
+        shooter_id,
+        aggregate,
+        string_ids,
+    }
+}
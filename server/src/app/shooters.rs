@@ -0,0 +1,362 @@
+use axum::{
+    Json,
+    Router,
+    extract::{
+        Path,
+        State,
+    },
+    routing::{
+        get,
+        post,
+    },
+};
+use log::info;
+use shared_types::{
+    request::ShooterOperation,
+    response::{
+        Class,
+        Shooter,
+        ShooterProposal,
+    },
+};
+use shotmarker_csv_parser::matching::{
+    StringName,
+    match_shooter,
+    split_string_name,
+};
+use sqlx::SqliteConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    app::{
+        AppState,
+        DbTransaction,
+        auth::{
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                require_permission,
+            },
+        },
+    },
+    error::AppError,
+};
+
+#[derive(Debug, Error)]
+pub enum ShooterError {
+    #[error("Class not found for league ({league_id}): {class_id}")]
+    ClassNotFound {
+        league_id: Uuid,
+        class_id:  Uuid,
+    },
+    #[error("Shooter name can't be empty")]
+    EmptyName,
+    #[error("Shooter not found for league ({league_id}): {shooter_id}")]
+    NotFound {
+        league_id:  Uuid,
+        shooter_id: Uuid,
+    },
+}
+
+pub fn router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_shooters))
+        .route("/{shooter_id}", get(get_shooter))
+        .route("/operation", post(handle_shooter_operation))
+        .with_state(app_state)
+}
+
+#[derive(sqlx::FromRow)]
+struct SqlxShooter {
+    id:               Uuid,
+    name:             String,
+    default_class_id: Option<Uuid>,
+    league_id:        Uuid,
+    aliases:          sqlx::types::Json<Vec<String>>,
+}
+
+impl From<SqlxShooter> for Shooter {
+    fn from(value: SqlxShooter) -> Self {
+        Shooter {
+            id:               value.id,
+            name:             value.name,
+            default_class_id: value.default_class_id,
+            league_id:        value.league_id,
+            aliases:          value.aliases.0,
+        }
+    }
+}
+
+pub async fn list_shooters(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    Path(league_id): Path<Uuid>,
+) -> Result<Json<Vec<Shooter>>, AppError> {
+    let shooters = fetch_shooters(&mut txn, league_id).await?;
+
+    Ok(Json(shooters))
+}
+
+pub(crate) async fn fetch_shooters(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+) -> Result<Vec<Shooter>, AppError> {
+    let shooters =
+        sqlx::query_file_as!(SqlxShooter, "queries/shooters/list_shooters.sql", league_id)
+            .fetch_all(&mut *txn)
+            .await?;
+
+    Ok(shooters.into_iter().map(Into::into).collect())
+}
+
+pub async fn get_shooter(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    Path((league_id, shooter_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Shooter>, AppError> {
+    let shooter = fetch_shooter(&mut txn, league_id, shooter_id).await?;
+
+    Ok(Json(shooter))
+}
+
+pub(crate) async fn fetch_shooter(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    shooter_id: Uuid,
+) -> Result<Shooter, AppError> {
+    let shooter = sqlx::query_file_as!(
+        SqlxShooter,
+        "queries/shooters/get_shooter.sql",
+        league_id,
+        shooter_id
+    )
+    .fetch_optional(&mut *txn)
+    .await?;
+
+    let Some(shooter) = shooter else {
+        return Err(ShooterError::NotFound {
+            league_id,
+            shooter_id,
+        }
+        .into());
+    };
+
+    Ok(shooter.into())
+}
+
+/// Makes sure `class_id`, if there is one, is one of the league's classes.
+pub(crate) async fn check_class(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    class_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let Some(class_id) = class_id else {
+        return Ok(());
+    };
+
+    let class = sqlx::query_file_as!(Class, "queries/classes/get_class.sql", league_id, class_id)
+        .fetch_optional(&mut *txn)
+        .await?;
+    if class.is_none() {
+        return Err(ShooterError::ClassNotFound {
+            league_id,
+            class_id,
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Makes sure a shooter's name isn't blank, so their strings can be matched and shown by it.
+fn check_name(name: &str) -> Result<(), ShooterError> {
+    if name.trim().is_empty() {
+        return Err(ShooterError::EmptyName);
+    }
+
+    Ok(())
+}
+
+pub async fn handle_shooter_operation(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+    Path(league_id): Path<Uuid>,
+    Json(operation): Json<ShooterOperation>,
+) -> Result<Json<Shooter>, AppError> {
+    let user = &auth_session.current_user;
+    info!("Handling shooter operation for {user:?} in league {league_id}: {operation:?}");
+    require_permission(
+        &auth_session,
+        &app_state.db_pool,
+        Permission::ManageLeague {
+            league_id,
+        },
+    )
+    .await?;
+
+    let shooter = match operation {
+        ShooterOperation::AddAlias {
+            id,
+            alias,
+        } => {
+            fetch_shooter(&mut txn, league_id, id).await?;
+            sqlx::query_file!("queries/shooters/add_alias.sql", id, alias)
+                .execute(&mut *txn)
+                .await?;
+
+            fetch_shooter(&mut txn, league_id, id).await?
+        }
+        ShooterOperation::Create {
+            name,
+            default_class_id,
+        } => {
+            check_name(&name)?;
+            check_class(&mut txn, league_id, default_class_id).await?;
+            let id = Uuid::new_v4();
+            sqlx::query_file!(
+                "queries/shooters/create_shooter.sql",
+                id,
+                name,
+                default_class_id,
+                league_id,
+            )
+            .execute(&mut *txn)
+            .await?;
+
+            fetch_shooter(&mut txn, league_id, id).await?
+        }
+        ShooterOperation::Delete {
+            id,
+        } => {
+            let shooter = fetch_shooter(&mut txn, league_id, id).await?;
+            // Strings fired by the shooter stay, but are no longer attributed to anyone.
+            sqlx::query_file!("queries/shooters/delete_shooter.sql", league_id, id)
+                .execute(&mut *txn)
+                .await?;
+
+            shooter
+        }
+        ShooterOperation::RemoveAlias {
+            id,
+            alias,
+        } => {
+            fetch_shooter(&mut txn, league_id, id).await?;
+            sqlx::query_file!("queries/shooters/remove_alias.sql", id, alias)
+                .execute(&mut *txn)
+                .await?;
+
+            fetch_shooter(&mut txn, league_id, id).await?
+        }
+        ShooterOperation::SetDefaultClass {
+            id,
+            default_class_id,
+        } => {
+            check_class(&mut txn, league_id, default_class_id).await?;
+            sqlx::query_file!(
+                "queries/shooters/set_default_class.sql",
+                league_id,
+                id,
+                default_class_id
+            )
+            .execute(&mut *txn)
+            .await?;
+
+            fetch_shooter(&mut txn, league_id, id).await?
+        }
+        ShooterOperation::SetName {
+            id,
+            name,
+        } => {
+            check_name(&name)?;
+            sqlx::query_file!("queries/shooters/set_name.sql", league_id, id, name)
+                .execute(&mut *txn)
+                .await?;
+
+            fetch_shooter(&mut txn, league_id, id).await?
+        }
+    };
+
+    txn.commit().await?;
+    Ok(Json(shooter))
+}
+
+/// A league's shooters and classes, for attributing strings to shooters by their names.
+pub(crate) struct ShooterMatcher {
+    shooters: Vec<Shooter>,
+    classes:  Vec<Class>,
+}
+
+impl ShooterMatcher {
+    pub(crate) async fn load(
+        txn: &mut SqliteConnection,
+        league_id: Uuid,
+    ) -> Result<Self, AppError> {
+        let shooters = fetch_shooters(txn, league_id).await?;
+        let classes = sqlx::query_file_as!(Class, "queries/classes/list_classes.sql", league_id)
+            .fetch_all(&mut *txn)
+            .await?;
+
+        Ok(Self {
+            shooters,
+            classes,
+        })
+    }
+
+    /// Splits a string's name into the shooter's name and the class it names.
+    pub(crate) fn split_name(&self, string_name: &str) -> StringName {
+        let class_names: Vec<_> = self.classes.iter().map(|class| class.name.as_str()).collect();
+        split_string_name(string_name, &class_names)
+    }
+
+    /// Assigns the string to the shooter its name is for, when that's exactly the name or one of
+    /// the aliases of a single shooter. Otherwise the closest shooter, if any are close, is
+    /// proposed for review. The class is the one the name gives, or else the shooter's default.
+    pub(crate) async fn attribute(
+        &self,
+        txn: &mut SqliteConnection,
+        string_id: Uuid,
+        string_name: &str,
+    ) -> Result<(), AppError> {
+        let name = self.split_name(string_name);
+        let Some(found) = match_shooter(&name.shooter, &self.shooters) else {
+            sqlx::query_file!(
+                "queries/shot_strings/set_shooter_proposal.sql",
+                string_id,
+                None::<serde_json::Value>
+            )
+            .execute(&mut *txn)
+            .await?;
+            return Ok(());
+        };
+
+        let shooter_id = found.shooter.id;
+        let class_id =
+            name.class.map(|class| self.classes[class].id).or(found.shooter.default_class_id);
+        if found.exact {
+            info!("Assigning shot string \"{string_name}\" to {}", found.shooter.name);
+            sqlx::query_file!(
+                "queries/shot_strings/set_shooter.sql",
+                string_id,
+                shooter_id,
+                class_id
+            )
+            .execute(&mut *txn)
+            .await?;
+        } else {
+            info!(
+                "Proposing {} for shot string \"{string_name}\" ({:.2})",
+                found.shooter.name, found.confidence
+            );
+            let proposal = serde_json::to_value(ShooterProposal {
+                shooter_id,
+                class_id,
+                confidence: found.confidence,
+            })?;
+            sqlx::query_file!("queries/shot_strings/set_shooter_proposal.sql", string_id, proposal)
+                .execute(&mut *txn)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
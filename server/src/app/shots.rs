@@ -1,16 +1,29 @@
+use std::collections::BTreeMap;
+
 use axum::{
     Json,
     Router,
-    extract::Path,
+    extract::{
+        Path,
+        Query,
+    },
     routing::get,
 };
-use chrono::NaiveTime;
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    NaiveTime,
+    Utc,
+};
 use shared_types::response::{
+    GroupOptions,
+    GroupStatistics,
     ShotMarkerShot,
     ShotPosition,
     ShotScore,
     ShotVelocity,
 };
+use sqlx::SqliteConnection;
 use uuid::Uuid;
 
 use crate::{
@@ -22,38 +35,46 @@ use crate::{
 };
 
 pub fn router(app_state: AppState) -> Router<AppState> {
-    Router::new().route("/", get(index)).with_state(app_state)
+    Router::new().route("/", get(index)).route("/statistics", get(statistics)).with_state(app_state)
 }
 
 #[derive(sqlx::FromRow)]
 struct SqlxShotMarkerShot {
-    id:             Uuid,
-    shot_time:      NaiveTime,
-    shot_id:        String,
-    tags:           String,
-    score:          sqlx::types::Json<ShotScore>,
-    position:       sqlx::types::Json<ShotPosition>,
-    velocity:       sqlx::types::Json<ShotVelocity>,
-    yaw:            f64,
-    pitch:          f64,
-    quality:        Option<String>,
-    shot_string_id: Uuid,
+    id:                 Uuid,
+    shot_time:          NaiveTime,
+    shot_date_time:     NaiveDateTime,
+    shot_utc_date_time: Option<DateTime<Utc>>,
+    shot_id:            String,
+    tags:               String,
+    score:              sqlx::types::Json<ShotScore>,
+    position:           sqlx::types::Json<ShotPosition>,
+    velocity:           sqlx::types::Json<ShotVelocity>,
+    yaw:                f64,
+    pitch:              f64,
+    quality:            Option<String>,
+    extras:             Option<sqlx::types::Json<BTreeMap<String, String>>>,
+    shot_string_id:     Uuid,
+    export_id:          Uuid,
 }
 
 impl From<SqlxShotMarkerShot> for ShotMarkerShot {
     fn from(value: SqlxShotMarkerShot) -> Self {
         ShotMarkerShot {
-            id:             value.id,
-            shot_time:      value.shot_time,
-            shot_id:        value.shot_id,
-            tags:           value.tags,
-            score:          value.score.0,
-            position:       value.position.0,
-            velocity:       value.velocity.0,
-            yaw:            value.yaw,
-            pitch:          value.pitch,
-            quality:        value.quality,
-            shot_string_id: value.shot_string_id,
+            id:                 value.id,
+            shot_time:          value.shot_time,
+            shot_date_time:     value.shot_date_time,
+            shot_utc_date_time: value.shot_utc_date_time,
+            shot_id:            value.shot_id,
+            tags:               value.tags.into(),
+            score:              value.score.0,
+            position:           value.position.0,
+            velocity:           value.velocity.0,
+            yaw:                value.yaw,
+            pitch:              value.pitch,
+            quality:            value.quality,
+            extras:             value.extras.map(|extras| extras.0).unwrap_or_default(),
+            shot_string_id:     value.shot_string_id,
+            export_id:          value.export_id,
         }
     }
 }
@@ -62,6 +83,28 @@ async fn index(
     DbTransaction(mut txn): DbTransaction<'_>,
     Path((league_id, match_id, shot_string_id)): Path<(Uuid, Uuid, Uuid)>,
 ) -> Result<Json<Vec<ShotMarkerShot>>, AppError> {
+    let shots = list_shots(&mut txn, league_id, match_id, shot_string_id).await?;
+
+    Ok(Json(shots))
+}
+
+/// Group statistics for the string's shots, or `null` if `options` excludes all of them.
+async fn statistics(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    Path((league_id, match_id, shot_string_id)): Path<(Uuid, Uuid, Uuid)>,
+    Query(options): Query<GroupOptions>,
+) -> Result<Json<Option<GroupStatistics>>, AppError> {
+    let shots = list_shots(&mut txn, league_id, match_id, shot_string_id).await?;
+
+    Ok(Json(GroupStatistics::for_shots(&shots, options)))
+}
+
+pub(crate) async fn list_shots(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    match_id: Uuid,
+    shot_string_id: Uuid,
+) -> Result<Vec<ShotMarkerShot>, AppError> {
     let shots = sqlx::query_file_as!(
         SqlxShotMarkerShot,
         "queries/shots/list_shots_for_string.sql",
@@ -72,5 +115,5 @@ async fn index(
     .fetch_all(&mut *txn)
     .await?;
 
-    Ok(Json(shots.into_iter().map(Into::into).collect()))
+    Ok(shots.into_iter().map(Into::into).collect())
 }
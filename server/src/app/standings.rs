@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    Json,
+    extract::Path,
+};
+use chrono::NaiveDate;
+use shared_types::response::{
+    Class,
+    ClassStandings,
+    LeagueStandings,
+    Match,
+    MatchContribution,
+    StandingsEntry,
+};
+use shotmarker_csv_parser::standings::SeasonTotal;
+use uuid::Uuid;
+
+use crate::{
+    app::{
+        DbTransaction,
+        leagues::fetch_league,
+        results::match_results,
+        shooters::fetch_shooters,
+    },
+    error::AppError,
+};
+
+/// A shooter's name, and their results in the matches they shot.
+type ShooterMatches = (String, Vec<MatchContribution>);
+
+/// A shooter's matches in a class, and their season total over them.
+struct Entry {
+    shooter_id:   Uuid,
+    shooter_name: String,
+    matches:      Vec<MatchContribution>,
+    total:        SeasonTotal,
+}
+
+pub async fn get_league_standings(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    Path(league_id): Path<Uuid>,
+) -> Result<Json<LeagueStandings>, AppError> {
+    let league = fetch_league(&mut txn, league_id).await?;
+    let rules = league.standings_rules;
+    let convention = league.scoring_convention;
+
+    let matches = sqlx::query_file_as!(Match, "queries/matches/list_matches.sql", league_id)
+        .fetch_all(&mut *txn)
+        .await?;
+    let mut classes = sqlx::query_file_as!(Class, "queries/classes/list_classes.sql", league_id)
+        .fetch_all(&mut *txn)
+        .await?;
+    classes.sort_by(|a, b| a.name.cmp(&b.name));
+    let shooters = fetch_shooters(&mut txn, league_id).await?;
+
+    // Each shooter's name and matches, by class and then shooter. Matches outside the league's
+    // season don't count towards it.
+    let mut shot: BTreeMap<Option<Uuid>, BTreeMap<Uuid, ShooterMatches>> = BTreeMap::new();
+    let in_season = |event_date: NaiveDate| {
+        league.start_date.is_none_or(|start_date| start_date <= event_date)
+            && league.end_date.is_none_or(|end_date| event_date <= end_date)
+    };
+    for match_object in matches.into_iter().filter(|m| in_season(m.event_date)) {
+        let results =
+            match_results(&mut txn, league_id, match_object.id, convention, &classes, &shooters)
+                .await?;
+        for class_results in results.classes {
+            let class_id = class_results.class.as_ref().map(|class| class.id);
+            for entry in class_results.entries {
+                shot.entry(class_id)
+                    .or_default()
+                    .entry(entry.shooter_id)
+                    .or_insert_with(|| (entry.shooter_name, Vec::new()))
+                    .1
+                    .push(MatchContribution {
+                        match_id:   match_object.id,
+                        match_name: match_object.name.clone(),
+                        event_date: match_object.event_date,
+                        place:      entry.place,
+                        score:      entry.score,
+                        dropped:    false,
+                    });
+            }
+        }
+    }
+
+    let class_standings = classes
+        .into_iter()
+        .map(Some)
+        .chain(std::iter::once(None))
+        .filter_map(|class| {
+            let class_id = class.as_ref().map(|class| class.id);
+            let shooter_matches = shot.remove(&class_id)?;
+            let entries = shooter_matches
+                .into_iter()
+                .map(|(shooter_id, (shooter_name, mut matches))| {
+                    let scores: Vec<_> = matches.iter().map(|m| m.score.clone()).collect();
+                    let total = rules.total(&scores, convention);
+                    for (contribution, counted) in matches.iter_mut().zip(&total.counted) {
+                        contribution.dropped = !counted;
+                    }
+                    Entry {
+                        shooter_id,
+                        shooter_name,
+                        matches,
+                        total,
+                    }
+                })
+                .collect();
+            let entries = rules
+                .rank(entries, |entry: &Entry| &entry.total)
+                .into_iter()
+                .map(|(place, entry)| {
+                    StandingsEntry {
+                        place,
+                        shooter_id: entry.shooter_id,
+                        shooter_name: entry.shooter_name,
+                        score: entry.total.score,
+                        qualified: entry.total.qualified,
+                        matches: entry.matches,
+                    }
+                })
+                .collect();
+
+            Some(ClassStandings {
+                class,
+                entries,
+            })
+        })
+        .collect();
+
+    Ok(Json(LeagueStandings {
+        league_id,
+        rules,
+        classes: class_standings,
+    }))
+}
@@ -1,33 +1,101 @@
+use std::collections::HashSet;
+
 use axum::{
     Json,
     Router,
-    extract::Path,
+    extract::{
+        Path,
+        State,
+    },
     routing::{
         get,
         post,
     },
 };
 use chrono::NaiveDate;
-use shared_types::response::ShotMarkerShotString;
-use shotmarker_csv_parser::string::StringScore;
+use log::info;
+use shared_types::{
+    request::StringOperation,
+    response::{
+        ShooterProposal,
+        ShotMarkerShot,
+        ShotMarkerShotString,
+        ShotTag,
+        ShotTags,
+    },
+};
+use shotmarker_csv_parser::{
+    string::{
+        ShotMarkerStringMetrics,
+        StringScore,
+    },
+    validation::{
+        ValidationFinding,
+        computed_score,
+        validate_score,
+    },
+};
+use sqlx::SqliteConnection;
+use thiserror::Error;
 use uuid::Uuid;
 
 use crate::{
     app::{
         AppState,
         DbTransaction,
+        auth::{
+            AuthenticatedUser,
+            roles::{
+                Permission,
+                require_permission,
+            },
+        },
+        matches::fetch_match,
+        shooters::{
+            ShooterMatcher,
+            check_class,
+            fetch_shooter,
+        },
+        shots::list_shots,
     },
-    error::{
-        AppError,
-        HttpResponse,
-    },
+    error::AppError,
 };
 
+#[derive(Debug, Error)]
+pub enum StringError {
+    #[error("Can't merge shot string {string_id} with itself")]
+    MergeWithItself {
+        string_id: Uuid,
+    },
+    #[error("No shooter has been proposed for shot string {string_id}")]
+    NoShooterProposal {
+        string_id: Uuid,
+    },
+    #[error("Only {available} sighters to convert, not {requested}")]
+    NotEnoughSighters {
+        requested: usize,
+        available: usize,
+    },
+    #[error("Shot string not found: {string_id}")]
+    NotFound {
+        string_id: Uuid,
+    },
+    #[error("Shot not found: {shot_id}")]
+    ShotNotFound {
+        shot_id: Uuid,
+    },
+    #[error("Can't split a shot string at its first shot ({shot_id})")]
+    SplitAtFirstShot {
+        shot_id: Uuid,
+    },
+}
+
 pub fn router(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", get(index))
         .nest("/{string_id}", single_string_router(app_state.clone()))
         .route("/operation", post(handle_string_operation))
+        .route("/operations", post(handle_string_operations))
         .with_state(app_state)
 }
 
@@ -40,37 +108,55 @@ fn single_string_router(app_state: AppState) -> Router<AppState> {
 
 #[derive(sqlx::FromRow)]
 struct SqlxShotMarkerShotString {
-    id:          Uuid,
-    string_date: NaiveDate,
-    string_name: String,
-    target:      String,
-    distance:    String,
-    score:       sqlx::types::Json<StringScore>,
-    export_id:   Uuid,
-    shooter_id:  Option<Uuid>,
-    class_id:    Option<Uuid>,
+    id:                  Uuid,
+    string_date:         NaiveDate,
+    string_name:         String,
+    target:              String,
+    distance:            String,
+    score:               sqlx::types::Json<StringScore>,
+    metrics:             Option<sqlx::types::Json<ShotMarkerStringMetrics>>,
+    export_id:           Uuid,
+    shooter_id:          Option<Uuid>,
+    class_id:            Option<Uuid>,
+    validation_findings: Option<sqlx::types::Json<Vec<ValidationFinding>>>,
+    edited:              bool,
+    shooter_proposal:    Option<sqlx::types::Json<ShooterProposal>>,
 }
 
 impl From<SqlxShotMarkerShotString> for ShotMarkerShotString {
     fn from(value: SqlxShotMarkerShotString) -> Self {
         ShotMarkerShotString {
-            id:          value.id,
-            string_date: value.string_date,
-            string_name: value.string_name,
-            target:      value.target,
-            distance:    value.distance,
-            score:       value.score.0,
-            export_id:   value.export_id,
-            shooter_id:  value.shooter_id,
-            class_id:    value.class_id,
+            id:                  value.id,
+            string_date:         value.string_date,
+            string_name:         value.string_name,
+            target:              value.target.into(),
+            distance:            value.distance.into(),
+            score:               value.score.0,
+            metrics:             value.metrics.map(|metrics| metrics.0),
+            export_id:           value.export_id,
+            shooter_id:          value.shooter_id,
+            class_id:            value.class_id,
+            validation_findings: value.validation_findings.map(|findings| findings.0),
+            edited:              value.edited,
+            shooter_proposal:    value.shooter_proposal.map(|proposal| proposal.0),
         }
     }
 }
 
 async fn index(
     DbTransaction(mut txn): DbTransaction<'_>,
-    Path((_league_id, match_id)): Path<(Uuid, Uuid)>,
+    Path((league_id, match_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<Vec<ShotMarkerShotString>>, AppError> {
+    fetch_match(&mut txn, league_id, match_id).await?;
+    let strings = list_strings(&mut txn, match_id).await?;
+
+    Ok(Json(strings))
+}
+
+pub(crate) async fn list_strings(
+    txn: &mut SqliteConnection,
+    match_id: Uuid,
+) -> Result<Vec<ShotMarkerShotString>, AppError> {
     let strings = sqlx::query_file_as!(
         SqlxShotMarkerShotString,
         "queries/shot_strings/list_shot_strings_for_match.sql",
@@ -79,13 +165,24 @@ async fn index(
     .fetch_all(&mut *txn)
     .await?;
 
-    Ok(Json(strings.into_iter().map(Into::into).collect()))
+    Ok(strings.into_iter().map(Into::into).collect())
 }
 
 async fn get_string(
     DbTransaction(mut txn): DbTransaction<'_>,
-    Path((_league_id, match_id, string_id)): Path<(Uuid, Uuid, Uuid)>,
+    Path((league_id, match_id, string_id)): Path<(Uuid, Uuid, Uuid)>,
 ) -> Result<Json<ShotMarkerShotString>, AppError> {
+    fetch_match(&mut txn, league_id, match_id).await?;
+    let shot_string = fetch_string(&mut txn, match_id, string_id).await?;
+
+    Ok(Json(shot_string))
+}
+
+async fn fetch_string(
+    txn: &mut SqliteConnection,
+    match_id: Uuid,
+    string_id: Uuid,
+) -> Result<ShotMarkerShotString, AppError> {
     let string = sqlx::query_file_as!(
         SqlxShotMarkerShotString,
         "queries/shot_strings/get_shot_string.sql",
@@ -96,16 +193,392 @@ async fn get_string(
     .await?;
 
     let Some(shot_string) = string else {
-        return Err(HttpResponse::NotFound {
-            message: "Shot string not found.".into(),
+        return Err(StringError::NotFound {
+            string_id,
         }
         .into());
     };
 
-    Ok(Json(shot_string.into()))
+    Ok(shot_string.into())
+}
+
+/// Applies `operation`, returning the strings it changed: the edited string, followed by the new
+/// string when one is split off.
+async fn handle_string_operation(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+    Path((league_id, match_id)): Path<(Uuid, Uuid)>,
+    Json(operation): Json<StringOperation>,
+) -> Result<Json<Vec<ShotMarkerShotString>>, AppError> {
+    let user = &auth_session.current_user;
+    info!("Handling string operation for {user:?} in match {match_id}: {operation:?}");
+    require_permission(
+        &auth_session,
+        &app_state.db_pool,
+        Permission::ScoreLeague {
+            league_id,
+        },
+    )
+    .await?;
+
+    let changed_string_ids =
+        apply_string_operation(&mut txn, league_id, match_id, operation).await?;
+    let mut changed_strings = Vec::new();
+    for string_id in changed_string_ids {
+        changed_strings.push(fetch_string(&mut txn, match_id, string_id).await?);
+    }
+
+    txn.commit().await?;
+    Ok(Json(changed_strings))
 }
 
-async fn handle_string_operation() -> Result<Json<ShotMarkerShotString>, AppError> {
-    //
-    todo!()
+/// Applies `operations` in order, such as assigning every string in a match to its shooter, all or
+/// none of them taking effect. Returns the strings they changed that are still in the match.
+async fn handle_string_operations(
+    DbTransaction(mut txn): DbTransaction<'_>,
+    State(app_state): State<AppState>,
+    AuthenticatedUser(auth_session): AuthenticatedUser,
+    Path((league_id, match_id)): Path<(Uuid, Uuid)>,
+    Json(operations): Json<Vec<StringOperation>>,
+) -> Result<Json<Vec<ShotMarkerShotString>>, AppError> {
+    let user = &auth_session.current_user;
+    info!(
+        "Handling {} string operations for {user:?} in match {match_id}: {operations:?}",
+        operations.len()
+    );
+    require_permission(
+        &auth_session,
+        &app_state.db_pool,
+        Permission::ScoreLeague {
+            league_id,
+        },
+    )
+    .await?;
+
+    let mut changed_string_ids = HashSet::new();
+    for operation in operations {
+        changed_string_ids
+            .extend(apply_string_operation(&mut txn, league_id, match_id, operation).await?);
+    }
+
+    // Strings merged into others along the way are gone, so only those still in the match are
+    // returned.
+    let changed_strings = list_strings(&mut txn, match_id)
+        .await?
+        .into_iter()
+        .filter(|shot_string| changed_string_ids.contains(&shot_string.id))
+        .collect();
+
+    txn.commit().await?;
+    Ok(Json(changed_strings))
 }
+
+/// Applies `operation` to a string in the match, returning the ids of the strings it changed.
+async fn apply_string_operation(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    match_id: Uuid,
+    operation: StringOperation,
+) -> Result<Vec<Uuid>, AppError> {
+    let string_id = operation.id();
+    // A scorer's permission is for the league in the path, so the match has to be in it too, and
+    // the string in the match, before touching its shots.
+    let match_object = fetch_match(txn, league_id, match_id).await?;
+    let shot_string = fetch_string(txn, match_id, string_id).await?;
+    let shots = list_shots(txn, league_id, match_id, string_id).await?;
+
+    let mut changed_string_ids = vec![string_id];
+    // An edited string is scored from its shots, while a reverted one goes back to the score it
+    // was uploaded with.
+    let mut rescore = true;
+    match operation {
+        StringOperation::AcceptShooterProposal {
+            id,
+        } => {
+            let Some(proposal) = shot_string.shooter_proposal else {
+                return Err(StringError::NoShooterProposal {
+                    string_id: id,
+                }
+                .into());
+            };
+            // The shooter or class may have gone since the proposal was made.
+            fetch_shooter(txn, league_id, proposal.shooter_id).await?;
+            check_class(txn, league_id, proposal.class_id).await?;
+            sqlx::query_file!(
+                "queries/shot_strings/set_shooter.sql",
+                id,
+                proposal.shooter_id,
+                proposal.class_id
+            )
+            .execute(&mut *txn)
+            .await?;
+
+            // Next time the shooter's name is spelled this way, the string is assigned to them
+            // without needing review.
+            let matcher = ShooterMatcher::load(txn, league_id).await?;
+            let alias = matcher.split_name(&shot_string.string_name).shooter;
+            sqlx::query_file!("queries/shooters/add_alias.sql", proposal.shooter_id, alias)
+                .execute(&mut *txn)
+                .await?;
+
+            return Ok(changed_string_ids);
+        }
+        StringOperation::AssignShooter {
+            id,
+            shooter_id,
+            class_id,
+        } => {
+            let shooter = fetch_shooter(txn, league_id, shooter_id).await?;
+            let class_id = class_id.or(shooter.default_class_id);
+            check_class(txn, league_id, class_id).await?;
+            sqlx::query_file!("queries/shot_strings/set_shooter.sql", id, shooter_id, class_id)
+                .execute(&mut *txn)
+                .await?;
+
+            // Who fired a string doesn't change its score.
+            return Ok(changed_string_ids);
+        }
+        StringOperation::ConvertSighters {
+            id: _,
+            count,
+        } => {
+            let sighters: Vec<_> = shots.iter().filter(|shot| shot.tags.is_sighter()).collect();
+            let Some(first) = sighters.len().checked_sub(count) else {
+                return Err(StringError::NotEnoughSighters {
+                    requested: count,
+                    available: sighters.len(),
+                }
+                .into());
+            };
+            if count == 0 {
+                // Nothing's converted, so the string isn't edited.
+                return Ok(changed_string_ids);
+            }
+            for shot in &sighters[first..] {
+                set_tags(txn, shot, |tags| tags.remove(&ShotTag::Sighter)).await?;
+            }
+        }
+        StringOperation::DropShot {
+            id: _,
+            shot_id,
+        } => {
+            let shot = find_shot(&shots, shot_id)?;
+            set_tags(txn, shot, |tags| tags.insert(ShotTag::Hidden)).await?;
+        }
+        StringOperation::MergeStrings {
+            id,
+            other_id,
+        } => {
+            if other_id == id {
+                return Err(StringError::MergeWithItself {
+                    string_id: id,
+                }
+                .into());
+            }
+            fetch_string(txn, match_id, other_id).await?;
+
+            sqlx::query_file!("queries/shots/move_shots_for_string.sql", other_id, id)
+                .execute(&mut *txn)
+                .await?;
+            sqlx::query_file!("queries/shot_strings/delete_shot_string.sql", other_id)
+                .execute(&mut *txn)
+                .await?;
+            // ShotMarker's averages were for the string as it was fired, not the merged string.
+            sqlx::query_file!("queries/shot_strings/clear_metrics.sql", id)
+                .execute(&mut *txn)
+                .await?;
+        }
+        StringOperation::ProposeShooter {
+            id,
+        } => {
+            if shot_string.shooter_id.is_none() {
+                let matcher = ShooterMatcher::load(txn, league_id).await?;
+                matcher.attribute(txn, id, &shot_string.string_name).await?;
+            }
+
+            return Ok(changed_string_ids);
+        }
+        StringOperation::RevertEdits {
+            id,
+        } => {
+            sqlx::query_file!("queries/shots/revert_tags_for_string.sql", id)
+                .execute(&mut *txn)
+                .await?;
+            sqlx::query_file!("queries/shot_strings/revert_score.sql", id)
+                .execute(&mut *txn)
+                .await?;
+            rescore = false;
+        }
+        StringOperation::RevertShot {
+            id,
+            shot_id,
+        } => {
+            find_shot(&shots, shot_id)?;
+            sqlx::query_file!("queries/shots/revert_tags.sql", id, shot_id)
+                .execute(&mut *txn)
+                .await?;
+
+            let edited_shots =
+                sqlx::query_file_scalar!("queries/shots/count_edited_shots_for_string.sql", id)
+                    .fetch_one(&mut *txn)
+                    .await?;
+            if edited_shots == 0 {
+                sqlx::query_file!("queries/shot_strings/revert_score.sql", id)
+                    .execute(&mut *txn)
+                    .await?;
+                rescore = false;
+            }
+        }
+        StringOperation::SetClass {
+            id,
+            class_id,
+        } => {
+            check_class(txn, league_id, class_id).await?;
+            sqlx::query_file!("queries/shot_strings/set_class.sql", id, class_id)
+                .execute(&mut *txn)
+                .await?;
+
+            return Ok(changed_string_ids);
+        }
+        StringOperation::SplitString {
+            id,
+            at_shot_id,
+        } => {
+            let Some(split_at) = shots.iter().position(|shot| shot.id == at_shot_id) else {
+                return Err(StringError::ShotNotFound {
+                    shot_id: at_shot_id,
+                }
+                .into());
+            };
+            if split_at == 0 {
+                return Err(StringError::SplitAtFirstShot {
+                    shot_id: at_shot_id,
+                }
+                .into());
+            }
+
+            let new_string_id = Uuid::new_v4();
+            sqlx::query_file!("queries/shot_strings/copy_shot_string.sql", id, new_string_id)
+                .execute(&mut *txn)
+                .await?;
+            for shot in &shots[split_at..] {
+                sqlx::query_file!("queries/shots/move_shot.sql", shot.id, new_string_id)
+                    .execute(&mut *txn)
+                    .await?;
+            }
+            sqlx::query_file!("queries/shot_strings/clear_metrics.sql", id)
+                .execute(&mut *txn)
+                .await?;
+            changed_string_ids.push(new_string_id);
+        }
+        StringOperation::UnassignShooter {
+            id,
+        } => {
+            sqlx::query_file!(
+                "queries/shot_strings/set_shooter.sql",
+                id,
+                None::<Uuid>,
+                None::<Uuid>
+            )
+            .execute(&mut *txn)
+            .await?;
+
+            return Ok(changed_string_ids);
+        }
+        StringOperation::UnhideShot {
+            id: _,
+            shot_id,
+        } => {
+            let shot = find_shot(&shots, shot_id)?;
+            set_tags(txn, shot, |tags| tags.remove(&ShotTag::Hidden)).await?;
+        }
+    }
+
+    // Merged and split strings no longer hold the shots their uploaded score was for.
+    let reshaped = matches!(
+        operation,
+        StringOperation::MergeStrings { .. } | StringOperation::SplitString { .. }
+    );
+    let expected_shot_count = match_object.shots_per_string.map(|count| count as usize);
+
+    for &string_id in &changed_string_ids {
+        let mut shot_string = fetch_string(txn, match_id, string_id).await?;
+        let shots = list_shots(txn, league_id, match_id, string_id).await?;
+        if rescore {
+            shot_string.score = computed_score(&shots, &shot_string.score);
+            let score = serde_json::to_value(&shot_string.score)?;
+            sqlx::query_file!("queries/shot_strings/set_score.sql", string_id, score)
+                .execute(&mut *txn)
+                .await?;
+        }
+        if reshaped {
+            // Reverting goes back to the score of these shots as they were uploaded.
+            let original_score = uploaded_score(txn, &shots, &shot_string.score).await?;
+            let original_score = serde_json::to_value(original_score)?;
+            sqlx::query_file!(
+                "queries/shot_strings/set_original_score.sql",
+                string_id,
+                original_score
+            )
+            .execute(&mut *txn)
+            .await?;
+        }
+
+        // The findings are for the string as it now stands.
+        let findings = validate_score(&shot_string.score, &shots, expected_shot_count);
+        let validation_findings = serde_json::to_value(findings)?;
+        sqlx::query_file!(
+            "queries/shot_strings/set_validation_findings.sql",
+            string_id,
+            validation_findings
+        )
+        .execute(&mut *txn)
+        .await?;
+    }
+
+    Ok(changed_string_ids)
+}
+
+fn find_shot(shots: &[ShotMarkerShot], shot_id: Uuid) -> Result<&ShotMarkerShot, StringError> {
+    shots.iter().find(|shot| shot.id == shot_id).ok_or(StringError::ShotNotFound {
+        shot_id,
+    })
+}
+
+/// Updates a shot's tags with `edit`, keeping the uploaded tags the first time the shot is edited.
+async fn set_tags(
+    txn: &mut SqliteConnection,
+    shot: &ShotMarkerShot,
+    edit: impl FnOnce(&mut ShotTags),
+) -> Result<(), AppError> {
+    let mut tags = shot.tags.clone();
+    edit(&mut tags);
+    let tags = tags.to_string();
+    sqlx::query_file!("queries/shots/set_tags.sql", shot.shot_string_id, shot.id, tags)
+        .execute(&mut *txn)
+        .await?;
+
+    Ok(())
+}
+
+/// The score `shots` would have with the tags they were uploaded with.
+async fn uploaded_score(
+    txn: &mut SqliteConnection,
+    shots: &[ShotMarkerShot],
+    recorded: &StringScore,
+) -> Result<StringScore, AppError> {
+    let mut uploaded_shots = shots.to_vec();
+    for shot in &mut uploaded_shots {
+        let original_tags =
+            sqlx::query_file_scalar!("queries/shots/get_original_tags.sql", shot.id)
+                .fetch_one(&mut *txn)
+                .await?;
+        shot.tags = original_tags.into();
+    }
+
+    Ok(computed_score(&uploaded_shots, recorded))
+}
+
+#[cfg(test)]
+mod tests;
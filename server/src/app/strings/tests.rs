@@ -0,0 +1,317 @@
+use sqlx::SqlitePool;
+
+use super::*;
+use crate::app::{
+    export::store_export,
+    matches::MatchError,
+};
+
+/// Two strings fired on one target: a sighter and three shots, then two shots.
+const EXPORT: &str = "ShotMarker Archived Data (generated Jan 31 2025)
+Exported 2 strings from Jan 31 of 2 total in archive
+
+Jan 31 2025,Alex Thompson,1,600y,29-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,6:57:00 pm,S1,sighter,10,40,10,1.57,0.39,0.25,0.06,0.07,0.02,611.1,2005,-0.6,-3.2,,
+,6:58:00 pm,1,,10,72,10,2.83,0.39,0.45,0.06,0.13,0.02,611.1,2005,-0.6,-3.2,,
+,6:59:00 pm,2,,X,22,20,0.87,0.79,0.14,0.13,0.04,0.04,620.8,2037,-0.5,-3.2,,
+,7:00:00 pm,3,,9,150,120,5.91,4.72,0.94,0.75,0.27,0.22,615.0,2018,-0.5,-3.2,,
+
+Jan 31 2025,Ethan Blackwood,1,600y,19-1X
+
+,time,id,tags,score,x (mm),y (mm),x (inch),y (inch),x (moa),y (moa),x (mil),y (mil),v (m/s),v (fps),yaw (deg), pitch (deg),quality
+,7:10:00 pm,1,,X,10,10,0.39,0.39,0.06,0.06,0.02,0.02,611.1,2005,-0.6,-3.2,,
+,7:11:00 pm,2,,9,150,140,5.91,5.51,0.94,0.88,0.27,0.25,620.8,2037,-0.5,-3.2,,
+";
+
+async fn create_league(txn: &mut SqliteConnection, league_name: &str) -> Uuid {
+    let league_id = Uuid::new_v4();
+    sqlx::query_file!("queries/leagues/create_league.sql", league_id, league_name)
+        .execute(&mut *txn)
+        .await
+        .unwrap();
+
+    league_id
+}
+
+async fn create_match(txn: &mut SqliteConnection, league_id: Uuid) -> Uuid {
+    let match_id = Uuid::new_v4();
+    let event_date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    sqlx::query_file!(
+        "queries/matches/create_match.sql",
+        match_id,
+        league_id,
+        "Week 1",
+        event_date
+    )
+    .execute(&mut *txn)
+    .await
+    .unwrap();
+
+    match_id
+}
+
+/// A league with a match, holding the strings from [`EXPORT`].
+async fn uploaded_match(txn: &mut SqliteConnection) -> (Uuid, Uuid) {
+    let league_id = create_league(txn, "Tuesday F-Class").await;
+    let match_id = create_match(txn, league_id).await;
+    store_export(txn, league_id, match_id, "SM_shotslog_Jan_31.csv", EXPORT, None).await.unwrap();
+
+    (league_id, match_id)
+}
+
+/// The match's strings, by name.
+async fn strings_by_name(
+    txn: &mut SqliteConnection,
+    match_id: Uuid,
+) -> Vec<(String, ShotMarkerShotString)> {
+    let mut strings: Vec<_> = list_strings(txn, match_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|shot_string| (shot_string.string_name.clone(), shot_string))
+        .collect();
+    strings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    strings
+}
+
+fn assert_match_not_found<T: std::fmt::Debug>(result: Result<T, AppError>) {
+    let error = result.unwrap_err();
+    assert!(
+        matches!(error.0.downcast_ref::<MatchError>(), Some(MatchError::NotFound { .. })),
+        "Expected the match not to be found, got {:?}",
+        error.0
+    );
+}
+
+#[sqlx::test]
+async fn scorer_of_one_league_cannot_change_another_leagues_match(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let (_league_b, match_b) = uploaded_match(&mut conn).await;
+    // A scorer in league A is permitted to change league A, so league A is in the path.
+    let league_a = create_league(&mut conn, "Thursday TR").await;
+
+    assert_match_not_found(
+        store_export(&mut conn, league_a, match_b, "SM_shotslog_Jan_31.csv", EXPORT, None).await,
+    );
+
+    let strings = strings_by_name(&mut conn, match_b).await;
+    let [(_, first), (_, second)] = &strings[..] else {
+        panic!("Expected two strings, found {}", strings.len());
+    };
+    let operations = [
+        StringOperation::RevertEdits {
+            id: first.id,
+        },
+        StringOperation::MergeStrings {
+            id:       first.id,
+            other_id: second.id,
+        },
+        StringOperation::ConvertSighters {
+            id:    first.id,
+            count: 1,
+        },
+    ];
+    for operation in operations {
+        assert_match_not_found(
+            apply_string_operation(&mut conn, league_a, match_b, operation).await,
+        );
+    }
+
+    assert_eq!(strings_by_name(&mut conn, match_b).await, strings);
+}
+
+/// The string's shot with the id ShotMarker gave it.
+async fn find_uploaded_shot(
+    txn: &mut SqliteConnection,
+    league_id: Uuid,
+    match_id: Uuid,
+    string_id: Uuid,
+    shot_id: &str,
+) -> ShotMarkerShot {
+    let shots = list_shots(txn, league_id, match_id, string_id).await.unwrap();
+
+    shots.into_iter().find(|shot| shot.shot_id == shot_id).unwrap()
+}
+
+fn assert_reverted(shot_string: &ShotMarkerShotString, score: &str) {
+    assert_eq!(shot_string.score.to_string(), score);
+    // Scored from its shots, the string's score matches them.
+    assert_eq!(shot_string.validation_findings, Some(vec![]));
+}
+
+#[sqlx::test]
+async fn reverting_a_merged_string_scores_the_merged_shots_as_uploaded(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let (league_id, match_id) = uploaded_match(&mut conn).await;
+    let strings = strings_by_name(&mut conn, match_id).await;
+    let [(_, alex), (_, ethan)] = &strings[..] else {
+        panic!("Expected two strings, found {}", strings.len());
+    };
+
+    let operations = [
+        StringOperation::ConvertSighters {
+            id:    alex.id,
+            count: 1,
+        },
+        StringOperation::MergeStrings {
+            id:       alex.id,
+            other_id: ethan.id,
+        },
+        StringOperation::RevertEdits {
+            id: alex.id,
+        },
+    ];
+    for operation in operations {
+        apply_string_operation(&mut conn, league_id, match_id, operation).await.unwrap();
+    }
+
+    let strings = strings_by_name(&mut conn, match_id).await;
+    let [(_, merged)] = &strings[..] else {
+        panic!("Expected one string, found {}", strings.len());
+    };
+    assert_reverted(merged, "48-2X");
+    let sighter = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "S1").await;
+    assert!(sighter.tags.is_sighter());
+}
+
+#[sqlx::test]
+async fn reverting_split_strings_scores_their_shots_as_uploaded(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let (league_id, match_id) = uploaded_match(&mut conn).await;
+    let strings = strings_by_name(&mut conn, match_id).await;
+    let (_, alex) = &strings[0];
+    let second = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "2").await;
+    let third = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "3").await;
+
+    apply_string_operation(
+        &mut conn,
+        league_id,
+        match_id,
+        StringOperation::DropShot {
+            id:      alex.id,
+            shot_id: third.id,
+        },
+    )
+    .await
+    .unwrap();
+    let split_ids = apply_string_operation(
+        &mut conn,
+        league_id,
+        match_id,
+        StringOperation::SplitString {
+            id:         alex.id,
+            at_shot_id: second.id,
+        },
+    )
+    .await
+    .unwrap();
+    for &id in &split_ids {
+        apply_string_operation(
+            &mut conn,
+            league_id,
+            match_id,
+            StringOperation::RevertEdits {
+                id,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let [first_id, second_id] = split_ids[..] else {
+        panic!("Expected two strings, found {}", split_ids.len());
+    };
+    assert_reverted(&fetch_string(&mut conn, match_id, first_id).await.unwrap(), "10-0X");
+    assert_reverted(&fetch_string(&mut conn, match_id, second_id).await.unwrap(), "19-1X");
+    let third = find_uploaded_shot(&mut conn, league_id, match_id, second_id, "3").await;
+    assert!(!third.tags.is_hidden());
+}
+
+#[sqlx::test]
+async fn converting_no_sighters_leaves_the_string_unedited(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let (league_id, match_id) = uploaded_match(&mut conn).await;
+    let (_, alex) = strings_by_name(&mut conn, match_id).await.remove(0);
+
+    apply_string_operation(
+        &mut conn,
+        league_id,
+        match_id,
+        StringOperation::ConvertSighters {
+            id:    alex.id,
+            count: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(fetch_string(&mut conn, match_id, alex.id).await.unwrap(), alex);
+}
+
+#[sqlx::test]
+async fn reverting_shots_one_at_a_time(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let (league_id, match_id) = uploaded_match(&mut conn).await;
+    let (_, alex) = strings_by_name(&mut conn, match_id).await.remove(0);
+    let sighter = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "S1").await;
+    let third = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "3").await;
+
+    let operations = [
+        StringOperation::ConvertSighters {
+            id:    alex.id,
+            count: 1,
+        },
+        StringOperation::DropShot {
+            id:      alex.id,
+            shot_id: third.id,
+        },
+        StringOperation::RevertShot {
+            id:      alex.id,
+            shot_id: sighter.id,
+        },
+    ];
+    for operation in operations {
+        apply_string_operation(&mut conn, league_id, match_id, operation).await.unwrap();
+    }
+
+    // The sighter's converted back, while the dropped shot stays dropped.
+    let shot_string = fetch_string(&mut conn, match_id, alex.id).await.unwrap();
+    assert_eq!(shot_string.score.to_string(), "20-1X");
+    assert!(shot_string.edited);
+    let sighter = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "S1").await;
+    assert!(sighter.tags.is_sighter());
+
+    apply_string_operation(
+        &mut conn,
+        league_id,
+        match_id,
+        StringOperation::RevertShot {
+            id:      alex.id,
+            shot_id: third.id,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(fetch_string(&mut conn, match_id, alex.id).await.unwrap(), alex);
+}
+
+#[sqlx::test]
+async fn uploaded_shots_are_fired_at_instants_in_the_shotmarkers_time_zone(pool: SqlitePool) {
+    let mut conn = pool.acquire().await.unwrap();
+    let league_id = create_league(&mut conn, "Tuesday F-Class").await;
+    let match_id = create_match(&mut conn, league_id).await;
+    let time_zone = Some(chrono_tz::America::Los_Angeles);
+    store_export(&mut conn, league_id, match_id, "SM_shotslog_Jan_31.csv", EXPORT, time_zone)
+        .await
+        .unwrap();
+
+    let (_, alex) = strings_by_name(&mut conn, match_id).await.remove(0);
+    let sighter = find_uploaded_shot(&mut conn, league_id, match_id, alex.id, "S1").await;
+    // 6:57 pm in California is the next morning in UTC.
+    let fired_at = "2025-02-01T02:57:00Z".parse().unwrap();
+    assert_eq!(sighter.shot_utc_date_time, Some(fired_at));
+}
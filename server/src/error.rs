@@ -1,66 +1,360 @@
+use std::collections::BTreeMap;
+
 use axum::{
     Json,
-    http::StatusCode,
+    http::{
+        HeaderMap,
+        HeaderValue,
+        StatusCode,
+        header::RETRY_AFTER,
+    },
     response::{
         IntoResponse,
         Response,
     },
 };
-use serde_json::json;
-use thiserror::Error;
+use log::error;
+use shared_types::response::{
+    ApiError,
+    ApiErrorCode,
+};
+use shotmarker_csv_parser::error::{
+    ShotMarkerParseError,
+    ShotMarkerReadError,
+};
 
-use crate::app::leagues::LeagueError;
+use crate::app::{
+    auth::AuthError,
+    classes::ClassError,
+    export::ShotMarkerExportError,
+    leagues::LeagueError,
+    matches::MatchError,
+    shooters::ShooterError,
+    strings::StringError,
+};
 
-#[derive(Debug, Clone, Error)]
-pub enum HttpResponse {
-    #[error("Not Found")]
-    NotFound {
-        message: String,
-    },
-    #[error("Not Authorized")]
-    Unauthorized,
+/// How an error is reported to API clients: the status it's sent with, and the [`ApiError`] body.
+trait ReportedError: std::fmt::Display {
+    fn status(&self) -> StatusCode;
+
+    fn code(&self) -> ApiErrorCode;
+
+    fn details(&self) -> BTreeMap<String, String> { BTreeMap::new() }
+
+    fn headers(&self) -> HeaderMap { HeaderMap::new() }
+
+    fn to_response(&self) -> Response {
+        let api_error = ApiError {
+            code:    self.code(),
+            message: self.to_string(),
+            details: self.details(),
+        };
+
+        (self.status(), self.headers(), Json(api_error)).into_response()
+    }
 }
 
-impl IntoResponse for HttpResponse {
-    fn into_response(self) -> Response {
+impl ReportedError for AuthError {
+    fn status(&self) -> StatusCode {
         match self {
-            HttpResponse::Unauthorized => {
-                (StatusCode::UNAUTHORIZED, Json(json!({"message": "Not Authorized"})))
-                    .into_response()
+            AuthError::AccountDisabled
+            | AuthError::AlreadyLoggedIn
+            | AuthError::Forbidden {
+                ..
             }
-            HttpResponse::NotFound {
-                message,
-            } => (StatusCode::NOT_FOUND, Json(json!({"message": message}))).into_response(),
+            | AuthError::InvalidInvite
+            | AuthError::RegistrationClosed => StatusCode::FORBIDDEN,
+            AuthError::InvalidCredentials
+            | AuthError::NotLoggedIn
+            | AuthError::UserNotFound {
+                ..
+            } => StatusCode::UNAUTHORIZED,
+            AuthError::LastSiteAdmin
+            | AuthError::UsernameTaken {
+                ..
+            } => StatusCode::CONFLICT,
+            AuthError::NoDbConnectionPool => StatusCode::INTERNAL_SERVER_ERROR,
+            AuthError::TooManyAttempts {
+                ..
+            } => StatusCode::TOO_MANY_REQUESTS,
+            AuthError::UsernameNotFound {
+                ..
+            } => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn code(&self) -> ApiErrorCode {
+        match self {
+            AuthError::AccountDisabled => ApiErrorCode::AccountDisabled,
+            AuthError::AlreadyLoggedIn => ApiErrorCode::AlreadyLoggedIn,
+            AuthError::Forbidden {
+                ..
+            } => ApiErrorCode::Forbidden,
+            AuthError::InvalidCredentials
+            | AuthError::UserNotFound {
+                ..
+            } => ApiErrorCode::InvalidCredentials,
+            AuthError::InvalidInvite => ApiErrorCode::InvalidInvite,
+            AuthError::LastSiteAdmin => ApiErrorCode::LastSiteAdmin,
+            AuthError::NoDbConnectionPool => ApiErrorCode::Internal,
+            AuthError::NotLoggedIn => ApiErrorCode::NotLoggedIn,
+            AuthError::RegistrationClosed => ApiErrorCode::RegistrationClosed,
+            AuthError::TooManyAttempts {
+                ..
+            } => ApiErrorCode::TooManyAttempts,
+            AuthError::UsernameNotFound {
+                ..
+            } => ApiErrorCode::UserNotFound,
+            AuthError::UsernameTaken {
+                ..
+            } => ApiErrorCode::UsernameTaken,
+        }
+    }
+
+    fn details(&self) -> BTreeMap<String, String> {
+        match self {
+            AuthError::TooManyAttempts {
+                retry_after,
+            } => BTreeMap::from([("retry_after".to_string(), retry_after.to_string())]),
+            _ => BTreeMap::new(),
+        }
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let AuthError::TooManyAttempts {
+            retry_after,
+        } = self
+        {
+            headers.insert(RETRY_AFTER, HeaderValue::from(*retry_after));
         }
+
+        headers
     }
 }
 
+impl ReportedError for ClassError {
+    fn status(&self) -> StatusCode { StatusCode::NOT_FOUND }
+
+    fn code(&self) -> ApiErrorCode { ApiErrorCode::ClassNotFound }
+}
+
+impl ReportedError for LeagueError {
+    fn status(&self) -> StatusCode {
+        match self {
+            LeagueError::InvalidDateRange {
+                ..
+            } => StatusCode::UNPROCESSABLE_ENTITY,
+            LeagueError::NotFound {
+                ..
+            } => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn code(&self) -> ApiErrorCode {
+        match self {
+            LeagueError::InvalidDateRange {
+                ..
+            } => ApiErrorCode::InvalidDateRange,
+            LeagueError::NotFound {
+                ..
+            } => ApiErrorCode::LeagueNotFound,
+        }
+    }
+}
+
+impl ReportedError for MatchError {
+    fn status(&self) -> StatusCode { StatusCode::NOT_FOUND }
+
+    fn code(&self) -> ApiErrorCode { ApiErrorCode::MatchNotFound }
+}
+
+impl ReportedError for ShooterError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ShooterError::ClassNotFound {
+                ..
+            }
+            | ShooterError::NotFound {
+                ..
+            } => StatusCode::NOT_FOUND,
+            ShooterError::EmptyName => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+
+    fn code(&self) -> ApiErrorCode {
+        match self {
+            ShooterError::ClassNotFound {
+                ..
+            } => ApiErrorCode::ClassNotFound,
+            ShooterError::EmptyName => ApiErrorCode::EmptyName,
+            ShooterError::NotFound {
+                ..
+            } => ApiErrorCode::ShooterNotFound,
+        }
+    }
+}
+
+impl ReportedError for ShotMarkerExportError {
+    fn status(&self) -> StatusCode { StatusCode::NOT_FOUND }
+
+    fn code(&self) -> ApiErrorCode { ApiErrorCode::ExportNotFound }
+}
+
+impl ReportedError for ShotMarkerParseError {
+    fn status(&self) -> StatusCode { StatusCode::UNPROCESSABLE_ENTITY }
+
+    fn code(&self) -> ApiErrorCode { ApiErrorCode::ParseFailed }
+
+    fn details(&self) -> BTreeMap<String, String> {
+        let mut details = BTreeMap::from([
+            ("line".to_string(), self.line.to_string()),
+            ("column".to_string(), self.column.to_string()),
+        ]);
+        if let Some(field) = &self.field {
+            details.insert("field".to_string(), field.clone());
+        }
+        if let Some(string_header) = &self.string_header {
+            details.insert("string_header".to_string(), string_header.clone());
+        }
+
+        details
+    }
+}
+
+impl ReportedError for StringError {
+    fn status(&self) -> StatusCode {
+        match self {
+            StringError::MergeWithItself {
+                ..
+            }
+            | StringError::NoShooterProposal {
+                ..
+            }
+            | StringError::NotEnoughSighters {
+                ..
+            }
+            | StringError::SplitAtFirstShot {
+                ..
+            } => StatusCode::UNPROCESSABLE_ENTITY,
+            StringError::NotFound {
+                ..
+            }
+            | StringError::ShotNotFound {
+                ..
+            } => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn code(&self) -> ApiErrorCode {
+        match self {
+            StringError::MergeWithItself {
+                ..
+            } => ApiErrorCode::MergeWithItself,
+            StringError::NoShooterProposal {
+                ..
+            } => ApiErrorCode::NoShooterProposal,
+            StringError::NotEnoughSighters {
+                ..
+            } => ApiErrorCode::NotEnoughSighters,
+            StringError::NotFound {
+                ..
+            } => ApiErrorCode::StringNotFound,
+            StringError::ShotNotFound {
+                ..
+            } => ApiErrorCode::ShotNotFound,
+            StringError::SplitAtFirstShot {
+                ..
+            } => ApiErrorCode::SplitAtFirstShot,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct AppError(pub anyhow::Error);
 
+impl AppError {
+    /// The error to report, if it's one clients are told about rather than an internal one.
+    fn reported(&self) -> Option<&dyn ReportedError> {
+        let error = &self.0;
+        if let Some(auth_error) = error.downcast_ref::<AuthError>() {
+            return Some(auth_error);
+        }
+        if let Some(class_error) = error.downcast_ref::<ClassError>() {
+            return Some(class_error);
+        }
+        if let Some(league_error) = error.downcast_ref::<LeagueError>() {
+            return Some(league_error);
+        }
+        if let Some(match_error) = error.downcast_ref::<MatchError>() {
+            return Some(match_error);
+        }
+        if let Some(shooter_error) = error.downcast_ref::<ShooterError>() {
+            return Some(shooter_error);
+        }
+        if let Some(export_error) = error.downcast_ref::<ShotMarkerExportError>() {
+            return Some(export_error);
+        }
+        if let Some(string_error) = error.downcast_ref::<StringError>() {
+            return Some(string_error);
+        }
+
+        error.downcast_ref::<ShotMarkerParseError>().map(|error| error as _).or_else(|| {
+            match error.downcast_ref::<ShotMarkerReadError>() {
+                Some(ShotMarkerReadError::Parse(parse_error)) => Some(parse_error as _),
+                _ => None,
+            }
+        })
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        if let Some(league_error) = self.0.downcast_ref::<LeagueError>() {
-            return (StatusCode::NOT_FOUND, Json(json!({"message": league_error.to_string()})))
-                .into_response();
-        }
-
-        match self.0.downcast_ref::<HttpResponse>() {
-            Some(response) => response.clone().into_response(),
-            None => {
-                let error = self.0;
-                let body = json!(
-                    {
-                        "error": format!("{:#}", error),
-                        "backtrace": if cfg!(debug_assertions) {
-                            Some(error.backtrace().to_string())
-                        } else {
-                            None
-                        }
-                    }
-                );
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
+        if let Some(reported) = self.reported() {
+            return reported.to_response();
+        }
+
+        // A row that isn't there is something the request refers to that doesn't exist, as is the
+        // missing parent of a foreign key. A unique key clashes with what's already stored. SQLite's
+        // own messages aren't passed on, as they describe the schema rather than the request.
+        let reported = match self.0.downcast_ref::<sqlx::Error>() {
+            Some(sqlx::Error::RowNotFound) => {
+                Some((StatusCode::NOT_FOUND, ApiErrorCode::NotFound, "Not found"))
+            }
+            Some(sqlx::Error::Database(db_error)) if db_error.is_foreign_key_violation() => {
+                Some((
+                    StatusCode::NOT_FOUND,
+                    ApiErrorCode::NotFound,
+                    "Refers to something that doesn't exist",
+                ))
             }
+            Some(sqlx::Error::Database(db_error)) if db_error.is_unique_violation() => {
+                Some((StatusCode::CONFLICT, ApiErrorCode::Conflict, "Conflicts with existing data"))
+            }
+            _ => None,
+        };
+        if let Some((status, code, message)) = reported {
+            let api_error = ApiError {
+                code,
+                message: message.to_string(),
+                details: BTreeMap::new(),
+            };
+            return (status, Json(api_error)).into_response();
         }
+
+        let error = self.0;
+        error!("Internal error: {error:?}");
+        let message = if cfg!(debug_assertions) {
+            format!("{error:#}")
+        } else {
+            "Internal server error".to_string()
+        };
+        let api_error = ApiError {
+            code: ApiErrorCode::Internal,
+            message,
+            details: BTreeMap::new(),
+        };
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(api_error)).into_response()
     }
 }
 
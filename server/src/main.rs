@@ -1,3 +1,10 @@
+use std::net::{
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+};
+
 use anyhow::Result;
 use axum_session::{
     SessionConfig,
@@ -14,6 +21,8 @@ use tokio::signal;
 use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
+use crate::app::auth::RegistrationMode;
+
 pub mod app;
 pub mod error;
 
@@ -63,15 +72,43 @@ async fn main() -> Result<()> {
 
     sqlx::migrate!().run(&db_pool).await?;
 
+    let registration_mode = match std::env::var("REGISTRATION_MODE") {
+        Ok(mode) => mode.parse()?,
+        Err(_) => RegistrationMode::default(),
+    };
+
+    let trusted_proxies = match std::env::var("TRUSTED_PROXIES") {
+        Ok(proxies) => {
+            proxies
+                .split(',')
+                .map(str::trim)
+                .filter(|proxy| !proxy.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?
+        }
+        Err(_) => vec![IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)],
+    };
+
+    let time_zone = match std::env::var("SHOTMARKER_TIME_ZONE") {
+        Ok(time_zone) => Some(time_zone.parse()?),
+        Err(_) => None,
+    };
+
     let app_state = app::AppState {
         db_pool,
+        registration_mode,
+        trusted_proxies,
+        time_zone,
     };
     let app = app::build(app_state, session_store, auth_config);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:9000").await?;
     println!("Server running on http://127.0.0.1:9000/");
 
-    axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()).await.map_err(Into::into)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown_signal())
+        .await
+        .map_err(Into::into)
 }
 
 async fn shutdown_signal() {
@@ -4,6 +4,7 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use shared_types::response::ApiError;
 use yew::prelude::*;
 
 pub async fn perform_api_operation<O, R>(
@@ -46,12 +47,14 @@ pub async fn perform_api_operation<O, R>(
                     Err(error) => error.to_string(),
                 };
                 error!("Operation failed ({}): {error_body}", resp.status());
+                // Errors from the API itself come as an `ApiError`, but those from in front of it,
+                // such as a proxy, may not.
+                let message = match serde_json::from_str::<ApiError>(&error_body) {
+                    Ok(api_error) => api_error.to_string(),
+                    Err(_) => format!("{} {}: {error_body}", resp.status(), resp.status_text()),
+                };
                 if let Some(item_setter) = maybe_item_setter {
-                    item_setter.set(Some(Err(format!(
-                        "{} {}: {error_body}",
-                        resp.status(),
-                        resp.status_text(),
-                    ))));
+                    item_setter.set(Some(Err(message)));
                 }
             }
         }
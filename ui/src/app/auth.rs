@@ -181,6 +181,7 @@ fn log_in_or_register_panel(props: &LogInOrRegisterPanelProps) -> Html {
     let username = use_state_eq(String::new);
     let password = use_state_eq(String::new);
     let confirm_password = use_state_eq(String::new);
+    let invite_code = use_state_eq(String::new);
     let is_password_confirmation_match = use_state(|| true);
     let is_submitting = use_state_eq(|| false);
     let maybe_user: UseStateHandle<Option<Result<User, String>>> = use_state_eq(|| None);
@@ -221,6 +222,9 @@ fn log_in_or_register_panel(props: &LogInOrRegisterPanelProps) -> Html {
         use_callback(confirm_password.clone(), |new_confirm_password, confirm_password| {
             confirm_password.set(new_confirm_password);
         });
+    let invite_code_onchange = use_callback(invite_code.clone(), |new_invite_code, invite_code| {
+        invite_code.set(new_invite_code);
+    });
 
     let effect_is_password_confirmation_match = is_password_confirmation_match.clone();
     use_effect_with(
@@ -235,6 +239,7 @@ fn log_in_or_register_panel(props: &LogInOrRegisterPanelProps) -> Html {
         let username = username.clone();
         let password = password.clone();
         let confirm_password = confirm_password.clone();
+        let invite_code = invite_code.clone();
         let is_submitting = is_submitting.clone();
         let maybe_user = maybe_user.clone();
 
@@ -262,8 +267,10 @@ fn log_in_or_register_panel(props: &LogInOrRegisterPanelProps) -> Html {
                         // Register logic goes here
                         debug!("Registering user with username: {}", &*username);
                         let register_payload = RegisterUser {
-                            username: (*username).clone(),
-                            password: (*password).clone(),
+                            username:    (*username).clone(),
+                            password:    (*password).clone(),
+                            invite_code: Some((*invite_code).clone())
+                                .filter(|invite_code| !invite_code.is_empty()),
                         };
                         let spawned_maybe_user_setter = maybe_user.setter();
                         wasm_bindgen_futures::spawn_local(perform_api_operation(
@@ -380,6 +387,13 @@ fn log_in_or_register_panel(props: &LogInOrRegisterPanelProps) -> Html {
                     state={if *is_password_confirmation_match.clone() {InputState::Success} else {InputState::Error}}
                 />
             </FormGroup>
+            <FormGroup label="Invite Code">
+                <TextInput
+                    name="invite_code"
+                    value={(*invite_code).clone()}
+                    onchange={invite_code_onchange.clone()}
+                />
+            </FormGroup>
         </>
     );
     let footer = html! {
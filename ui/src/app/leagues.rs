@@ -4,7 +4,10 @@ use anyhow::{
 };
 use gloo_net::http::Request;
 use patternfly_yew::prelude::*;
-use shared_types::response::League;
+use shared_types::response::{
+    League,
+    LeagueStandings,
+};
 use uuid::Uuid;
 use yew::prelude::*;
 use yew_nested_router::{
@@ -16,6 +19,7 @@ pub mod league_create_panel;
 pub mod league_details_panel;
 pub mod league_list;
 pub mod league_panel;
+pub mod league_standings_panel;
 pub mod leagues_panel;
 
 use crate::app::{
@@ -59,6 +63,7 @@ pub enum LeagueRoute {
     },
     Matches(MatchesRoute),
     Shooters(ShootersRoute),
+    Standings,
 }
 
 impl LeagueRoute {
@@ -127,3 +132,18 @@ pub async fn fetch_league(league_id: Uuid) -> Result<League> {
 
     Ok(league)
 }
+
+pub async fn fetch_league_standings(league_id: Uuid) -> Result<LeagueStandings> {
+    let response = Request::get(&format!("/api/league/{league_id}/standings")).send().await?;
+    let standings = if response.ok() {
+        response.json().await?
+    } else {
+        return Err(anyhow!(
+            "Failed to fetch standings for league {league_id}: {}\n{}",
+            response.status(),
+            response.text().await?,
+        ));
+    };
+
+    Ok(standings)
+}
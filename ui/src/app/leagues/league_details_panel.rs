@@ -65,6 +65,9 @@ pub fn league_details_panel(props: &LeagueDetailsPanelProps) -> Html {
             <DescriptionList>
                 { description }
                 { dates }
+                <DescriptionGroup term="Scoring">
+                    { league.scoring_convention.to_string() }
+                </DescriptionGroup>
             </DescriptionList>
         </Content>
     }
@@ -25,6 +25,7 @@ use crate::app::{
         LeagueRoute,
         fetch_league,
         league_details_panel::LeagueDetailsPanel,
+        league_standings_panel::LeagueStandingsPanel,
     },
     matches::{
         MatchesRoute,
@@ -98,6 +99,10 @@ fn league_panel_body(props: &LeaguePanelProps) -> HtmlResult {
                                 to={LeagueRoute::Shooters(ShootersRoute::Index)}
                                 title="Shooters"
                             />
+                            <TabRouterItem<LeagueRoute>
+                                to={LeagueRoute::Standings}
+                                title="Standings"
+                            />
                         </TabsRouter<LeagueRoute>>
                     </PageSection>
                     <PageSection>
@@ -161,6 +166,13 @@ pub fn switch_league_panel(league: Rc<League>, target: LeagueRoute) -> Html {
                 </Suspense>
             )
         }
+        LeagueRoute::Standings => {
+            html!(
+                <Suspense fallback="Loading standings...">
+                    <LeagueStandingsPanel {league} />
+                </Suspense>
+            )
+        }
     };
 
     html!({ route })
@@ -0,0 +1,159 @@
+use std::rc::Rc;
+
+use patternfly_yew::prelude::*;
+use shared_types::response::{
+    ClassStandings,
+    League,
+    StandingsEntry,
+};
+use yew::{
+    prelude::*,
+    suspense::use_future,
+};
+
+use crate::app::leagues::fetch_league_standings;
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+pub struct LeagueStandingsPanelProps {
+    pub league: Rc<League>,
+}
+
+#[function_component(LeagueStandingsPanel)]
+pub fn league_standings_panel(props: &LeagueStandingsPanelProps) -> HtmlResult {
+    let league_id = props.league.id;
+    let standings_result = use_future(|| async move { fetch_league_standings(league_id).await })?;
+
+    let standings = match &*standings_result {
+        Ok(standings) => standings.clone(),
+        Err(error) => {
+            return Ok(html!(
+                <Content>
+                    { format!("Error fetching standings: {error}") }
+                </Content>
+            ));
+        }
+    };
+
+    let rules = &standings.rules;
+    let counted = match rules.best_matches {
+        Some(best_matches) => format!("Best {best_matches} matches count"),
+        None => "All matches count".to_string(),
+    };
+    let tie_breakers =
+        rules.tie_breakers.iter().map(|tie_breaker| format!("{tie_breaker:?}")).collect::<Vec<_>>();
+
+    Ok(html!(
+        <>
+            <Content>
+                <p>
+                    { format!(
+                        "{counted}, after dropping the worst {}. {} matches needed to qualify. \
+                         Ties broken by: {}.",
+                        rules.drop_worst,
+                        rules.min_matches,
+                        tie_breakers.join(", "),
+                    ) }
+                </p>
+            </Content>
+            { for standings.classes.into_iter().map(|class_standings| {
+                let class_standings = Rc::new(class_standings);
+                html!(<ClassStandingsTable {class_standings} />)
+            }) }
+        </>
+    ))
+}
+
+#[remain::sorted]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ClassStandingsTableColumn {
+    Matches,
+    Place,
+    Score,
+    Shooter,
+}
+
+impl TableEntryRenderer<ClassStandingsTableColumn> for StandingsEntry {
+    fn render_cell(&self, context: CellContext<'_, ClassStandingsTableColumn>) -> Cell {
+        match context.column {
+            ClassStandingsTableColumn::Matches => {
+                html!(
+                    <>
+                        { for self.matches.iter().map(|contribution| {
+                            let text = format!(
+                                "{} ({}): {}",
+                                contribution.match_name, contribution.place, contribution.score
+                            );
+                            if contribution.dropped {
+                                html!(<div><s>{ text }</s></div>)
+                            } else {
+                                html!(<div>{ text }</div>)
+                            }
+                        }) }
+                    </>
+                )
+                .into()
+            }
+            ClassStandingsTableColumn::Place => {
+                html!(self.place.map_or_else(|| "-".to_string(), |place| place.to_string())).into()
+            }
+            ClassStandingsTableColumn::Score => html!(self.score.to_string()).into(),
+            ClassStandingsTableColumn::Shooter => html!(self.shooter_name.clone()).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+struct ClassStandingsTableProps {
+    pub class_standings: Rc<ClassStandings>,
+}
+
+#[function_component(ClassStandingsTable)]
+fn class_standings_table(props: &ClassStandingsTableProps) -> Html {
+    let class_name = props
+        .class_standings
+        .class
+        .as_ref()
+        .map_or_else(|| "Unclassified".to_string(), |class| class.name.clone());
+    let entries = use_state_eq(|| props.class_standings.entries.clone());
+    let (entries, _) = use_table_data(UseStateTableModel::new(entries));
+
+    let header = html_nested!(
+        <TableHeader<ClassStandingsTableColumn>>
+            <TableColumn<ClassStandingsTableColumn>
+                label="Place"
+                width={ColumnWidth::FitContent}
+                index={ClassStandingsTableColumn::Place}
+            />
+            <TableColumn<ClassStandingsTableColumn>
+                label="Shooter"
+                width={ColumnWidth::WidthMax}
+                index={ClassStandingsTableColumn::Shooter}
+            />
+            <TableColumn<ClassStandingsTableColumn>
+                label="Score"
+                width={ColumnWidth::FitContent}
+                text_modifier={TextModifier::NoWrap}
+                index={ClassStandingsTableColumn::Score}
+            />
+            <TableColumn<ClassStandingsTableColumn>
+                label="Matches (place): score"
+                width={ColumnWidth::FitContent}
+                text_modifier={TextModifier::NoWrap}
+                index={ClassStandingsTableColumn::Matches}
+            />
+        </TableHeader<ClassStandingsTableColumn>>
+    );
+
+    html!(
+        <Content>
+            <Title level={Level::H3}>
+                { class_name }
+            </Title>
+            <Table<ClassStandingsTableColumn,UseTableData<ClassStandingsTableColumn,UseStateTableModel<StandingsEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Content>
+    )
+}
@@ -3,7 +3,10 @@ use anyhow::{
     anyhow,
 };
 use gloo_net::http::Request;
-use shared_types::response::Match;
+use shared_types::response::{
+    Match,
+    MatchResults,
+};
 use uuid::Uuid;
 use yew_nested_router::prelude::*;
 
@@ -21,6 +24,7 @@ use crate::app::{
 pub mod match_details_panel;
 pub mod match_list_panel;
 pub mod match_panel;
+pub mod match_results_panel;
 pub mod matches_create_panel;
 pub mod matches_panel;
 
@@ -37,6 +41,7 @@ pub enum MatchRoute {
     #[default]
     #[target(index)]
     Details,
+    Results,
     SmExport {
         sm_export_id: Uuid,
         #[target(nested, default)]
@@ -123,3 +128,19 @@ pub async fn fetch_match(league_id: Uuid, match_id: Uuid) -> Result<Match> {
 
     Ok(match_object)
 }
+
+pub async fn fetch_match_results(league_id: Uuid, match_id: Uuid) -> Result<MatchResults> {
+    let response =
+        Request::get(&format!("/api/league/{league_id}/match/{match_id}/results")).send().await?;
+    let results = if response.ok() {
+        response.json().await?
+    } else {
+        return Err(anyhow!(
+            "Failed to fetch results for match {match_id} in league {league_id}: {}\n{}",
+            response.status(),
+            response.text().await?,
+        ));
+    };
+
+    Ok(results)
+}
@@ -22,6 +22,7 @@ use crate::app::{
         MatchRoute,
         fetch_match,
         match_details_panel::MatchDetailsPanel,
+        match_results_panel::MatchResultsPanel,
     },
     shot_strings::{
         ShotStringsRoute,
@@ -97,6 +98,7 @@ pub fn match_details_panel(props: &MatchPanelBodyProps) -> HtmlResult {
                                 to={MatchRoute::ShotStrings(ShotStringsRoute::Index)}
                                 title="Shot Strings"
                             />
+                            <TabRouterItem<MatchRoute> to={MatchRoute::Results} title="Results" />
                         </TabsRouter<MatchRoute>>
                     </PageSection>
                     <PageSection>
@@ -119,6 +121,13 @@ pub fn switch_match_panel(league: Rc<League>, match_object: Rc<Match>, target: M
                 </Suspense>
             )
         }
+        MatchRoute::Results => {
+            html!(
+                <Suspense fallback="Loading match results...">
+                    <MatchResultsPanel {league} {match_object} />
+                </Suspense>
+            )
+        }
         MatchRoute::SmExport {
             sm_export_id,
             ..
@@ -0,0 +1,150 @@
+use std::rc::Rc;
+
+use patternfly_yew::prelude::*;
+use shared_types::response::{
+    ClassResults,
+    League,
+    Match,
+    ResultEntry,
+};
+use yew::{
+    prelude::*,
+    suspense::use_future,
+};
+
+use crate::app::matches::fetch_match_results;
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+pub struct MatchResultsPanelProps {
+    pub league:       Rc<League>,
+    pub match_object: Rc<Match>,
+}
+
+#[function_component(MatchResultsPanel)]
+pub fn match_results_panel(props: &MatchResultsPanelProps) -> HtmlResult {
+    let league_id = props.league.id;
+    let match_id = props.match_object.id;
+    let results_result =
+        use_future(|| async move { fetch_match_results(league_id, match_id).await })?;
+
+    let results = match &*results_result {
+        Ok(results) => results.clone(),
+        Err(error) => {
+            return Ok(html!(
+                <Content>
+                    { format!("Error fetching match results: {error}") }
+                </Content>
+            ));
+        }
+    };
+
+    let unattributed = if results.unattributed_strings > 0 {
+        html!(
+            <Content>
+                <p>
+                    { format!(
+                        "{} strings aren't assigned to a shooter, and aren't counted.",
+                        results.unattributed_strings
+                    ) }
+                </p>
+            </Content>
+        )
+    } else {
+        html!()
+    };
+
+    Ok(html!(
+        <>
+            { unattributed }
+            { for results.classes.into_iter().map(|class_results| {
+                let class_results = Rc::new(class_results);
+                html!(<ClassResultsTable {class_results} />)
+            }) }
+        </>
+    ))
+}
+
+#[remain::sorted]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ClassResultsTableColumn {
+    CountBack,
+    Place,
+    Score,
+    Shooter,
+}
+
+impl TableEntryRenderer<ClassResultsTableColumn> for ResultEntry {
+    fn render_cell(&self, context: CellContext<'_, ClassResultsTableColumn>) -> Cell {
+        match context.column {
+            ClassResultsTableColumn::CountBack => {
+                let count_back = &self.count_back;
+                html!({
+                    format!(
+                        "{} / {} / {}",
+                        count_back.last_ten, count_back.last_five, count_back.last_shot
+                    )
+                })
+                .into()
+            }
+            ClassResultsTableColumn::Place => html!(self.place.to_string()).into(),
+            ClassResultsTableColumn::Score => html!(self.score.to_string()).into(),
+            ClassResultsTableColumn::Shooter => html!(self.shooter_name.clone()).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+struct ClassResultsTableProps {
+    pub class_results: Rc<ClassResults>,
+}
+
+#[function_component(ClassResultsTable)]
+fn class_results_table(props: &ClassResultsTableProps) -> Html {
+    let class_name = props
+        .class_results
+        .class
+        .as_ref()
+        .map_or_else(|| "Unclassified".to_string(), |class| class.name.clone());
+    let entries = use_state_eq(|| props.class_results.entries.clone());
+    let (entries, _) = use_table_data(UseStateTableModel::new(entries));
+
+    let header = html_nested!(
+        <TableHeader<ClassResultsTableColumn>>
+            <TableColumn<ClassResultsTableColumn>
+                label="Place"
+                width={ColumnWidth::FitContent}
+                index={ClassResultsTableColumn::Place}
+            />
+            <TableColumn<ClassResultsTableColumn>
+                label="Shooter"
+                width={ColumnWidth::WidthMax}
+                index={ClassResultsTableColumn::Shooter}
+            />
+            <TableColumn<ClassResultsTableColumn>
+                label="Score"
+                width={ColumnWidth::FitContent}
+                text_modifier={TextModifier::NoWrap}
+                index={ClassResultsTableColumn::Score}
+            />
+            <TableColumn<ClassResultsTableColumn>
+                label="Count-back (10 / 5 / 1)"
+                width={ColumnWidth::FitContent}
+                text_modifier={TextModifier::NoWrap}
+                index={ClassResultsTableColumn::CountBack}
+            />
+        </TableHeader<ClassResultsTableColumn>>
+    );
+
+    html!(
+        <Content>
+            <Title level={Level::H3}>
+                { class_name }
+            </Title>
+            <Table<ClassResultsTableColumn,UseTableData<ClassResultsTableColumn,UseStateTableModel<ResultEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Content>
+    )
+}
@@ -1,3 +1,9 @@
+use anyhow::{
+    Result,
+    anyhow,
+};
+use gloo_net::http::Request;
+use shared_types::response::Shooter;
 use uuid::Uuid;
 use yew_nested_router::prelude::*;
 
@@ -16,3 +22,34 @@ pub enum ShootersRoute {
         shooter_id: Uuid,
     },
 }
+
+pub async fn fetch_shooters(league_id: Uuid) -> Result<Vec<Shooter>> {
+    let response = Request::get(&format!("/api/league/{league_id}/shooter")).send().await?;
+    let shooters = if response.ok() {
+        response.json().await?
+    } else {
+        return Err(anyhow!(
+            "Failed to fetch shooters for league {league_id}: {}\n{}",
+            response.status(),
+            response.text().await?,
+        ));
+    };
+
+    Ok(shooters)
+}
+
+pub async fn fetch_shooter(league_id: Uuid, shooter_id: Uuid) -> Result<Shooter> {
+    let response =
+        Request::get(&format!("/api/league/{league_id}/shooter/{shooter_id}")).send().await?;
+    let shooter = if response.ok() {
+        response.json().await?
+    } else {
+        return Err(anyhow!(
+            "Failed to fetch shooter {shooter_id} for league {league_id}: {}\n{}",
+            response.status(),
+            response.text().await?,
+        ));
+    };
+
+    Ok(shooter)
+}
@@ -1,5 +1,29 @@
+use std::{
+    borrow::Borrow,
+    time::Duration,
+};
+
+use log::debug;
+use patternfly_yew::prelude::*;
+use shared_types::{
+    request::ShooterOperation,
+    response::Shooter,
+};
 use uuid::Uuid;
-use yew::prelude::*;
+use yew::{
+    prelude::*,
+    suspense::use_future,
+};
+use yew_nested_router::prelude::*;
+
+use crate::{
+    api::perform_api_operation,
+    app::{
+        PageContent,
+        classes::fetch_classes,
+        shooters::ShootersRoute,
+    },
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Properties)]
 pub struct ShootersCreatePanelProps {
@@ -7,15 +31,153 @@ pub struct ShootersCreatePanelProps {
 }
 
 #[function_component(ShootersCreatePanel)]
-pub fn shooters_create_panel(props: &ShootersCreatePanelProps) -> Html {
-    html! {
-        <div>
-            <h2>
-                { "Create Shooter" }
-            </h2>
-            <p>
-                { format!("League ID: {}", props.league_id) }
-            </p>
-        </div>
-    }
+pub fn shooters_create_panel(props: &ShootersCreatePanelProps) -> HtmlResult {
+    let league_id = props.league_id;
+    let classes_result = use_future(|| async move { fetch_classes(league_id).await })?;
+    let shooter_name = use_state_eq(String::new);
+    let default_class_id = use_state_eq(|| None);
+    let is_creating = use_state_eq(|| false);
+    let maybe_shooter: UseStateHandle<Option<Result<Shooter, String>>> = use_state_eq(|| None);
+    let maybe_router = use_router::<ShootersRoute>();
+    let toaster = use_toaster();
+
+    let shooter_name_onchange =
+        use_callback(shooter_name.clone(), |new_shooter_name, shooter_name| {
+            shooter_name.set(new_shooter_name);
+        });
+
+    let default_class_onchange =
+        use_callback(default_class_id.clone(), |new_class_id, default_class_id| {
+            default_class_id.set(new_class_id);
+        });
+
+    let onsubmit = {
+        let shooter_name = shooter_name.clone();
+        let default_class_id = default_class_id.clone();
+        let is_creating = is_creating.setter();
+        let maybe_shooter = maybe_shooter.clone();
+
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+            is_creating.set(true);
+
+            let shooter_operation = ShooterOperation::Create {
+                name:             (*shooter_name).trim().to_string(),
+                default_class_id: *default_class_id,
+            };
+
+            let spawned_maybe_shooter_setter = maybe_shooter.setter();
+            wasm_bindgen_futures::spawn_local(perform_api_operation(
+                format!("/api/league/{league_id}/shooter/operation"),
+                shooter_operation,
+                Some(spawned_maybe_shooter_setter),
+            ));
+
+            is_creating.set(false);
+            if matches!(&*maybe_shooter, Some(Ok(_))) {
+                shooter_name.set(String::new());
+                default_class_id.set(None);
+            }
+        })
+    };
+
+    use_effect_with(maybe_shooter.clone(), move |_| {
+        if let Some(toaster) = toaster.borrow() {
+            if let Some(shooter_result) = (*maybe_shooter).borrow() {
+                let (alert_type, title, body) = match shooter_result {
+                    Ok(shooter) => {
+                        if let Some(router) = maybe_router {
+                            debug!("Navigating to shooter details page: {shooter:?}");
+                            router.push(ShootersRoute::Detail {
+                                shooter_id: shooter.id,
+                            });
+                        }
+                        (
+                            AlertType::Success,
+                            "Shooter Created",
+                            html!({ format!("Shooter \"{}\" created successfully", shooter.name) }),
+                        )
+                    }
+                    Err(error) => {
+                        (
+                            AlertType::Danger,
+                            "Error Creating Shooter",
+                            html!(
+                                <>
+                                    <p>
+                                        { "An error occurred while creating the shooter." }
+                                    </p>
+                                    <p>
+                                        { error }
+                                    </p>
+                                </>
+                            ),
+                        )
+                    }
+                };
+
+                toaster.toast(Toast {
+                    title: title.to_string(),
+                    r#type: alert_type,
+                    timeout: Some(Duration::from_secs(5)),
+                    body,
+                    actions: Vec::new(),
+                })
+            }
+        }
+    });
+
+    let classes = match &*classes_result {
+        Ok(classes) => classes.clone(),
+        Err(error) => {
+            return Ok(html!(
+                <Content>
+                    { format!("Error fetching classes: {error}") }
+                </Content>
+            ));
+        }
+    };
+
+    let html_content = html!(
+        <PageContent title="Create Shooter">
+            <Content>
+                <Form {onsubmit}>
+                    <FormGroup label="Shooter Name" required=true>
+                        <TextInput
+                            placeholder="Enter shooter name"
+                            required=true
+                            autofocus=true
+                            value={(*shooter_name).clone()}
+                            onchange={shooter_name_onchange}
+                        />
+                    </FormGroup>
+                    <FormGroup label="Default Class">
+                        <FormSelect<Uuid>
+                            placeholder="No default class"
+                            onchange={default_class_onchange}
+                        >
+                            { for classes.iter().map(|class| html_nested!(
+                                <FormSelectOption<Uuid>
+                                    value={class.id}
+                                    description={class.name.clone()}
+                                    selected={*default_class_id == Some(class.id)}
+                                />
+                            )) }
+                        </FormSelect<Uuid>>
+                    </FormGroup>
+                    <ActionGroup>
+                        <Button
+                            variant={ButtonVariant::Primary}
+                            label="Submit"
+                            r#type={ButtonType::Submit}
+                            icon={Icon::PlusCircle}
+                            loading={*is_creating}
+                        />
+                    </ActionGroup>
+                </Form>
+            </Content>
+        </PageContent>
+    );
+
+    Ok(html_content)
 }
@@ -1,5 +1,35 @@
+use std::{
+    borrow::Borrow,
+    rc::Rc,
+    time::Duration,
+};
+
+use patternfly_yew::prelude::*;
+use shared_types::{
+    request::ShooterOperation,
+    response::{
+        Class,
+        Shooter,
+    },
+};
 use uuid::Uuid;
-use yew::prelude::*;
+use yew::{
+    prelude::*,
+    suspense::use_future,
+};
+use yew_nested_router::prelude::*;
+
+use crate::{
+    api::perform_api_operation,
+    app::{
+        PageContent,
+        classes::fetch_classes,
+        shooters::{
+            ShootersRoute,
+            fetch_shooter,
+        },
+    },
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Properties)]
 pub struct ShooterDetailPanelProps {
@@ -8,15 +38,241 @@ pub struct ShooterDetailPanelProps {
 }
 
 #[function_component(ShooterDetailPanel)]
-pub fn shooters_create_panel(props: &ShooterDetailPanelProps) -> Html {
-    html! {
-        <div>
-            <h2>
-                { format!("Shooter {}", props.shooter_id) }
-            </h2>
-            <p>
-                { format!("League ID: {}", props.league_id) }
-            </p>
-        </div>
+pub fn shooter_detail_panel(props: &ShooterDetailPanelProps) -> HtmlResult {
+    let league_id = props.league_id;
+    let shooter_id = props.shooter_id;
+    let shooter_future = use_future(|| async move { fetch_shooter(league_id, shooter_id).await })?;
+    let classes_future = use_future(|| async move { fetch_classes(league_id).await })?;
+
+    let (shooter, classes) = match (&*shooter_future, &*classes_future) {
+        (Ok(shooter), Ok(classes)) => (Rc::new(shooter.clone()), Rc::new(classes.clone())),
+        (Err(error), _) | (_, Err(error)) => {
+            return Ok(html!(
+                <Content>
+                    { format!("Error fetching shooter: {error}") }
+                </Content>
+            ));
+        }
+    };
+
+    let html_content = html!(<ShooterDetails {shooter} {classes} />);
+
+    Ok(html_content)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+pub struct ShooterDetailsProps {
+    pub shooter: Rc<Shooter>,
+    pub classes: Rc<Vec<Class>>,
+}
+
+#[function_component(ShooterDetails)]
+pub fn shooter_details(props: &ShooterDetailsProps) -> Html {
+    let shooter = use_state_eq(|| props.shooter.as_ref().clone());
+    let shooter_name = use_state_eq(|| props.shooter.name.clone());
+    let default_class_id = use_state_eq(|| props.shooter.default_class_id);
+    let maybe_updated: UseStateHandle<Option<Result<Shooter, String>>> = use_state_eq(|| None);
+    let maybe_deleted: UseStateHandle<Option<Result<Shooter, String>>> = use_state_eq(|| None);
+    let maybe_router = use_router::<ShootersRoute>();
+    let toaster = use_toaster();
+    let league_id = shooter.league_id;
+    let shooter_id = shooter.id;
+
+    let shooter_name_onchange =
+        use_callback(shooter_name.clone(), |new_shooter_name, shooter_name| {
+            shooter_name.set(new_shooter_name);
+        });
+
+    let default_class_onchange =
+        use_callback(default_class_id.clone(), |new_class_id, default_class_id| {
+            default_class_id.set(new_class_id);
+        });
+
+    let onsubmit = {
+        let shooter = shooter.clone();
+        let shooter_name = shooter_name.clone();
+        let default_class_id = default_class_id.clone();
+        let maybe_updated = maybe_updated.clone();
+
+        Callback::from(move |event: SubmitEvent| {
+            event.prevent_default();
+
+            let name = (*shooter_name).trim().to_string();
+            let rename = (name != shooter.name).then_some(ShooterOperation::SetName {
+                id: shooter_id,
+                name,
+            });
+            let set_default_class = (*default_class_id != shooter.default_class_id).then_some(
+                ShooterOperation::SetDefaultClass {
+                    id:               shooter_id,
+                    default_class_id: *default_class_id,
+                },
+            );
+
+            let spawned_maybe_updated_setter = maybe_updated.setter();
+            wasm_bindgen_futures::spawn_local(async move {
+                for operation in rename.into_iter().chain(set_default_class) {
+                    perform_api_operation(
+                        format!("/api/league/{league_id}/shooter/operation"),
+                        operation,
+                        Some(spawned_maybe_updated_setter.clone()),
+                    )
+                    .await;
+                }
+            });
+        })
+    };
+
+    let ondelete = {
+        let maybe_deleted = maybe_deleted.clone();
+
+        Callback::from(move |_| {
+            wasm_bindgen_futures::spawn_local(perform_api_operation(
+                format!("/api/league/{league_id}/shooter/operation"),
+                ShooterOperation::Delete {
+                    id: shooter_id,
+                },
+                Some(maybe_deleted.setter()),
+            ));
+        })
+    };
+
+    {
+        let shooter = shooter.clone();
+        let toaster = toaster.clone();
+        use_effect_with(maybe_updated.clone(), move |_| {
+            if let Some(toaster) = toaster.borrow() {
+                if let Some(update_result) = (*maybe_updated).borrow() {
+                    let (alert_type, title, body) = match update_result {
+                        Ok(updated) => {
+                            shooter.set(updated.clone());
+                            (
+                                AlertType::Success,
+                                "Shooter Updated",
+                                html!({
+                                    format!("Shooter \"{}\" updated successfully", updated.name)
+                                }),
+                            )
+                        }
+                        Err(error) => {
+                            (
+                                AlertType::Danger,
+                                "Error Updating Shooter",
+                                html!(
+                                    <p>
+                                        { error }
+                                    </p>
+                                ),
+                            )
+                        }
+                    };
+
+                    toaster.toast(Toast {
+                        title: title.to_string(),
+                        r#type: alert_type,
+                        timeout: Some(Duration::from_secs(5)),
+                        body,
+                        actions: Vec::new(),
+                    })
+                }
+            }
+        });
     }
+
+    use_effect_with(maybe_deleted.clone(), move |_| {
+        if let Some(toaster) = toaster.borrow() {
+            if let Some(delete_result) = (*maybe_deleted).borrow() {
+                let (alert_type, title, body) = match delete_result {
+                    Ok(deleted) => {
+                        if let Some(router) = maybe_router {
+                            router.push(ShootersRoute::Index);
+                        }
+                        (
+                            AlertType::Success,
+                            "Shooter Deleted",
+                            html!({ format!("Shooter \"{}\" deleted", deleted.name) }),
+                        )
+                    }
+                    Err(error) => {
+                        (
+                            AlertType::Danger,
+                            "Error Deleting Shooter",
+                            html!(
+                                <p>
+                                    { error }
+                                </p>
+                            ),
+                        )
+                    }
+                };
+
+                toaster.toast(Toast {
+                    title: title.to_string(),
+                    r#type: alert_type,
+                    timeout: Some(Duration::from_secs(5)),
+                    body,
+                    actions: Vec::new(),
+                })
+            }
+        }
+    });
+
+    let default_class = shooter
+        .default_class_id
+        .and_then(|class_id| props.classes.iter().find(|class| class.id == class_id))
+        .map(|class| class.name.clone())
+        .unwrap_or_else(|| "None".to_string());
+
+    html!(
+        <PageContent title={shooter.name.clone()}>
+            <Content>
+                <DescriptionList>
+                    <DescriptionGroup term="Default Class">
+                        { default_class }
+                    </DescriptionGroup>
+                    <DescriptionGroup term="Aliases">
+                        { shooter.aliases.join(", ") }
+                    </DescriptionGroup>
+                </DescriptionList>
+            </Content>
+            <Content>
+                <Form {onsubmit}>
+                    <FormGroup label="Shooter Name" required=true>
+                        <TextInput
+                            required=true
+                            value={(*shooter_name).clone()}
+                            onchange={shooter_name_onchange}
+                        />
+                    </FormGroup>
+                    <FormGroup label="Default Class">
+                        <FormSelect<Uuid>
+                            placeholder="No default class"
+                            onchange={default_class_onchange}
+                        >
+                            { for props.classes.iter().map(|class| html_nested!(
+                                <FormSelectOption<Uuid>
+                                    value={class.id}
+                                    description={class.name.clone()}
+                                    selected={*default_class_id == Some(class.id)}
+                                />
+                            )) }
+                        </FormSelect<Uuid>>
+                    </FormGroup>
+                    <ActionGroup>
+                        <Button
+                            variant={ButtonVariant::Primary}
+                            label="Save"
+                            r#type={ButtonType::Submit}
+                        />
+                        <Button
+                            variant={ButtonVariant::Danger}
+                            label="Delete"
+                            icon={Icon::Trash}
+                            onclick={ondelete}
+                        />
+                    </ActionGroup>
+                </Form>
+            </Content>
+        </PageContent>
+    )
 }
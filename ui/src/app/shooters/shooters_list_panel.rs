@@ -1,5 +1,21 @@
+use std::rc::Rc;
+
+use patternfly_yew::prelude::*;
+use shared_types::response::Shooter;
 use uuid::Uuid;
-use yew::prelude::*;
+use yew::{
+    prelude::*,
+    suspense::use_future,
+};
+use yew_nested_router::components::Link;
+
+use crate::app::{
+    classes::fetch_classes,
+    shooters::{
+        ShootersRoute,
+        fetch_shooters,
+    },
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Properties)]
 pub struct ShootersListPanelProps {
@@ -7,15 +23,149 @@ pub struct ShootersListPanelProps {
 }
 
 #[function_component(ShootersListPanel)]
-pub fn shooters_create_panel(props: &ShootersListPanelProps) -> Html {
-    html! {
-        <div>
-            <h2>
-                { "Create Shooter" }
-            </h2>
-            <p>
-                { format!("League ID: {}", props.league_id) }
-            </p>
-        </div>
+pub fn shooters_list_panel(props: &ShootersListPanelProps) -> Html {
+    let league_id = props.league_id;
+    html!(
+        <>
+            <Content>
+                <Suspense fallback={html!({"Loading shooter list..."})}>
+                    <ShooterList {league_id} />
+                </Suspense>
+            </Content>
+        </>
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+pub struct ShooterListProps {
+    pub league_id: Uuid,
+}
+
+#[function_component(ShooterList)]
+pub fn shooter_list(props: &ShooterListProps) -> HtmlResult {
+    let league_id = props.league_id;
+    let shooters_result = use_future(|| async move { fetch_shooters(league_id).await })?;
+    let classes_result = use_future(|| async move { fetch_classes(league_id).await })?;
+
+    let html_result = match (&*shooters_result, &*classes_result) {
+        (Ok(shooters), Ok(classes)) => {
+            let rows = shooters
+                .iter()
+                .map(|shooter| {
+                    let default_class = shooter.default_class_id.and_then(|class_id| {
+                        classes
+                            .iter()
+                            .find(|class| class.id == class_id)
+                            .map(|class| class.name.clone())
+                    });
+                    ShooterRow {
+                        shooter: shooter.clone(),
+                        default_class,
+                    }
+                })
+                .collect();
+            let rows = Rc::new(rows);
+            html!(<ShooterListTable {rows} />)
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            html!(
+                <Content>
+                    { format!("Error: {e}") }
+                </Content>
+            )
+        }
+    };
+
+    Ok(html_result)
+}
+
+/// A shooter, along with the name of their default class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShooterRow {
+    pub shooter:       Shooter,
+    pub default_class: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ShooterListTableColumn {
+    DefaultClass,
+    Name,
+}
+
+impl TableEntryRenderer<ShooterListTableColumn> for ShooterRow {
+    fn render_cell(&self, context: CellContext<'_, ShooterListTableColumn>) -> Cell {
+        match context.column {
+            ShooterListTableColumn::DefaultClass => {
+                html!({ self.default_class.clone().unwrap_or_default() }).into()
+            }
+            ShooterListTableColumn::Name => {
+                html!(
+                    <Link<ShootersRoute>
+                        to={ShootersRoute::Detail { shooter_id: self.shooter.id }}
+                    >
+                        { self.shooter.name.clone() }
+                    </Link<ShootersRoute>>
+                )
+                .into()
+            }
+        }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+pub struct ShooterListTableProps {
+    pub rows: Rc<Vec<ShooterRow>>,
+}
+
+#[function_component(ShooterListTable)]
+pub fn shooter_list_table(props: &ShooterListTableProps) -> Html {
+    let rows = use_state_eq(|| props.rows.as_ref().clone());
+
+    let on_sort_by = {
+        let rows = rows.clone();
+
+        Some(Callback::from(move |column: TableHeaderSortBy<ShooterListTableColumn>| {
+            let mut entries_sorted = (*rows).clone();
+
+            match column.index {
+                ShooterListTableColumn::DefaultClass => {
+                    entries_sorted.sort_by_key(|val| val.default_class.clone().unwrap_or_default())
+                }
+                ShooterListTableColumn::Name => {
+                    entries_sorted.sort_by_key(|val| val.shooter.name.clone())
+                }
+            }
+
+            if matches!(column.order, Order::Descending) {
+                entries_sorted.reverse();
+            }
+            rows.set(entries_sorted);
+        }))
+    };
+
+    let (entries, onexpand) = use_table_data(UseStateTableModel::new(rows));
+
+    let header = html_nested!(
+        <TableHeader<ShooterListTableColumn>>
+            <TableColumn<ShooterListTableColumn>
+                label="Name"
+                index={ShooterListTableColumn::Name}
+                onsort={on_sort_by.clone()}
+            />
+            <TableColumn<ShooterListTableColumn>
+                label="Default Class"
+                index={ShooterListTableColumn::DefaultClass}
+                onsort={on_sort_by.clone()}
+            />
+        </TableHeader<ShooterListTableColumn>>
+    );
+
+    html!(
+        <Table<ShooterListTableColumn,UseTableData<ShooterListTableColumn,UseStateTableModel<ShooterRow>>>
+            mode={TableMode::Compact}
+            {header}
+            {entries}
+            {onexpand}
+        />
+    )
+}
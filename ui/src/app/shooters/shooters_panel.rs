@@ -1,11 +1,15 @@
 use std::rc::Rc;
 
+use patternfly_yew::prelude::*;
 use shared_types::response::League;
 use uuid::Uuid;
 use yew::prelude::*;
-use yew_nested_router::prelude::{
-    Switch as RouterSwitch,
-    *,
+use yew_nested_router::{
+    components::Link,
+    prelude::{
+        Switch as RouterSwitch,
+        *,
+    },
 };
 
 use crate::app::{
@@ -40,7 +44,25 @@ pub fn shooters_panel(props: &ShootersPanelProps) -> Html {
 
 fn switch_shooters_panel(league_id: Uuid, target: ShootersRoute) -> Html {
     let route = match target {
-        ShootersRoute::Index => html!(<ShootersListPanel {league_id} />),
+        ShootersRoute::Index => {
+            html!(
+                <>
+                    <Content>
+                        <Link<ShootersRoute> to={ShootersRoute::Create}>
+                            <Button
+                                variant={ButtonVariant::Primary}
+                                label="Create Shooter"
+                                icon={Icon::PlusCircle}
+                                align={Align::Start}
+                            />
+                        </Link<ShootersRoute>>
+                    </Content>
+                    <Content>
+                        <ShootersListPanel {league_id} />
+                    </Content>
+                </>
+            )
+        }
         ShootersRoute::Create => html!(<ShootersCreatePanel {league_id} />),
         ShootersRoute::Detail {
             shooter_id,
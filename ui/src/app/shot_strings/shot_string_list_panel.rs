@@ -4,8 +4,11 @@ use patternfly_yew::prelude::*;
 use shared_types::response::{
     League,
     Match,
+    Shooter,
+    ShooterProposal,
     ShotMarkerShotString,
 };
+use uuid::Uuid;
 use yew::{
     prelude::*,
     suspense::use_future,
@@ -17,6 +20,7 @@ use yew_nested_router::{
 
 use crate::app::{
     matches::MatchRoute,
+    shooters::fetch_shooters,
     shot_strings::{
         ShotStringRoute,
         fetch_shot_strings,
@@ -37,19 +41,23 @@ pub fn shot_string_list_panel(props: &ShotStringListPanelProps) -> HtmlResult {
     let match_id = match_object.id;
     let shot_strings_result =
         use_future(|| async move { fetch_shot_strings(league_id, match_id).await })?;
+    let shooters_result = use_future(|| async move { fetch_shooters(league_id).await })?;
 
-    let html_result = match &*shot_strings_result {
-        Ok(shot_strings) => {
+    let html_result = match (&*shot_strings_result, &*shooters_result) {
+        (Ok(shot_strings), Ok(shooters)) => {
             let shot_strings = Rc::new(shot_strings.clone());
+            let shooters = Rc::new(shooters.clone());
             html!(
-                <Scope<MatchRoute,ShotStringRoute>
-                    mapper={move |_| MatchRoute::mapper_shot_string(match_id)}
-                >
-                    <ShotStringListTable {shot_strings} />
-                </Scope<MatchRoute,ShotStringRoute>>
+                <ContextProvider<Rc<Vec<Shooter>>> context={shooters}>
+                    <Scope<MatchRoute,ShotStringRoute>
+                        mapper={move |_| MatchRoute::mapper_shot_string(match_id)}
+                    >
+                        <ShotStringListTable {shot_strings} />
+                    </Scope<MatchRoute,ShotStringRoute>>
+                </ContextProvider<Rc<Vec<Shooter>>>>
             )
         }
-        Err(e) => {
+        (Err(e), _) | (_, Err(e)) => {
             html!(
                 <Content>
                     { format!("Error: {e}") }
@@ -65,7 +73,9 @@ pub fn shot_string_list_panel(props: &ShotStringListPanelProps) -> HtmlResult {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ShotStringListTableColumn {
     Distance,
+    Review,
     Score,
+    Shooter,
     StringDate,
     StringName,
     Target,
@@ -88,14 +98,32 @@ impl TableEntryRenderer<ShotStringListTableColumn> for ShotMarkerShotString {
                 )
                 .into()
             }
-            ShotStringListTableColumn::Target => html!(self.target.clone()).into(),
+            ShotStringListTableColumn::Target => html!(self.target.to_string()).into(),
             ShotStringListTableColumn::Distance => html!(self.distance.to_string()).into(),
             ShotStringListTableColumn::Score => html!(self.score.to_string()).into(),
+            ShotStringListTableColumn::Shooter => {
+                html!(
+                    <ShotStringShooter
+                        shooter_id={self.shooter_id}
+                        proposal={self.shooter_proposal.clone()}
+                    />
+                )
+                .into()
+            }
+            ShotStringListTableColumn::Review => {
+                let findings = self.validation_findings.iter().flatten();
+                html!(
+                    <>
+                        { for findings.map(|finding| html!(<p>{ finding.to_string() }</p>)) }
+                    </>
+                )
+                .into()
+            }
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Properties)]
+#[derive(Debug, Clone, PartialEq, Properties)]
 struct ShotStringListTableProps {
     pub shot_strings: Rc<Vec<ShotMarkerShotString>>,
 }
@@ -126,6 +154,17 @@ fn shot_string_list_table(props: &ShotStringListTableProps) -> Html {
                 ShotStringListTableColumn::Score => {
                     entries_sorted.sort_by_key(|val| val.score.clone())
                 }
+                ShotStringListTableColumn::Shooter => {
+                    entries_sorted.sort_by_key(|val| {
+                        (
+                            val.shooter_id,
+                            val.shooter_proposal.as_ref().map(|proposal| proposal.shooter_id),
+                        )
+                    })
+                }
+                ShotStringListTableColumn::Review => {
+                    entries_sorted.sort_by_key(|val| val.validation_findings.as_ref().map(Vec::len))
+                }
             }
 
             if matches!(column.order, Order::Descending) {
@@ -146,6 +185,13 @@ fn shot_string_list_table(props: &ShotStringListTableProps) -> Html {
                 index={ShotStringListTableColumn::StringName}
                 onsort={onsort.clone()}
             />
+            <TableColumn<ShotStringListTableColumn>
+                label="Shooter"
+                width={ColumnWidth::FitContent}
+                text_modifier={TextModifier::NoWrap}
+                index={ShotStringListTableColumn::Shooter}
+                onsort={onsort.clone()}
+            />
             <TableColumn<ShotStringListTableColumn>
                 label="Target"
                 width={ColumnWidth::FitContent}
@@ -167,6 +213,12 @@ fn shot_string_list_table(props: &ShotStringListTableProps) -> Html {
                 index={ShotStringListTableColumn::Score}
                 onsort={onsort.clone()}
             />
+            <TableColumn<ShotStringListTableColumn>
+                label="Review"
+                width={ColumnWidth::FitContent}
+                index={ShotStringListTableColumn::Review}
+                onsort={onsort.clone()}
+            />
             <TableColumn<ShotStringListTableColumn>
                 label="Date"
                 width={ColumnWidth::FitContent}
@@ -185,3 +237,38 @@ fn shot_string_list_table(props: &ShotStringListTableProps) -> Html {
         />
     )
 }
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct ShotStringShooterProps {
+    pub shooter_id: Option<Uuid>,
+    pub proposal:   Option<ShooterProposal>,
+}
+
+/// The shooter a string is assigned to, or the one proposed for it from its name.
+#[function_component(ShotStringShooter)]
+fn shot_string_shooter(props: &ShotStringShooterProps) -> Html {
+    let shooters = use_context::<Rc<Vec<Shooter>>>().unwrap_or_default();
+    let shooter_name = |shooter_id: Uuid| {
+        shooters
+            .iter()
+            .find(|shooter| shooter.id == shooter_id)
+            .map(|shooter| shooter.name.clone())
+            .unwrap_or_else(|| shooter_id.to_string())
+    };
+
+    match (props.shooter_id, &props.proposal) {
+        (Some(shooter_id), _) => html!({ shooter_name(shooter_id) }),
+        (None, Some(proposal)) => {
+            html!(
+                <i>
+                    { format!(
+                        "{}? ({:.0}%)",
+                        shooter_name(proposal.shooter_id),
+                        proposal.confidence * 100.0
+                    ) }
+                </i>
+            )
+        }
+        (None, None) => html!(),
+    }
+}
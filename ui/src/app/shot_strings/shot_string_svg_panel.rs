@@ -3,9 +3,15 @@ use std::rc::Rc;
 use log::error;
 use patternfly_yew::prelude::*;
 use shared_types::response::{
+    GroupMeasurements,
+    GroupOptions,
+    GroupStatistics,
     League,
+    MILLIMETERS_PER_INCH,
     Match,
     ShotMarkerShot,
+    ShotScore,
+    TargetFace,
 };
 use uuid::Uuid;
 use yew::{
@@ -76,13 +82,18 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         label_stroke_width: f64,
     }
 
+    // Ring radii in inches, from the 600 yard F-Class face.
+    let face = TargetFace::nra_mr63fc();
+    let ring_radius =
+        |score| face.ring(score).map_or(0.0, |ring| ring.radius() / MILLIMETERS_PER_INCH);
+
     let stroke_width = 0.2;
     let label_font_size = 10.0;
     let label_stroke_width = 0.1;
     let ring_defs = [
         RingDef {
             id: "ring-5",
-            radius: 24.0,
+            radius: ring_radius(ShotScore::Numeric(5)),
             fill: "white",
             stroke: "black",
             stroke_width,
@@ -93,7 +104,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         },
         RingDef {
             id: "ring-6",
-            radius: 18.0,
+            radius: ring_radius(ShotScore::Numeric(6)),
             fill: "black",
             stroke: "white",
             stroke_width,
@@ -104,7 +115,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         },
         RingDef {
             id: "ring-7",
-            radius: 12.0,
+            radius: ring_radius(ShotScore::Numeric(7)),
             fill: "black",
             stroke: "white",
             stroke_width,
@@ -115,7 +126,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         },
         RingDef {
             id: "ring-8",
-            radius: 9.0,
+            radius: ring_radius(ShotScore::Numeric(8)),
             fill: "black",
             stroke: "white",
             stroke_width,
@@ -126,7 +137,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         },
         RingDef {
             id: "ring-9",
-            radius: 6.0,
+            radius: ring_radius(ShotScore::Numeric(9)),
             fill: "black",
             stroke: "white",
             stroke_width,
@@ -137,7 +148,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         },
         RingDef {
             id: "ring-10",
-            radius: 3.0,
+            radius: ring_radius(ShotScore::Numeric(10)),
             fill: "black",
             stroke: "white",
             stroke_width,
@@ -148,7 +159,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         },
         RingDef {
             id: "ring-x",
-            radius: 1.5,
+            radius: ring_radius(ShotScore::X),
             fill: "white",
             stroke: "black",
             stroke_width,
@@ -201,71 +212,19 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
     let mut scored_shots = Vec::new();
     let mut shot_hover_boxes = Vec::new();
     let mut shot_hover_box_css = Vec::new();
-    let mut velocities = Vec::new();
-
-    let mut min_scored_x = None;
-    let mut min_scored_y = None;
-    let mut max_scored_x = None;
-    let mut max_scored_y = None;
-    let mut min_x = None;
-    let mut min_y = None;
-    let mut max_x = None;
-    let mut max_y = None;
-
-    for shot in &*props.shot_string_shots {
-        velocities.push(shot.velocity.fps);
-
-        let shot_x = shot_x(shot, UNIT_SCALE_FACTOR);
-        // SVG and ShotMarker y-axis are inverted relative to each other.
-        let shot_y = shot_y(shot, UNIT_SCALE_FACTOR);
 
-        let curr_min_x = min_x.get_or_insert(shot_x);
-        if shot_x < *curr_min_x {
-            *curr_min_x = shot_x;
-        }
-        let curr_min_y = min_y.get_or_insert(shot_y);
-        if shot_y < *curr_min_y {
-            *curr_min_y = shot_y;
-        }
-        let curr_max_x = max_x.get_or_insert(shot_x);
-        if shot_x > *curr_max_x {
-            *curr_max_x = shot_x;
-        }
-        let curr_max_y = max_y.get_or_insert(shot_y);
-        if shot_y > *curr_max_y {
-            *curr_max_y = shot_y;
-        }
-
-        if !shot.tags.contains("sighter") {
-            let curr_min_x = min_scored_x.get_or_insert(shot_x);
-            if shot_x < *curr_min_x {
-                *curr_min_x = shot_x;
-            }
-            let curr_min_y = min_scored_y.get_or_insert(shot_y);
-            if shot_y < *curr_min_y {
-                *curr_min_y = shot_y;
-            }
-            let curr_max_x = max_scored_x.get_or_insert(shot_x);
-            if shot_x > *curr_max_x {
-                *curr_max_x = shot_x;
-            }
-            let curr_max_y = max_scored_y.get_or_insert(shot_y);
-            if shot_y > *curr_max_y {
-                *curr_max_y = shot_y;
-            }
-        }
-    }
+    let all_shots =
+        GroupStatistics::for_shots(props.shot_string_shots.as_slice(), GroupOptions::default());
+    let scored = GroupStatistics::for_shots(
+        props.shot_string_shots.as_slice(),
+        GroupOptions::scored_shots(),
+    );
 
-    let (view_left, view_top, view_width, view_height) = if let (
-        Some(min_x),
-        Some(min_y),
-        Some(max_x),
-        Some(max_y),
-    ) = (min_x, min_y, max_x, max_y)
-    {
-        let view_max_dimension = (max_x - min_x).max(max_y - min_y);
-        let view_center_x = (max_x + min_x) / 2.0;
-        let view_center_y = (max_y + min_y) / 2.0;
+    let (view_left, view_top, view_width, view_height) = if let Some(all_shots) = &all_shots {
+        let (left, top, right, bottom) = svg_bounds(&all_shots.inch, UNIT_SCALE_FACTOR);
+        let view_max_dimension = (right - left).max(bottom - top);
+        let view_center_x = (right + left) / 2.0;
+        let view_center_y = (bottom + top) / 2.0;
         let view_width = view_max_dimension * 1.5;
         let view_height = view_max_dimension * 1.5;
         let view_left = view_center_x - view_width / 2.0;
@@ -277,11 +236,10 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
     };
     let view_box = format!("{view_left} {view_top} {view_width} {view_height}");
 
-    let velocity_avg = mean(&velocities);
-    let velocity_sd = std_deviation(&velocities);
-    let velocity_min = velocities.iter().fold(f64::MAX, |acc, &v| f64::min(acc, v as f64));
-    let velocity_max = velocities.iter().fold(f64::MIN, |acc, &v| f64::max(acc, v as f64));
-    let velocity_es = velocity_max - velocity_min;
+    let velocity = all_shots.map(|all_shots| all_shots.velocity_fps).unwrap_or_default();
+    let velocity_avg = velocity.mean;
+    let velocity_sd = velocity.sd;
+    let velocity_es = velocity.extreme_spread();
 
     let (hover_box_top, hover_box_left, hover_box_width, hover_box_height) = {
         let hover_box_width = 0.20 * view_width;
@@ -308,7 +266,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         let shot_y = shot_y(shot, UNIT_SCALE_FACTOR);
         let marker_stroke_width = 0.3;
 
-        let stroke_color = if shot.tags == "sighter" {
+        let stroke_color = if shot.tags.is_sighter() {
             "DimGray"
         } else {
             "Gainsboro"
@@ -448,31 +406,54 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
         shot_hover_box_css
             .push(format!("#{css_shot_id}:hover ~ #{css_shot_hover_id} {{ display: block; }}"));
 
-        if shot.tags.contains("sighter") {
+        if shot.tags.is_sighter() || shot.tags.is_hidden() {
             sighters.push(shot_group);
         } else {
             scored_shots.push(shot_group);
         }
     }
 
-    let scored_shot_bounding_box =
-        if let (Some(min_scored_x), Some(min_scored_y), Some(max_scored_x), Some(max_scored_y)) =
-            (min_scored_x, min_scored_y, max_scored_x, max_scored_y)
-        {
-            html!(
-                <rect
-                    id="scored_shot_bounding_box"
-                    fill="DeepSkyBlue"
-                    fill-opacity="0.40"
-                    x={min_scored_x.to_string()}
-                    y={min_scored_y.to_string()}
-                    width={(max_scored_x - min_scored_x).to_string()}
-                    height={(max_scored_y - min_scored_y).to_string()}
-                />
-            )
-        } else {
-            html!()
-        };
+    let scored_shot_bounding_box = if let Some(scored) = &scored {
+        let (left, top, right, bottom) = svg_bounds(&scored.inch, UNIT_SCALE_FACTOR);
+        html!(
+            <rect
+                id="scored_shot_bounding_box"
+                fill="DeepSkyBlue"
+                fill-opacity="0.40"
+                x={left.to_string()}
+                y={top.to_string()}
+                width={(right - left).to_string()}
+                height={(bottom - top).to_string()}
+            />
+        )
+    } else {
+        html!()
+    };
+
+    let group_statistics = if let Some(scored) = &scored {
+        let measurement = |inch: f64, moa: f64| format!("{inch:.2} in. ({moa:.2} MOA)");
+        html!(
+            <DescriptionList>
+                <DescriptionGroup term="Scored shots">
+                    { scored.shot_count.to_string() }
+                </DescriptionGroup>
+                <DescriptionGroup term="Extreme spread">
+                    { measurement(scored.inch.extreme_spread, scored.moa.extreme_spread) }
+                </DescriptionGroup>
+                <DescriptionGroup term="Mean radius">
+                    { measurement(scored.inch.mean_radius, scored.moa.mean_radius) }
+                </DescriptionGroup>
+                <DescriptionGroup term="Radial SD">
+                    { measurement(scored.inch.radial_sd, scored.moa.radial_sd) }
+                </DescriptionGroup>
+                <DescriptionGroup term="CEP50">
+                    { measurement(scored.inch.cep50, scored.moa.cep50) }
+                </DescriptionGroup>
+            </DescriptionList>
+        )
+    } else {
+        html!()
+    };
 
     html!(
         <>
@@ -493,6 +474,7 @@ fn shot_string_svg(props: &ShotStringSvgProps) -> Html {
                 { for scored_shots }
                 { for shot_hover_boxes }
             </svg>
+            { group_statistics }
         </>
     )
 }
@@ -501,6 +483,16 @@ fn shot_x(shot: &ShotMarkerShot, scale_factor: f64) -> f64 { shot.position.inch.
 
 fn shot_y(shot: &ShotMarkerShot, scale_factor: f64) -> f64 { -shot.position.inch.y * scale_factor }
 
+/// The left, top, right, and bottom of a group in SVG coordinates.
+fn svg_bounds(group: &GroupMeasurements, scale_factor: f64) -> (f64, f64, f64, f64) {
+    (
+        group.x.min * scale_factor,
+        -group.y.max * scale_factor,
+        group.x.max * scale_factor,
+        -group.y.min * scale_factor,
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 fn target_ring(
     id: &str,
@@ -564,26 +556,3 @@ fn ring_label(
         </text>
     )
 }
-
-fn mean(data: &[u32]) -> f64 {
-    let sum = data.iter().sum::<u32>() as f64;
-    let count = data.len();
-
-    sum / count as f64
-}
-
-fn std_deviation(data: &[u32]) -> f64 {
-    let data_mean = mean(data);
-    let count = data.len();
-    let variance = data
-        .iter()
-        .map(|value| {
-            let diff = data_mean - (*value as f64);
-
-            diff * diff
-        })
-        .sum::<f64>()
-        / count as f64;
-
-    variance.sqrt()
-}
@@ -10,6 +10,7 @@ use shared_types::{
     response::{
         League,
         Match,
+        SmCsvExportUploaded,
     },
 };
 use wasm_bindgen_futures::JsFuture;
@@ -75,6 +76,7 @@ pub fn sm_export_upload(props: &SmExportUploadProps) -> Html {
                 async move {
                     let content = match &*content {
                         Some(files) => {
+                            let mut warnings = Vec::new();
                             for (idx, file) in files.iter().enumerate() {
                                 info!("Uploading file: {}", file.name());
                                 upload_progress.set(idx + 1);
@@ -114,10 +116,26 @@ pub fn sm_export_upload(props: &SmExportUploadProps) -> Html {
                                         response.text().await.map_err(|e| e.to_string())?,
                                     ));
                                 }
+
+                                let uploaded: SmCsvExportUploaded =
+                                    response.json().await.map_err(|e| e.to_string())?;
+                                warnings.extend(
+                                    uploaded
+                                        .warnings
+                                        .into_iter()
+                                        .map(|warning| format!("{}: {warning}", file.name())),
+                                );
                             }
 
                             drop_content_setter.set(None);
-                            Ok("File(s) uploaded successfully.".to_string())
+                            if warnings.is_empty() {
+                                Ok("File(s) uploaded successfully.".to_string())
+                            } else {
+                                Ok(format!(
+                                    "File(s) uploaded, skipping what could not be read:\n{}",
+                                    warnings.join("\n")
+                                ))
+                            }
                         }
                         None => Err("No files selected.".to_string()),
                     };